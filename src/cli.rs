@@ -0,0 +1,39 @@
+use std::{fs, io::Write};
+
+use crate::pages::runway::{self, RunwayParameters, RunwayQuery};
+
+const USAGE: &str = "Usage: aircraft-performance runway-pdf <query string> [output file]
+  e.g. aircraft-performance runway-pdf \"aircraft_type=cessna172m&is_take_off=true&headwind_kts=5&pressure_in_hg=29.92&temperature_f=59&elevation_ft=900&aircraft_weight_lbs=2300\" take-off.pdf";
+
+fn runway_pdf(args: &[String]) -> Result<(), String> {
+    let query = args.first().ok_or(USAGE)?;
+    let query: RunwayQuery = serde_urlencoded::from_str(query.trim_start_matches('?')).map_err(|err| format!("Invalid runway parameters: {err}"))?;
+    let params = RunwayParameters::try_from(&query).map_err(|errors| {
+        errors.iter().map(|error| format!("{}: {}", error.field, error.message)).collect::<Vec<_>>().join("\n")
+    })?;
+    let pdf = runway::render_pdf(&params);
+
+    match args.get(1) {
+        Some(path) => fs::write(path, pdf).map_err(|err| format!("Unable to write {path}: {err}")),
+        None => std::io::stdout().write_all(&pdf).map_err(|err| format!("Unable to write the PDF: {err}"))
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (command, args) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "runway-pdf" => runway_pdf(args),
+        _ => Err(USAGE.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_missing_command_is_the_usage() {
+        assert_eq!(run(&[]), Err(USAGE.to_string()));
+        assert_eq!(run(&[String::from("runway-pdf")]), Err(USAGE.to_string()));
+    }
+}
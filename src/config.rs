@@ -0,0 +1,267 @@
+use std::{env, fs, net::SocketAddr, path::{Path, PathBuf}, sync::OnceLock};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{data::store::{Store, STORE_FILE}, math::units::Units, pages::aircraft_pages};
+
+const DEFAULT_CONFIG_FILE: &str = "aircraft-performance.json";
+const CONFIG_FILE_ENV: &str = "AIRCRAFT_PERFORMANCE_CONFIG";
+const ENV_PREFIX: &str = "AIRCRAFT_PERFORMANCE_";
+const DEFAULT_WEATHER_URL: &str = "https://aviationweather.gov/api/data/metar";
+
+/// The settings which can be given in the config file, as AIRCRAFT_PERFORMANCE_<KEY> or as --<key-with-dashes>.
+const KEYS: [&str; 13] = [
+    "bind_address", "data_dir", "static_dir", "weather_url", "weather_cache_ttl_secs",
+    "pressure_unit", "length_unit", "temperature_unit", "weight_unit",
+    "default_aircraft", "take_off_safety_factor", "landing_safety_factor", "config"
+];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Where the server listens and finds its data, and the defaults for anything a request leaves out.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub bind_address: String,
+    pub data_dir: PathBuf,
+    pub static_dir: PathBuf,
+    pub weather_url: String,
+    /// How long fetched METARs are reused for, 0 to always fetch.
+    pub weather_cache_ttl_secs: u64,
+    pub default_units: Units,
+    pub default_aircraft: String,
+    /// Multiplies the book distance to clear a 50' obstacle before it is compared with the runway, 1.0 for none.
+    pub take_off_safety_factor: f64,
+    pub landing_safety_factor: f64
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bind_address: String::from("0.0.0.0:3030"),
+            data_dir: PathBuf::from("data"),
+            static_dir: PathBuf::from("static"),
+            weather_url: String::from(DEFAULT_WEATHER_URL),
+            weather_cache_ttl_secs: 300,
+            default_units: Units::IMPERIAL,
+            default_aircraft: String::from("cessna150j"),
+            take_off_safety_factor: 1.0,
+            landing_safety_factor: 1.0
+        }
+    }
+}
+
+fn parse<T: DeserializeOwned>(key: &str, value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .or_else(|_| serde_json::from_str(value))
+        .map_err(|_| format!("{key}: \"{value}\" is not a valid value."))
+}
+
+impl Config {
+    /// Reads the config file, then applies the environment and finally the leading `--key value` flags, each overriding the last.
+    /// Returns the arguments after the flags.
+    pub fn load(args: &[String]) -> Result<(Config, Vec<String>), Vec<String>> {
+        let (flags, rest) = split_flags(args).map_err(|error| vec![error])?;
+
+        let flag_file = flags.iter().find(|(key, _)| key == "config").map(|(_, value)| PathBuf::from(value));
+        let env_file = env::var(CONFIG_FILE_ENV).ok().map(PathBuf::from);
+        let mut config = match flag_file.or(env_file) {
+            Some(path) => Self::from_file(&path).map_err(|error| vec![error])?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => Self::from_file(Path::new(DEFAULT_CONFIG_FILE)).map_err(|error| vec![error])?,
+            None => Config::default()
+        };
+
+        let mut errors = vec![];
+        for key in KEYS.iter().filter(|key| **key != "config") {
+            if let Ok(value) = env::var(format!("{ENV_PREFIX}{}", key.to_uppercase())) {
+                if let Err(error) = config.set(key, &value) {
+                    errors.push(error);
+                }
+            }
+        }
+        for (key, value) in flags.iter().filter(|(key, _)| key != "config") {
+            if let Err(error) = config.set(key, value) {
+                errors.push(error);
+            }
+        }
+
+        errors.extend(config.validate());
+        if errors.is_empty() {
+            Ok((config, rest))
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Config, String> {
+        let json = fs::read_to_string(path).map_err(|err| format!("Unable to read the config file {}: {err}", path.display()))?;
+        serde_json::from_str(&json).map_err(|err| format!("Invalid config file {}: {err}", path.display()))
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "bind_address" => self.bind_address = value.to_string(),
+            "data_dir" => self.data_dir = PathBuf::from(value),
+            "static_dir" => self.static_dir = PathBuf::from(value),
+            "weather_url" => self.weather_url = value.to_string(),
+            "weather_cache_ttl_secs" => self.weather_cache_ttl_secs = parse(key, value)?,
+            "pressure_unit" => self.default_units.pressure_unit = parse(key, value)?,
+            "length_unit" => self.default_units.length_unit = parse(key, value)?,
+            "temperature_unit" => self.default_units.temperature_unit = parse(key, value)?,
+            "weight_unit" => self.default_units.weight_unit = parse(key, value)?,
+            "default_aircraft" => self.default_aircraft = value.to_string(),
+            "take_off_safety_factor" => self.take_off_safety_factor = parse(key, value)?,
+            "landing_safety_factor" => self.landing_safety_factor = parse(key, value)?,
+            _ => return Err(format!("Unknown setting {key}, expected one of {}.", KEYS.join(", ")))
+        }
+        Ok(())
+    }
+
+    /// Every problem with the settings, so they can all be fixed before the next start.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+
+        if self.bind_address.parse::<SocketAddr>().is_err() {
+            errors.push(format!("bind_address: \"{}\" is not an address and port such as 0.0.0.0:3030.", self.bind_address));
+        }
+        for file in ["airports.csv", "runways.csv"] {
+            let path = self.data_file(file);
+            if !path.is_file() {
+                errors.push(format!("data_dir: {} was not found.", path.display()));
+            }
+        }
+        if let Err(error) = Store::load(self.data_file(STORE_FILE)) {
+            errors.push(format!("data_dir: {error}"));
+        }
+        if !self.static_dir.is_dir() {
+            errors.push(format!("static_dir: {} is not a directory.", self.static_dir.display()));
+        }
+        if !self.weather_url.starts_with("http://") && !self.weather_url.starts_with("https://") {
+            errors.push(format!("weather_url: \"{}\" is not an http or https URL.", self.weather_url));
+        }
+        if aircraft_pages::aircraft_name(&self.default_aircraft).is_none() {
+            errors.push(format!("default_aircraft: {} is not a known aircraft type.", self.default_aircraft));
+        }
+        for (key, factor) in [("take_off_safety_factor", self.take_off_safety_factor), ("landing_safety_factor", self.landing_safety_factor)] {
+            if !(1.0..=3.0).contains(&factor) {
+                errors.push(format!("{key}: {factor} is not between 1.0 and 3.0."));
+            }
+        }
+
+        errors
+    }
+
+    pub fn data_file(&self, name: &str) -> PathBuf {
+        self.data_dir.join(name)
+    }
+
+    pub fn safety_factor(&self, is_take_off: bool) -> f64 {
+        if is_take_off { self.take_off_safety_factor } else { self.landing_safety_factor }
+    }
+}
+
+type Flags = Vec<(String, String)>;
+
+/// Splits the leading `--key value` or `--key=value` flags from the arguments that follow them.
+fn split_flags(args: &[String]) -> Result<(Flags, Vec<String>), String> {
+    let mut flags = vec![];
+    let mut args = args.iter();
+    let mut rest = vec![];
+
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            rest.push(arg.clone());
+            rest.extend(args.cloned());
+            break;
+        };
+
+        let (key, value) = match flag.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (flag.to_string(), args.next().cloned().ok_or(format!("--{flag} needs a value."))?)
+        };
+        flags.push((key.replace('-', "_"), value));
+    }
+
+    Ok((flags, rest))
+}
+
+/// The first config wins, the airports and store are loaded once from wherever it points.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// The units for anything a request leaves unchosen.
+pub fn default_units() -> Units {
+    get().default_units
+}
+
+pub fn default_aircraft() -> String {
+    get().default_aircraft.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::units::{LengthUnit, PressureUnit};
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn flags_are_split_from_the_command() {
+        let (flags, rest) = split_flags(&args(&["--bind-address", "127.0.0.1:8080", "--data-dir=fixtures", "runway-pdf", "--not-a-flag"])).unwrap();
+        assert_eq!(flags, vec![(String::from("bind_address"), String::from("127.0.0.1:8080")), (String::from("data_dir"), String::from("fixtures"))]);
+        assert_eq!(rest, args(&["runway-pdf", "--not-a-flag"]));
+    }
+
+    #[test]
+    fn a_flag_without_a_value_is_an_error() {
+        assert_eq!(split_flags(&args(&["--data-dir"])).err(), Some(String::from("--data-dir needs a value.")));
+    }
+
+    #[test]
+    fn file_settings_are_overridden_by_set() {
+        let mut config: Config = serde_json::from_str(r#"{ "bind_address": "127.0.0.1:4000", "default_units": { "pressure_unit": "hpa" }, "landing_safety_factor": 1.43 }"#).unwrap();
+        assert_eq!(config.default_units.pressure_unit, PressureUnit::Hpa);
+        assert_eq!(config.default_units.length_unit, LengthUnit::Feet);
+        assert_eq!(config.landing_safety_factor, 1.43);
+
+        config.set("bind_address", "127.0.0.1:5000").unwrap();
+        config.set("length_unit", "metres").unwrap();
+        assert_eq!(config.bind_address, "127.0.0.1:5000");
+        assert_eq!(config.default_units.length_unit, LengthUnit::Metres);
+    }
+
+    #[test]
+    fn unknown_and_invalid_settings_are_reported() {
+        assert!(serde_json::from_str::<Config>(r#"{ "bind_adress": "127.0.0.1:4000" }"#).unwrap_err().to_string().contains("unknown field `bind_adress`"));
+
+        let mut config = Config::default();
+        assert_eq!(config.set("pressure_unit", "mmhg"), Err(String::from("pressure_unit: \"mmhg\" is not a valid value.")));
+        assert!(config.set("colour", "blue").unwrap_err().starts_with("Unknown setting colour"));
+    }
+
+    #[test]
+    fn validation_lists_every_problem() {
+        let config = Config {
+            bind_address: String::from("localhost"),
+            data_dir: PathBuf::from("no-such-dir"),
+            weather_url: String::from("ftp://example.com"),
+            default_aircraft: String::from("cessna152"),
+            take_off_safety_factor: 0.9,
+            ..Config::default()
+        };
+
+        let errors = config.validate();
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert!(errors[0].starts_with("bind_address:"));
+        assert!(errors.iter().any(|error| error == "default_aircraft: cessna152 is not a known aircraft type."));
+        assert!(errors.iter().any(|error| error == "take_off_safety_factor: 0.9 is not between 1.0 and 3.0."));
+    }
+}
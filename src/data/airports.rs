@@ -3,6 +3,8 @@ use lazy_static::lazy_static;
 use serde::Deserialize;
 use std::{collections::HashMap, fs::File};
 
+use crate::{config, math::{geo::Coordinates, magnetic, Length}};
+
 lazy_static! {
     pub static ref AIRPORTS: HashMap<String, Airport> = load_airports();
 }
@@ -14,32 +16,112 @@ fn default_if_empty<'de, D, T>(de: D) -> Result<T, D::Error> where
     Option::<T>::deserialize(de).map(|x| x.unwrap_or_else(|| T::default()))
 } 
 
+/// The database marks flags as 1 or 0.
+fn flag<'de, D>(de: D) -> Result<bool, D::Error> where
+    D: serde::Deserializer<'de>,
+{
+    Option::<u8>::deserialize(de).map(|x| x.is_some_and(|value| value != 0))
+}
+
+/// Runways narrower than this leave less room to correct for a crosswind.
+pub const NARROW_RUNWAY_WIDTH_FT: u16 = 50;
+
+/// Where a runway end's heading came from, the coordinates and cardinal idents are inferences to be checked against a chart.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HeadingSource {
+    /// The true heading recorded in the database.
+    Surveyed,
+    RunwayNumber,
+    /// The great circle bearing between the thresholds.
+    Coordinates,
+    CardinalDirection,
+    #[default]
+    Unknown
+}
+
+impl HeadingSource {
+    pub fn is_inferred(&self) -> bool {
+        matches!(self, HeadingSource::Coordinates | HeadingSource::CardinalDirection)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            HeadingSource::Surveyed => "surveyed",
+            HeadingSource::RunwayNumber => "from the runway number",
+            HeadingSource::Coordinates => "inferred from the threshold coordinates",
+            HeadingSource::CardinalDirection => "inferred from the cardinal direction",
+            HeadingSource::Unknown => "unknown"
+        }
+    }
+}
+
+/// What a runway row is for, only airplane runways (and water lanes for a seaplane) are offered for calculations.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RunwayKind {
+    Airplane,
+    Helipad,
+    Water,
+    /// Neither the ident nor the coordinates give a heading.
+    #[default]
+    Unknown
+}
+
 #[derive(Deserialize)]
 pub struct Runway {
     pub airport_ref: u32,
     #[serde(rename = "length_ft", deserialize_with = "default_if_empty")]
     pub length: u16,
+    /// The length was missing and is the distance between the thresholds.
+    #[serde(skip)]
+    pub is_length_inferred: bool,
+    /// 0 when the width is not recorded.
+    #[serde(rename = "width_ft", deserialize_with = "default_if_empty")]
+    pub width: u16,
     pub surface: String,
+    #[serde(deserialize_with = "flag")]
+    pub lighted: bool,
+    #[serde(deserialize_with = "flag")]
+    pub closed: bool,
     pub le_ident: String,
+    #[serde(rename = "le_latitude_deg")]
+    le_latitude: Option<f64>,
+    #[serde(rename = "le_longitude_deg")]
+    le_longitude: Option<f64>,
     #[serde(rename = "le_elevation_ft", deserialize_with = "default_if_empty")]
     pub le_elevation: i16,
     #[serde(rename = "le_heading_degT", deserialize_with = "default_if_empty")]
     le_heading_raw: f32,
+    /// Magnetic, as the runway is numbered.
     #[serde(skip)]
     pub le_heading: u16,
+    /// True, as METAR winds are reported.
+    #[serde(skip)]
+    pub le_true_heading: u16,
+    #[serde(skip)]
+    pub le_heading_source: HeadingSource,
     #[serde(rename = "le_displaced_threshold_ft", deserialize_with = "default_if_empty")]
     pub le_displaced_threshold: u16,
     pub he_ident: String,
+    #[serde(rename = "he_latitude_deg")]
+    he_latitude: Option<f64>,
+    #[serde(rename = "he_longitude_deg")]
+    he_longitude: Option<f64>,
     #[serde(rename = "he_elevation_ft", deserialize_with = "default_if_empty")]
     pub he_elevation: i16,
     #[serde(rename = "he_heading_degT", deserialize_with = "default_if_empty")]
     he_heading_raw: f32,
     #[serde(skip)]
     pub he_heading: u16,
+    #[serde(skip)]
+    pub he_true_heading: u16,
+    #[serde(skip)]
+    pub he_heading_source: HeadingSource,
     #[serde(rename = "he_displaced_threshold_ft", deserialize_with = "default_if_empty")]
     pub he_displaced_threshold: u16,
     #[serde(skip)]
-    pub is_grass: bool
+    pub is_grass: bool,
+    #[serde(skip)]
+    pub kind: RunwayKind
 }
 
 #[derive(Deserialize)]
@@ -53,10 +135,84 @@ pub struct Airport {
     pub longitude: f64,
     #[serde(rename = "elevation_ft", deserialize_with = "default_if_empty")]
     pub elevation: i16,
+    /// Degrees east of true north that magnetic north lies today, from the World Magnetic Model.
+    #[serde(skip)]
+    pub magnetic_variation: f64,
     #[serde(skip)]
     pub runways: Vec<Runway>
 }
 
+pub struct RunwayEnd<'a> {
+    pub runway: &'a Runway,
+    pub ident: &'a str,
+    pub heading: u16,
+    pub true_heading: u16,
+    pub heading_source: HeadingSource,
+    pub elevation: i16,
+    pub displaced_threshold: u16
+}
+
+impl Runway {
+    pub fn le_coordinates(&self) -> Option<Coordinates> {
+        Some(Coordinates { latitude: self.le_latitude?, longitude: self.le_longitude? })
+    }
+
+    pub fn he_coordinates(&self) -> Option<Coordinates> {
+        Some(Coordinates { latitude: self.he_latitude?, longitude: self.he_longitude? })
+    }
+
+    /// Helipads and runways without a heading are never offered, water lanes only to seaplanes.
+    pub fn is_usable(&self, is_seaplane: bool) -> bool {
+        match self.kind {
+            RunwayKind::Airplane => true,
+            RunwayKind::Water => is_seaplane,
+            RunwayKind::Helipad | RunwayKind::Unknown => false
+        }
+    }
+
+    pub fn is_narrow(&self) -> bool {
+        self.width > 0 && self.width < NARROW_RUNWAY_WIDTH_FT
+    }
+
+    pub fn ends(&self) -> [RunwayEnd<'_>; 2] {
+        [
+            RunwayEnd { runway: self, ident: &self.le_ident, heading: self.le_heading, true_heading: self.le_true_heading, heading_source: self.le_heading_source, elevation: self.le_elevation, displaced_threshold: self.le_displaced_threshold },
+            RunwayEnd { runway: self, ident: &self.he_ident, heading: self.he_heading, true_heading: self.he_true_heading, heading_source: self.he_heading_source, elevation: self.he_elevation, displaced_threshold: self.he_displaced_threshold }
+        ]
+    }
+}
+
+impl Airport {
+    pub fn find_runway_end<S: AsRef<str>>(&self, ident: S) -> Option<RunwayEnd<'_>> {
+        let ident_ref = ident.as_ref();
+        self.runways.iter()
+            .flat_map(|runway| runway.ends())
+            .find(|end| end.ident.eq_ignore_ascii_case(ident_ref))
+    }
+
+    /// The runway end for planning, a closed runway or one the aircraft cannot use is reported as such rather than used.
+    pub fn find_open_runway_end<S: AsRef<str>>(&self, ident: S, is_seaplane: bool) -> Result<RunwayEnd<'_>, String> {
+        let ident_ref = ident.as_ref();
+        let end = self.find_runway_end(ident_ref).ok_or(format!("Runway {ident_ref} not found at {}", self.ident))?;
+
+        match end.runway.kind {
+            _ if end.runway.closed => Err(format!("Runway {} at {} is closed", end.ident, self.ident)),
+            RunwayKind::Helipad => Err(format!("{} at {} is a helipad", end.ident, self.ident)),
+            RunwayKind::Water if !is_seaplane => Err(format!("Runway {} at {} is a water lane and the aircraft is not a seaplane", end.ident, self.ident)),
+            RunwayKind::Unknown => Err(format!("Runway {} at {} has no known heading", end.ident, self.ident)),
+            _ => Ok(end)
+        }
+    }
+
+    pub fn usable_runways(&self, is_seaplane: bool) -> Vec<&Runway> {
+        self.runways.iter().filter(|runway| runway.is_usable(is_seaplane)).collect()
+    }
+
+    pub fn formatted_variation(&self) -> String {
+        magnetic::format_variation(self.magnetic_variation)
+    }
+}
+
 fn heading_from_runway_number(str: &String) -> u16 {
     match atoi::atoi::<u16>(str.as_bytes()) {
         Some(value) => value * 10,
@@ -64,46 +220,94 @@ fn heading_from_runway_number(str: &String) -> u16 {
     }
 }
 
+fn heading_from_cardinal_direction(str: &str) -> u16 {
+    match str {
+        "N" => 360,
+        "NE" => 45,
+        "E" => 90,
+        "SE" => 135,
+        "S" => 180,
+        "SW" => 225,
+        "W" => 270,
+        "NW" => 315,
+        _ => 0
+    }
+}
+
+/// The magnetic and true headings of a runway end and where they came from. The surveyed true heading is preferred,
+/// the runway number is only to the nearest 10° magnetic. Without either the bearing between the thresholds is used, then a cardinal ident such as N or SE.
+fn headings(ident: &String, true_heading_raw: f32, coordinates_heading: Option<f64>, variation: f64) -> (u16, u16, HeadingSource) {
+    let from_true = |true_heading: f64, source| (magnetic::normalize_heading(true_heading - variation), magnetic::normalize_heading(true_heading), source);
+    let from_magnetic = |magnetic_heading: u16, source| (magnetic_heading, magnetic::normalize_heading(magnetic_heading as f64 + variation), source);
+
+    if true_heading_raw > 0.0 {
+        return from_true(true_heading_raw as f64, HeadingSource::Surveyed);
+    }
+
+    match (heading_from_runway_number(ident), coordinates_heading, heading_from_cardinal_direction(ident)) {
+        (0, Some(true_heading), _) => from_true(true_heading, HeadingSource::Coordinates),
+        (0, None, 0) => (0, 0, HeadingSource::Unknown),
+        (0, None, heading) => from_magnetic(heading, HeadingSource::CardinalDirection),
+        (heading, _, _) => from_magnetic(heading, HeadingSource::RunwayNumber)
+    }
+}
+
+fn classify(runway: &Runway) -> RunwayKind {
+    let ident = runway.le_ident.to_uppercase();
+    let surface = runway.surface.to_uppercase();
+    let is_numbered_with_suffix = |suffix: char| ident.len() > 1 && ident.ends_with(suffix) && ident[..ident.len() - 1].chars().all(|c| c.is_ascii_digit());
+
+    if (ident.starts_with('H') && ident[1..].chars().all(|c| c.is_ascii_digit())) || surface.contains("HELI") {
+        RunwayKind::Helipad
+    } else if surface.contains("WAT") || is_numbered_with_suffix('W') {
+        RunwayKind::Water
+    } else if runway.le_heading != 0 {
+        RunwayKind::Airplane
+    } else {
+        RunwayKind::Unknown
+    }
+}
+
 pub fn load_airports() -> HashMap<String, Airport> {
     let mut runways = HashMap::new();
-    let mut runway_rdr = csv::Reader::from_reader(File::open("data/runways.csv").expect("To open the runways db"));
+    let mut runway_rdr = csv::Reader::from_reader(File::open(config::get().data_file("runways.csv")).expect("To open the runways db"));
     for result in runway_rdr.deserialize() {
         let mut runway: Runway = result.expect("To deserialize a runway");
-
-        if runway.le_ident != "" && runway.he_ident != "" {
-            runway.le_heading = heading_from_runway_number(&runway.le_ident);
-            runway.he_heading = heading_from_runway_number(&runway.he_ident);
-        }
-
-        if runway.le_heading == 0 {
-            runway.le_heading = runway.le_heading_raw.round() as u16;
-            runway.he_heading = runway.he_heading_raw.round() as u16;
-        }
-
         runway.is_grass = runway.surface.contains("GRASS") || runway.surface.contains("TURF") || runway.surface.contains("SOD") || runway.surface.contains("GRS");
 
-        if !runways.contains_key(&runway.airport_ref) {
-            runways.insert(runway.airport_ref, Vec::<Runway>::new());
-        }
-
-        let vec = runways.get_mut(&runway.airport_ref).unwrap();
-        vec.push(runway);
+        runways.entry(runway.airport_ref).or_insert_with(Vec::<Runway>::new).push(runway);
     }
 
     let mut airports = HashMap::new();
-    let mut airport_rdr = csv::Reader::from_reader(File::open("data/airports.csv").expect("To open the airports db"));
+    let year = magnetic::current_year();
+    let mut airport_rdr = csv::Reader::from_reader(File::open(config::get().data_file("airports.csv")).expect("To open the airports db"));
     for result in airport_rdr.deserialize() {
         let mut airport: Airport = result.expect("To get the airport data from the row");
 
         if runways.contains_key(&airport.id) {
             let mut runways = runways.remove(&airport.id).unwrap(); 
-            for i in 0..runways.len() {
-                if runways[i].le_elevation == 0 {
-                    runways[i].le_elevation = airport.elevation;
+            airport.magnetic_variation = magnetic::declination(airport.latitude, airport.longitude, Length::Feet(airport.elevation as f64), year);
+            for runway in runways.iter_mut() {
+                let thresholds = runway.le_coordinates().zip(runway.he_coordinates());
+                let le_bearing = thresholds.map(|(le, he)| le.bearing_to(&he));
+                (runway.le_heading, runway.le_true_heading, runway.le_heading_source) = headings(&runway.le_ident, runway.le_heading_raw, le_bearing, airport.magnetic_variation);
+                (runway.he_heading, runway.he_true_heading, runway.he_heading_source) = headings(&runway.he_ident, runway.he_heading_raw, le_bearing.map(|bearing| bearing + 180.0), airport.magnetic_variation);
+
+                if runway.length == 0 {
+                    if let Some((le, he)) = thresholds {
+                        runway.length = le.distance_to(&he).feet().round() as u16;
+                        runway.is_length_inferred = true;
+                    }
+                }
+
+                runway.kind = classify(runway);
+
+                if runway.le_elevation == 0 {
+                    runway.le_elevation = airport.elevation;
                 }
 
-                if runways[i].he_elevation == 0 {
-                    runways[i].he_elevation = airport.elevation;
+                if runway.he_elevation == 0 {
+                    runway.he_elevation = airport.elevation;
                 }
             }
 
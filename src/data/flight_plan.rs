@@ -0,0 +1,52 @@
+use crate::math::PressureAltitudeMethod;
+
+pub struct PlannedRunway {
+    pub identifier: String,
+    pub runway: String,
+    pub metar: Option<String>
+}
+
+impl PlannedRunway {
+    pub fn new(identifier: &str, runway: &str, metar: Option<String>) -> Self {
+        PlannedRunway {
+            identifier: identifier.trim().to_uppercase(),
+            runway: runway.trim().to_uppercase(),
+            metar: metar.filter(|metar| !metar.trim().is_empty())
+        }
+    }
+
+    /// Parses a comma separated list of `airport/runway` pairs, e.g. `KFCM/28R, KSTP/32`.
+    pub fn parse_list(text: &str) -> Result<Vec<PlannedRunway>, String> {
+        text.split(',')
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('/') {
+                Some((identifier, runway)) => Ok(PlannedRunway::new(identifier, runway, None)),
+                None => Err(format!("Expected airport/runway but found \"{entry}\"."))
+            })
+            .collect()
+    }
+}
+
+pub struct FlightPlan {
+    pub aircraft_type: String,
+    pub tail_number: Option<String>,
+    pub take_off_weight_lbs: i16,
+    pub fuel_burn_lbs: i16,
+    pub alternate_fuel_burn_lbs: i16,
+    pub destination_landing_weight_lbs: i16,
+    pub alternate_landing_weight_lbs: i16,
+    pub departure: PlannedRunway,
+    pub destination: PlannedRunway,
+    pub alternates: Vec<PlannedRunway>,
+    pub pressure_altitude_method: PressureAltitudeMethod
+}
+
+/// The weight after burning the fuel, none when the burn is negative or is all of the weight.
+pub fn landing_weight_lbs(weight_lbs: i16, fuel_burn_lbs: i16) -> Option<i16> {
+    if fuel_burn_lbs < 0 {
+        return None;
+    }
+
+    weight_lbs.checked_sub(fuel_burn_lbs).filter(|landing_weight_lbs| *landing_weight_lbs > 0)
+}
@@ -1,2 +1,5 @@
 pub mod performance;
-pub mod airports;
\ No newline at end of file
+pub mod airports;
+pub mod flight_plan;
+pub mod obstacles;
+pub mod store;
\ No newline at end of file
@@ -0,0 +1,85 @@
+use lazy_static::lazy_static;
+
+use serde::Deserialize;
+use std::{collections::HashMap, fs::File};
+
+use crate::config;
+
+/// The obstacle file is optional, without it only the obstacles entered with a calculation are checked.
+pub const OBSTACLES_FILE: &str = "obstacles.csv";
+
+lazy_static! {
+    static ref OBSTACLES: HashMap<(String, String), Vec<Obstacle>> = load_obstacles();
+}
+
+/// Something to out-climb on the departure path, the height is above the departure end of the runway
+/// and the distance is measured from the departure end along the extended centerline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Obstacle {
+    pub name: String,
+    pub height_ft: f64,
+    pub distance_ft: f64
+}
+
+#[derive(Deserialize)]
+struct ObstacleRow {
+    airport_ident: String,
+    runway_ident: String,
+    name: String,
+    height_ft: f64,
+    distance_ft: f64
+}
+
+fn load_obstacles() -> HashMap<(String, String), Vec<Obstacle>> {
+    let mut obstacles = HashMap::new();
+    let Ok(file) = File::open(config::get().data_file(OBSTACLES_FILE)) else {
+        return obstacles;
+    };
+
+    for result in csv::Reader::from_reader(file).deserialize() {
+        let row: ObstacleRow = result.expect("To deserialize an obstacle");
+        let obstacle = Obstacle { name: row.name, height_ft: row.height_ft, distance_ft: row.distance_ft };
+        obstacles.entry((row.airport_ident.to_uppercase(), row.runway_ident.to_uppercase())).or_insert_with(Vec::new).push(obstacle);
+    }
+
+    obstacles
+}
+
+/// The obstacles in the obstacle file for departures from the runway end.
+pub fn find(airport_ident: &str, runway_ident: &str) -> Vec<Obstacle> {
+    OBSTACLES.get(&(airport_ident.to_uppercase(), runway_ident.to_uppercase())).cloned().unwrap_or_default()
+}
+
+/// Parses a comma separated list of `height@distance` in feet, each optionally preceded by a name, e.g. `Trees 60@800, Tower 200@3500`.
+pub fn parse_list(text: &str) -> Result<Vec<Obstacle>, String> {
+    text.split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, measurements) = entry.rsplit_once(char::is_whitespace).unwrap_or(("", entry));
+            let (height, distance) = measurements.split_once('@').ok_or(format!("Expected height@distance but found \"{entry}\"."))?;
+            let parse = |value: &str| value.trim().trim_end_matches('\'').parse::<f64>().ok().filter(|value| *value >= 0.0);
+
+            match (parse(height), parse(distance)) {
+                (Some(height_ft), Some(distance_ft)) => Ok(Obstacle { name: name.trim().to_string(), height_ft, distance_ft }),
+                _ => Err(format!("Expected the height and distance in feet but found \"{entry}\"."))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_named_and_unnamed_obstacles() {
+        let obstacles = parse_list("Trees 60@800, 200'@3500,").unwrap();
+        assert_eq!(obstacles, vec![
+            Obstacle { name: String::from("Trees"), height_ft: 60.0, distance_ft: 800.0 },
+            Obstacle { name: String::new(), height_ft: 200.0, distance_ft: 3500.0 }
+        ]);
+        assert!(parse_list("Tower 200").is_err());
+        assert!(parse_list("Tower -5@100").is_err());
+    }
+}
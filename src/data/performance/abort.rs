@@ -0,0 +1,67 @@
+use super::distance::Distance;
+
+/// The rule of thumb: abandon the take off if 70% of the rotation speed has not been reached by the runway midpoint.
+pub const ABORT_SPEED_FRACTION: f64 = 0.7;
+pub const ABORT_RUNWAY_FRACTION: f64 = 0.5;
+
+/// Where to abandon a take off, all distances are from the start of the take off run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AbortPoint {
+    pub runway_length_ft: f64,
+    pub abort_speed_kias: f64,
+    /// The runway midpoint, by which the abort speed must have been reached.
+    pub marker_ft: f64,
+    /// Where the book ground run reaches the abort speed. The speed squared grows with the distance under a steady acceleration.
+    pub expected_ft: f64,
+    pub ground_run_ft: f64,
+    pub stop_ft: f64
+}
+
+impl AbortPoint {
+    /// The stop is the landing ground roll, which starts from a touchdown speed close to the rotation speed.
+    pub fn new(rotation_speed_kias: f64, runway_length_ft: f64, take_off: Distance, landing: Distance) -> Self {
+        AbortPoint {
+            runway_length_ft,
+            abort_speed_kias: rotation_speed_kias * ABORT_SPEED_FRACTION,
+            marker_ft: runway_length_ft * ABORT_RUNWAY_FRACTION,
+            expected_ft: take_off.ground_run_ft() * ABORT_SPEED_FRACTION.powi(2),
+            ground_run_ft: take_off.ground_run_ft(),
+            stop_ft: landing.ground_run_ft()
+        }
+    }
+
+    /// The distance to accelerate to the rotation speed and then stop again.
+    pub fn accelerate_stop_ft(&self) -> f64 {
+        self.ground_run_ft + self.stop_ft
+    }
+
+    pub fn is_abort_speed_reached(&self) -> bool {
+        self.expected_ft <= self.marker_ft
+    }
+
+    pub fn is_accelerate_stop_within_runway(&self) -> bool {
+        self.accelerate_stop_ft().round() <= self.runway_length_ft
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 0.01;
+
+    #[test]
+    fn abort_point_follows_the_70_50_rule() {
+        let abort_point = AbortPoint::new(55.0, 3000.0, Distance(1000.0, 1800.0), Distance(600.0, 1300.0));
+        assert!((abort_point.abort_speed_kias - 38.5).abs() < TOLERANCE);
+        assert!((abort_point.marker_ft - 1500.0).abs() < TOLERANCE);
+        assert!((abort_point.expected_ft - 490.0).abs() < TOLERANCE);
+        assert!((abort_point.accelerate_stop_ft() - 1600.0).abs() < TOLERANCE);
+        assert!(abort_point.is_abort_speed_reached());
+        assert!(abort_point.is_accelerate_stop_within_runway());
+
+        let abort_point = AbortPoint::new(55.0, 1500.0, Distance(1600.0, 2400.0), Distance(600.0, 1300.0));
+        assert!(!abort_point.is_abort_speed_reached());
+        assert!(!abort_point.is_accelerate_stop_within_runway());
+    }
+}
@@ -1,180 +1,201 @@
+use lazy_static::lazy_static;
+
 use crate::{
-    data::performance::{atmosphere_bounds::{AtmosphereBounds, AtmosphereDef}, 
-    distance::Distance, headwinds::Headwinds, performance_row::PerformanceRow}, 
-    math::{FloatingCalcs, Velocity}
+    data::performance::{atmosphere_bounds::{AtmosphereBounds, AtmosphereDef}, climb::Climb, configuration::{Configuration, ConfigurationGroup},
+    distance::Distance, error::PerformanceError, grid::{Axis, Extrapolation, Grid, Lookup}, headwinds::Headwinds, limits::ChartLimits, performance_row::PerformanceRow, speeds::{SpeedKind, SpeedTable}},
+    math::{Length, Temperature, Velocity}
 };
 
-fn find_headwind_performance_keys(headwind_kts: i16) -> Result<Headwinds, &'static str> {
-    let lower_value = (headwind_kts / 10) * 10;
-    let upper_value = lower_value + 10;    
+const ALTITUDES_FT: [f64; 4] = [0.0, 2500.0, 5000.0, 7500.0];
+const STANDARD_TEMPERATURES_F: [i16; 4] = [59, 50, 41, 32];
+const HEADWINDS_KTS: [f64; 3] = [0.0, 10.0, 20.0];
 
-    if lower_value < 0 || upper_value > 20  {
-        Err("Unable to calculate, performance not defined")
-    } else {
+/// The charts are for the 1600 lbs gross weight and give no tailwind correction. Elevations above the charts are extrapolated.
+pub const LIMITS: ChartLimits = ChartLimits {
+    max_weight_lbs: 1600,
+    take_off_by_weight: false,
+    by_pressure_altitude: false,
+    min_altitude_ft: Some(ALTITUDES_FT[0]),
+    max_altitude_ft: None,
+    max_temperature_c: None,
+    min_headwind_kts: HEADWINDS_KTS[0],
+    max_take_off_headwind_kts: Some(HEADWINDS_KTS[2])
+};
 
-        Ok(Headwinds {
-            lower_value: Velocity::Knots(lower_value),
-            upper_value: Velocity::Knots(upper_value)
-        }) 
-    }
-}
+const TAKE_OFF: [[Distance; 3]; 4] = [
+    [Distance(735.0, 1385.0),  Distance(500.0, 1035.0), Distance(305.0, 730.0)],
+    [Distance(910.0, 1660.0),  Distance(630.0, 1250.0), Distance(395.0, 890.0)],
+    [Distance(1115.0, 1985.0), Distance(780.0, 1510.0), Distance(505.0, 1090.0)],
+    [Distance(1360.0, 2440.0), Distance(970.0, 1875.0), Distance(640.0, 1375.0)]
+];
+
+const LANDING: [Distance; 4] = [Distance(445.0, 1075.0), Distance(470.0, 1135.0), Distance(495.0, 1195.0), Distance(520.0, 1255.0)];
+
+/// The take off chart is for flaps 0° and the landing chart for flaps 40° with power off.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+    Configuration { id: "short_field", name: "Short field, flaps 0° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.1, air_distance_factor: 1.1 },
+    Configuration { id: "full", name: "Flaps 40° (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.2, air_distance_factor: 1.3 },
+    Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.35, air_distance_factor: 1.45 },
+    Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
+
+/// The POH speeds at 1600 lbs, converted from its mph IAS.
+pub const SPEEDS: SpeedTable = SpeedTable {
+    weights_lbs: &[1300.0, 1600.0],
+    speeds_kias: &[
+        (SpeedKind::Vr, &[39.0, 43.0]),
+        (SpeedKind::Vx, &[50.0, 55.0]),
+        (SpeedKind::Vy, &[57.0, 63.0]),
+        (SpeedKind::Vref, &[47.0, 52.0]),
+        (SpeedKind::Vs0, &[38.0, 42.0]),
+        (SpeedKind::Vs1, &[43.0, 48.0]),
+        (SpeedKind::Vg, &[55.0, 61.0])
+    ]
+};
 
-#[derive(Clone, Copy)]
-pub enum Atmosphere {
-    Alt0_59F = 0,
-    Alt2500_50F = 1,
-    Alt5000_41F = 2,
-    Alt7500_32F = 3
+/// The maximum rate of climb chart at 1600 lbs, flown at the charted IAS in mph. Rate of climb is decreased 15 fpm for each 10°F above standard.
+const CLIMB_ALTITUDES_FT: [f64; 4] = [0.0, 5000.0, 10000.0, 15000.0];
+const CLIMB_RATES_FPM: [f64; 4] = [670.0, 440.0, 220.0, -5.0];
+const CLIMB_SPEEDS_MPH: [f64; 4] = [73.0, 68.0, 63.0, 58.0];
+
+fn altitude_axis() -> Axis {
+    Axis::new("altitude", "'", &ALTITUDES_FT).extrapolate(Extrapolation::Error, Extrapolation::LinearWithWarning)
 }
 
-const ALT0_59F: AtmosphereDef<Atmosphere> = AtmosphereDef { altitude: 0, temperature: 59, indexer: Atmosphere::Alt0_59F };
-const ALT2500_50F: AtmosphereDef<Atmosphere> = AtmosphereDef { altitude: 2500, temperature: 50, indexer: Atmosphere::Alt2500_50F };
-const ALT5000_41F: AtmosphereDef<Atmosphere> = AtmosphereDef { altitude: 5000, temperature: 41, indexer: Atmosphere::Alt5000_41F };
-const ALT7500_32F: AtmosphereDef<Atmosphere> = AtmosphereDef { altitude: 7500, temperature: 32, indexer: Atmosphere::Alt7500_32F };
-
-fn find_atmosphere(altitude_ft: i16) -> Result<AtmosphereBounds<Atmosphere>, &'static str> {
-    if altitude_ft == 7500 {
-        Ok(AtmosphereBounds { lower: ALT7500_32F, upper: ALT7500_32F })
-    } else if altitude_ft > 5000 {
-        Ok(AtmosphereBounds { lower: ALT5000_41F, upper: ALT7500_32F })
-    } else if altitude_ft == 5000 {
-        Ok(AtmosphereBounds { lower: ALT5000_41F, upper: ALT5000_41F })
-    } else if altitude_ft > 2500 {
-        Ok(AtmosphereBounds { lower: ALT2500_50F, upper: ALT5000_41F })
-    } else if altitude_ft == 2500 {
-        Ok(AtmosphereBounds { lower: ALT2500_50F, upper: ALT2500_50F })
-    } else if altitude_ft > 0 {
-        Ok(AtmosphereBounds { lower: ALT0_59F, upper: ALT2500_50F })
-    } else if altitude_ft == 0 {
-        Ok(AtmosphereBounds { lower: ALT0_59F, upper: ALT0_59F })
-    } else {
-        Err("Unable to calculate, performance not defined")
-    }
+lazy_static! {
+    static ref TAKE_OFF_GRID: Grid<Distance> = Grid::new(
+        vec![altitude_axis(), Axis::new("headwind", " kts", &HEADWINDS_KTS)],
+        TAKE_OFF.iter().flatten().map(|distance| Some(*distance)).collect()
+    );
+
+    static ref LANDING_GRID: Grid<Distance> = Grid::new(
+        vec![altitude_axis()],
+        LANDING.iter().map(|distance| Some(*distance)).collect()
+    );
+
+    static ref CLIMB_RATE_GRID: Grid<f64> = Grid::new(vec![climb_altitude_axis()], CLIMB_RATES_FPM.iter().map(|rate| Some(*rate)).collect());
+
+    static ref CLIMB_SPEED_GRID: Grid<f64> = Grid::new(vec![climb_altitude_axis()], CLIMB_SPEEDS_MPH.iter().map(|speed| Some(*speed)).collect());
 }
 
-fn get_take_off_distance(velocity: &Velocity, atmosphere: &Atmosphere) -> Result<Distance, &'static str> {
-    match velocity {
-        Velocity::Knots(0) => match atmosphere {
-            Atmosphere::Alt0_59F => Ok(Distance(735, 1385)),
-            Atmosphere::Alt2500_50F => Ok(Distance(910, 1660)),
-            Atmosphere::Alt5000_41F => Ok(Distance(1115, 1985)),
-            Atmosphere::Alt7500_32F => Ok(Distance(1360, 2440))
-        },
-        Velocity::Knots(10) => match atmosphere {
-            Atmosphere::Alt0_59F => Ok(Distance(500, 1035)),
-            Atmosphere::Alt2500_50F => Ok(Distance(630, 1250)),
-            Atmosphere::Alt5000_41F => Ok(Distance(780, 1510)),
-            Atmosphere::Alt7500_32F => Ok(Distance(970, 1875))
-        },
-        Velocity::Knots(20) => match atmosphere {
-            Atmosphere::Alt0_59F => Ok(Distance(305, 730)),
-            Atmosphere::Alt2500_50F => Ok(Distance(395, 890)),
-            Atmosphere::Alt5000_41F => Ok(Distance(505, 1090)),
-            Atmosphere::Alt7500_32F => Ok(Distance(640, 1375))
-        },
-        _ => Err("Undefined performance")
-    }
+fn climb_altitude_axis() -> Axis {
+    Axis::new("altitude", "'", &CLIMB_ALTITUDES_FT).extrapolate(Extrapolation::Clamp, Extrapolation::Error)
 }
 
-fn get_landing_distance(atmosphere: &Atmosphere) -> Result<Distance, &'static str> {
-    match atmosphere {
-        Atmosphere::Alt0_59F => Ok(Distance(445, 1075)),
-        Atmosphere::Alt2500_50F => Ok(Distance(470, 1135)),
-        Atmosphere::Alt5000_41F => Ok(Distance(495, 1195)),
-        Atmosphere::Alt7500_32F => Ok(Distance(520, 1255))
-    }
+fn atmosphere_def(index: usize) -> AtmosphereDef<usize> {
+    AtmosphereDef { altitude: ALTITUDES_FT[index] as i16, temperature: STANDARD_TEMPERATURES_F[index], indexer: index }
 }
 
 pub struct Corrections {
     pub standard_temperature_correction_percentage: f64,
     pub distance_corrected_for_temperature: Distance,
-    pub grass_offset: i16,
+    pub grass_offset: f64,
     pub distance_corrected_for_grass: Distance
 }
 
+impl Corrections {
+    pub fn final_distance(&self, is_grass: bool) -> Distance {
+        if is_grass {
+            self.distance_corrected_for_grass
+        } else {
+            self.distance_corrected_for_temperature
+        }
+    }
+}
+
 pub struct TakeOff {
+    pub headwinds: Headwinds,
+    pub headwind_tween_percentage: f64,
     pub takeoff_distances: [PerformanceRow; 3],
     pub distance_at_elevation: Distance,
+    pub lookup: Lookup<Distance>,
     pub correction: Corrections
 }
 
 pub struct Landing {
     pub landing_distances: PerformanceRow,
     pub distance_at_elevation: Distance,
+    pub lookup: Lookup<Distance>,
     pub headwind_correction_percentage: f64,
     pub distance_with_headwind: Distance,
     pub correction: Corrections
 }
 
 pub struct Cessna150J {
-    pub headwind_kts: i16,
-    pub headwinds: Headwinds,
-    pub headwind_tween_percentage: f64,
-    pub elevation_ft: i16,
-    pub atmosphere_bounds: AtmosphereBounds<Atmosphere>,
+    pub headwind_kts: f64,
+    pub elevation_ft: f64,
+    pub atmosphere_bounds: AtmosphereBounds<usize>,
     pub altitude_tween_percentage: f64,
-    pub temperature_f: i16,
-    pub standard_temperature_f: i16,
-    pub temperature_f_diff_from_standard: i16
+    pub temperature_f: f64,
+    pub standard_temperature_f: f64,
+    pub temperature_f_diff_from_standard: f64
 }
 
 impl Cessna150J {
-    pub fn new(headwind: Velocity, temperature_f: i16, elevation_ft: i16, standard_temperature_f: i16) -> Cessna150J {
+    pub fn try_new(headwind: Velocity, temperature: Temperature, elevation: Length, standard_temperature: Temperature) -> Result<Cessna150J, PerformanceError> {
         let headwind_kts = headwind.knots();
-        let headwinds = find_headwind_performance_keys(headwind_kts).expect("To get the headwind bounds.");
-        let wind_percentage = (headwind_kts as f64).percent_velocity(headwinds.lower_value, headwinds.upper_value);
-        
-        let atmosphere_bounds = find_atmosphere(elevation_ft).expect("To get the atmospheric bounds.");
-        let altitude_tween_percentage = (elevation_ft as f64).percent_i16(atmosphere_bounds.lower.altitude, atmosphere_bounds.upper.altitude);
+        if headwind_kts < 0.0 {
+            return Err(PerformanceError::TailwindLimit { tailwind_kts: -headwind_kts, limit_kts: 0.0 });
+        }
+        let elevation_ft = elevation.feet();
+        let temperature_f = temperature.fahrenheit();
+        let standard_temperature_f = standard_temperature.fahrenheit();
+        let mut warnings = vec![];
 
-        Cessna150J {
+        let altitude_bracket = TAKE_OFF_GRID.axis(0).bracket(elevation_ft, &mut warnings)?;
+        let atmosphere_bounds = AtmosphereBounds {
+            lower: atmosphere_def(altitude_bracket.lower_index),
+            upper: atmosphere_def(altitude_bracket.upper_index)
+        };
+
+        Ok(Cessna150J {
             headwind_kts,
-            headwinds,
-            headwind_tween_percentage: wind_percentage,
             elevation_ft,
             atmosphere_bounds,
-            altitude_tween_percentage,
+            altitude_tween_percentage: altitude_bracket.fraction,
             temperature_f,
             standard_temperature_f,
             temperature_f_diff_from_standard: temperature_f - standard_temperature_f
-        }
+        })
+    }
+
+    pub fn new(headwind: Velocity, temperature: Temperature, elevation: Length, standard_temperature: Temperature) -> Cessna150J {
+        Self::try_new(headwind, temperature, elevation, standard_temperature).expect("To get the performance bounds.")
     }
     
     fn calc_standard_temperature_correction_percentage(&self, standard_temperature_correction_interval: f64) -> f64 {
-        (0.0f64).max(0.1 * (self.temperature_f_diff_from_standard as f64 / standard_temperature_correction_interval))
+        (0.0f64).max(0.1 * (self.temperature_f_diff_from_standard / standard_temperature_correction_interval))
     }
 
     fn calc_distance_corrected_for_temperature(&self, distance: Distance, standard_temperature_correction_percentage: f64) -> Distance {
-        Distance::new_from_f64(
-            distance.ground_run() as f64 * (1.0 + standard_temperature_correction_percentage),
-            distance.clear_50_ft_obstacle() as f64 * (1.0 + standard_temperature_correction_percentage))
+        distance.scale(1.0 + standard_temperature_correction_percentage)
     }
 
-    fn calc_distance_corrected_for_grass(&self, distance: Distance, scale_factor: f64) -> (i16, Distance) {
-        let grass_offset = (distance.clear_50_ft_obstacle() as f64 * scale_factor).round() as i16;
-        (grass_offset, Distance(
-            distance.ground_run() + grass_offset,
-            distance.clear_50_ft_obstacle() + grass_offset,
-        ))
+    fn calc_distance_corrected_for_grass(&self, distance: Distance, scale_factor: f64) -> (f64, Distance) {
+        let grass_offset = distance.clear_50_ft_obstacle_ft() * scale_factor;
+        (grass_offset, distance.offset(grass_offset))
     }
 
-    pub fn calc_take_off(&self) -> TakeOff {
-        let lower_row_lower_distance = get_take_off_distance(&self.headwinds.lower_value, &self.atmosphere_bounds.lower.indexer).expect("To get lower_wind_lower_altitude.");
-        let lower_row_upper_distance = get_take_off_distance(&self.headwinds.lower_value, &self.atmosphere_bounds.upper.indexer).expect("To get lower_wind_upper_altitude.");
-        let upper_row_lower_distance = get_take_off_distance(&self.headwinds.upper_value, &self.atmosphere_bounds.lower.indexer).expect("To get upper_wind_lower_altitude.");
-        let upper_row_upper_distance = get_take_off_distance(&self.headwinds.upper_value, &self.atmosphere_bounds.upper.indexer).expect("To get upper_wind_upper_altitude.");
-
-        let lower_row_middle_tween = self.altitude_tween_percentage.percent_of_distance(lower_row_lower_distance, lower_row_upper_distance);
-        let upper_row_middle_tween = self.altitude_tween_percentage.percent_of_distance(upper_row_lower_distance, upper_row_upper_distance);
+    /// Only the take off chart is entered by headwind, so only a take off is limited to its charted headwinds.
+    pub fn try_calc_take_off(&self) -> Result<TakeOff, PerformanceError> {
+        let headwind_bracket = TAKE_OFF_GRID.axis(1).bracket(self.headwind_kts, &mut vec![])?;
+        let headwinds = Headwinds {
+            lower_value: Velocity::Knots(headwind_bracket.lower),
+            upper_value: Velocity::Knots(headwind_bracket.upper)
+        };
 
-        let middle_row_lower_tween = self.headwind_tween_percentage.percent_of_distance(lower_row_lower_distance, upper_row_lower_distance);
-        let middle_row_upper_tween = self.headwind_tween_percentage.percent_of_distance(lower_row_upper_distance, upper_row_upper_distance);
-        let distance_at_elevation = self.altitude_tween_percentage.percent_of_distance(middle_row_lower_tween, middle_row_upper_tween);
+        let point = [self.elevation_ft, self.headwind_kts];
+        let lookup = TAKE_OFF_GRID.lookup(&point)?;
+        let [lower_row, middle_row, upper_row] = TAKE_OFF_GRID.cross_section(&point, 1, 0)?;
+        let distance_at_elevation = lookup.value;
 
         let takeoff_distances = [
-            PerformanceRow::new_labeled(self.headwinds.lower_value.knots(), lower_row_lower_distance, lower_row_middle_tween, lower_row_upper_distance),
-            PerformanceRow::new_labeled(self.headwind_kts, middle_row_lower_tween, distance_at_elevation, middle_row_upper_tween),
-            PerformanceRow::new_labeled(self.headwinds.upper_value.knots(), upper_row_lower_distance, upper_row_middle_tween, upper_row_upper_distance)
+            PerformanceRow::new_labeled(headwinds.lower_value.knots(), lower_row[0], lower_row[1], lower_row[2]),
+            PerformanceRow::new_labeled(self.headwind_kts, middle_row[0], middle_row[1], middle_row[2]),
+            PerformanceRow::new_labeled(headwinds.upper_value.knots(), upper_row[0], upper_row[1], upper_row[2])
         ];
 
         let standard_temperature_correction_interval = 35.0;
@@ -184,30 +205,37 @@ impl Cessna150J {
         let distance_corrected_for_grass_scale_factor = 0.07;
         let (grass_offset, distance_corrected_for_grass) = self.calc_distance_corrected_for_grass(distance_corrected_for_temperature, distance_corrected_for_grass_scale_factor);
 
-        TakeOff {
+        Ok(TakeOff {
+            headwinds,
+            headwind_tween_percentage: headwind_bracket.fraction,
             takeoff_distances,
             distance_at_elevation,
+            lookup,
             correction: Corrections {
                 standard_temperature_correction_percentage,
                 distance_corrected_for_temperature,
                 grass_offset,
                 distance_corrected_for_grass
             }
-        }
+        })
+    }
+
+    pub fn calc_take_off(&self) -> TakeOff {
+        self.try_calc_take_off().expect("To get the take off performance")
     }
 
     pub fn calc_landing(&self) -> Landing {
-        let lower_distance = get_landing_distance(&self.atmosphere_bounds.lower.indexer).expect("To get lower_altitude.");
-        let upper_distance = get_landing_distance(&self.atmosphere_bounds.upper.indexer).expect("To get upper_altitude.");
-        
-        let distance_at_elevation = self.altitude_tween_percentage.percent_of_distance(lower_distance, upper_distance);
+        let at_altitude = |altitude: f64| LANDING_GRID.lookup(&[altitude]).expect("To get the landing distance.");
+        let lookup = at_altitude(self.elevation_ft);
+        let distance_at_elevation = lookup.value;
 
-        let landing_distances = PerformanceRow::new_unlabeled(lower_distance, distance_at_elevation, upper_distance);
+        let landing_distances = PerformanceRow::new_unlabeled(
+            at_altitude(self.atmosphere_bounds.lower.altitude as f64).value,
+            distance_at_elevation,
+            at_altitude(self.atmosphere_bounds.upper.altitude as f64).value);
 
-        let headwind_correction_percentage = (self.headwind_kts as f64 / 4.0) * 0.1;
-        let distance_with_headwind = Distance::new_from_f64(
-        distance_at_elevation.ground_run() as f64 * (1.0 - headwind_correction_percentage), 
-        distance_at_elevation.clear_50_ft_obstacle() as f64 * (1.0 - headwind_correction_percentage));
+        let headwind_correction_percentage = (self.headwind_kts / 4.0) * 0.1;
+        let distance_with_headwind = distance_at_elevation.scale(1.0 - headwind_correction_percentage);
 
         let standard_temperature_correction_interval = 60.0;
         let standard_temperature_correction_percentage = self.calc_standard_temperature_correction_percentage(standard_temperature_correction_interval);
@@ -220,7 +248,8 @@ impl Cessna150J {
             landing_distances,
             headwind_correction_percentage,
             distance_with_headwind,
-            distance_at_elevation: distance_at_elevation,
+            distance_at_elevation,
+            lookup,
             correction: Corrections {
                 standard_temperature_correction_percentage,
                 distance_corrected_for_temperature,
@@ -229,4 +258,61 @@ impl Cessna150J {
             }
         }
     }
-}
\ No newline at end of file
+
+    pub fn calc_climb(&self) -> Result<Climb, PerformanceError> {
+        let rate_fpm = CLIMB_RATE_GRID.lookup(&[self.elevation_ft])?.value - 15.0 * (self.temperature_f_diff_from_standard / 10.0).max(0.0);
+        let speed_mph = CLIMB_SPEED_GRID.lookup(&[self.elevation_ft])?.value;
+
+        Ok(Climb { rate_fpm, speed_kias: Velocity::MilesPerHour(speed_mph).knots() })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::data::performance::aircraft::assert_distance;
+
+    use super::*;
+
+    fn cessna(headwind_kts: f64, temperature_f: f64, elevation_ft: f64) -> Cessna150J {
+        let standard_temperature = Temperature::standard_temperature(Length::Feet(elevation_ft));
+        Cessna150J::new(Velocity::Knots(headwind_kts), Temperature::Fahrenheit(temperature_f), Length::Feet(elevation_ft), standard_temperature)
+    }
+
+    #[test]
+    fn take_off_matches_the_poh_at_charted_points() {
+        assert_distance(cessna(0.0, 59.0, 0.0).calc_take_off().correction.final_distance(false), Distance(735.0, 1385.0));
+        assert_distance(cessna(10.0, 50.25, 2500.0).calc_take_off().correction.final_distance(false), Distance(630.0, 1250.0));
+        assert_distance(cessna(20.0, 32.75, 7500.0).calc_take_off().correction.final_distance(false), Distance(640.0, 1375.0));
+    }
+
+    #[test]
+    fn take_off_interpolates_without_rounding() {
+        let take_off = cessna(5.0, 54.625, 1250.0).calc_take_off();
+        assert_distance(take_off.distance_at_elevation, Distance(693.75, 1332.5));
+    }
+
+    #[test]
+    fn take_off_temperature_correction_keeps_precision() {
+        let take_off = cessna(0.0, 94.0, 0.0).calc_take_off();
+        assert_distance(take_off.correction.distance_corrected_for_temperature, Distance(808.5, 1523.5));
+        assert_distance(take_off.correction.distance_corrected_for_grass, Distance(808.5 + 106.645, 1523.5 + 106.645));
+    }
+
+    #[test]
+    fn only_a_take_off_is_limited_to_the_charted_headwinds() {
+        assert!(cessna(25.0, 59.0, 0.0).try_calc_take_off().is_err());
+        assert_distance(cessna(25.0, 59.0, 0.0).calc_landing().distance_with_headwind, Distance(445.0 * 0.375, 1075.0 * 0.375));
+    }
+
+    #[test]
+    fn landing_matches_the_poh_at_charted_points() {
+        assert_distance(cessna(0.0, 59.0, 0.0).calc_landing().correction.final_distance(false), Distance(445.0, 1075.0));
+        assert_distance(cessna(0.0, 41.5, 5000.0).calc_landing().correction.final_distance(false), Distance(495.0, 1195.0));
+    }
+
+    #[test]
+    fn tailwind_is_not_available() {
+        let standard_temperature = Temperature::standard_temperature(Length::Feet(0.0));
+        let result = Cessna150J::try_new(Velocity::Knots(-2.0), Temperature::Fahrenheit(59.0), Length::Feet(0.0), standard_temperature);
+        assert_eq!(result.err(), Some(PerformanceError::TailwindLimit { tailwind_kts: 2.0, limit_kts: 0.0 }));
+    }
+}
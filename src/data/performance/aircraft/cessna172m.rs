@@ -11,301 +11,222 @@ NOTES:
 3. For operation on a dry, grass runway, increase distances by 45% of the "ground roll" figure.
 */
 
-use std::cmp::max;
-
-use crate::{data::performance::{distance::Distance, performance_row::PerformanceRow}, math::{FloatingCalcs, Pressure, Velocity}};
+use lazy_static::lazy_static;
+
+use crate::data::performance::{configuration::{Configuration, ConfigurationGroup}, distance::Distance, grid::{Axis, Grid}, limits::ChartLimits, speeds::{SpeedKind, SpeedTable}};
+
+use super::cessna_tables::{pressure_altitude_axis, temperature_axis, TableNotes, Tables};
+
+const WEIGHTS_LBS: [f64; 3] = [1900.0, 2100.0, 2300.0];
+const PRESSURE_ALTITUDES_FT: [f64; 9] = [0.0, 1000.0, 2000.0, 3000.0, 4000.0, 5000.0, 6000.0, 7000.0, 8000.0];
+const TEMPERATURES_C: [f64; 5] = [0.0, 10.0, 20.0, 30.0, 40.0];
+/// Notes 3 and 2 of the take off and landing charts only correct for tailwinds up to 10 knots.
+const MAX_TAILWIND_KTS: f64 = 10.0;
+
+/// Pressure altitudes and temperatures below the charts are clamped to the first row and column, note 3 allows tailwinds up to 10 knots.
+pub const LIMITS: ChartLimits = ChartLimits {
+	max_weight_lbs: WEIGHTS_LBS[2] as i16,
+	take_off_by_weight: true,
+	by_pressure_altitude: true,
+	min_altitude_ft: None,
+	max_altitude_ft: Some(PRESSURE_ALTITUDES_FT[8]),
+	max_temperature_c: Some(TEMPERATURES_C[4]),
+	min_headwind_kts: -MAX_TAILWIND_KTS,
+	max_take_off_headwind_kts: None
+};
 
 const TAKE_OFF_AT_2300_LBS: [[Option<Distance>; 5]; 9] = [
-	[Some(Distance(775, 1380)),  Some(Distance(835, 1475)),  Some(Distance(895, 1575)),  Some(Distance(960, 1685)),  Some(Distance(1030, 1795))],
-	[Some(Distance(850, 1510)),  Some(Distance(915, 1615)),  Some(Distance(980, 1725)),  Some(Distance(1050, 1845)), Some(Distance(1125, 1970))],
-	[Some(Distance(930, 1650)),  Some(Distance(1000, 1770)), Some(Distance(1075, 1895)), Some(Distance(1155, 2030)), Some(Distance(1235, 2170))],
-	[Some(Distance(1020, 1815)), Some(Distance(1100, 1945)), Some(Distance(1180, 2085)), Some(Distance(1270, 2235)), Some(Distance(1360, 2395))],
-	[Some(Distance(1125, 2000)), Some(Distance(1210, 2145)), Some(Distance(1300, 2305)), Some(Distance(1395, 2475)), Some(Distance(1495, 2655))],
-	[Some(Distance(1235, 2210)), Some(Distance(1330, 2375)), Some(Distance(1430, 2555)), Some(Distance(1540, 2750)), Some(Distance(1650, 2960))],
-	[Some(Distance(1365, 2450)), Some(Distance(1470, 2640)), Some(Distance(1580, 2850)), Some(Distance(1700, 3070)), None],
-	[Some(Distance(1505, 2730)), Some(Distance(1625, 2955)), Some(Distance(1750, 3190)), None,                       None],
-	[Some(Distance(1505, 2730)), Some(Distance(1625, 2955)), Some(Distance(1750, 3190)), None,                       None]
+	[Some(Distance(775.0, 1380.0)),  Some(Distance(835.0, 1475.0)),  Some(Distance(895.0, 1575.0)),  Some(Distance(960.0, 1685.0)),  Some(Distance(1030.0, 1795.0))],
+	[Some(Distance(850.0, 1510.0)),  Some(Distance(915.0, 1615.0)),  Some(Distance(980.0, 1725.0)),  Some(Distance(1050.0, 1845.0)), Some(Distance(1125.0, 1970.0))],
+	[Some(Distance(930.0, 1650.0)),  Some(Distance(1000.0, 1770.0)), Some(Distance(1075.0, 1895.0)), Some(Distance(1155.0, 2030.0)), Some(Distance(1235.0, 2170.0))],
+	[Some(Distance(1020.0, 1815.0)), Some(Distance(1100.0, 1945.0)), Some(Distance(1180.0, 2085.0)), Some(Distance(1270.0, 2235.0)), Some(Distance(1360.0, 2395.0))],
+	[Some(Distance(1125.0, 2000.0)), Some(Distance(1210.0, 2145.0)), Some(Distance(1300.0, 2305.0)), Some(Distance(1395.0, 2475.0)), Some(Distance(1495.0, 2655.0))],
+	[Some(Distance(1235.0, 2210.0)), Some(Distance(1330.0, 2375.0)), Some(Distance(1430.0, 2555.0)), Some(Distance(1540.0, 2750.0)), Some(Distance(1650.0, 2960.0))],
+	[Some(Distance(1365.0, 2450.0)), Some(Distance(1470.0, 2640.0)), Some(Distance(1580.0, 2850.0)), Some(Distance(1700.0, 3070.0)), None],
+	[Some(Distance(1505.0, 2730.0)), Some(Distance(1625.0, 2955.0)), Some(Distance(1750.0, 3190.0)), None,                           None],
+	[Some(Distance(1505.0, 2730.0)), Some(Distance(1625.0, 2955.0)), Some(Distance(1750.0, 3190.0)), None,                           None]
 ];
 
 const TAKE_OFF_AT_2100_LBS: [[Option<Distance>; 5]; 9] = [
-	[Some(Distance(630, 1130)),  Some(Distance(680, 1210)),  Some(Distance(725, 1290)),  Some(Distance(780, 1375)),  Some(Distance(835, 1465))],
-	[Some(Distance(690, 1235)),  Some(Distance(740, 1320)),  Some(Distance(795, 1405)),  Some(Distance(855, 1500)),  Some(Distance(915, 1600))],
-	[Some(Distance(755, 1350)),  Some(Distance(810, 1440)),  Some(Distance(870, 1540)),  Some(Distance(935, 1645)),  Some(Distance(1000, 1755))],
-	[Some(Distance(830, 1475)),  Some(Distance(890, 1580)),  Some(Distance(955, 1690)),  Some(Distance(1025, 1805)), Some(Distance(1100, 1930))],
-	[Some(Distance(910, 1620)),  Some(Distance(980, 1735)),  Some(Distance(1050, 1860)), Some(Distance(1125, 1990)), Some(Distance(1210, 2130))],
-	[Some(Distance(1000, 1780)), Some(Distance(1075, 1910)), Some(Distance(1155, 2050)), Some(Distance(1240, 2195)), Some(Distance(1330, 2355))],
-	[Some(Distance(1100, 1965)), Some(Distance(1185, 2115)), Some(Distance(1275, 2270)), Some(Distance(1370, 2435)), Some(Distance(1465, 2615))],
-	[Some(Distance(1215, 2180)), Some(Distance(1305, 2345)), Some(Distance(1405, 2520)), Some(Distance(1510, 2715)), Some(Distance(1620, 2920))],
-	[Some(Distance(1340, 2425)), Some(Distance(1445, 2615)), Some(Distance(1555, 2815)), Some(Distance(1675, 3040)), Some(Distance(1795, 3280))]
+	[Some(Distance(630.0, 1130.0)),  Some(Distance(680.0, 1210.0)),  Some(Distance(725.0, 1290.0)),  Some(Distance(780.0, 1375.0)),  Some(Distance(835.0, 1465.0))],
+	[Some(Distance(690.0, 1235.0)),  Some(Distance(740.0, 1320.0)),  Some(Distance(795.0, 1405.0)),  Some(Distance(855.0, 1500.0)),  Some(Distance(915.0, 1600.0))],
+	[Some(Distance(755.0, 1350.0)),  Some(Distance(810.0, 1440.0)),  Some(Distance(870.0, 1540.0)),  Some(Distance(935.0, 1645.0)),  Some(Distance(1000.0, 1755.0))],
+	[Some(Distance(830.0, 1475.0)),  Some(Distance(890.0, 1580.0)),  Some(Distance(955.0, 1690.0)),  Some(Distance(1025.0, 1805.0)), Some(Distance(1100.0, 1930.0))],
+	[Some(Distance(910.0, 1620.0)),  Some(Distance(980.0, 1735.0)),  Some(Distance(1050.0, 1860.0)), Some(Distance(1125.0, 1990.0)), Some(Distance(1210.0, 2130.0))],
+	[Some(Distance(1000.0, 1780.0)), Some(Distance(1075.0, 1910.0)), Some(Distance(1155.0, 2050.0)), Some(Distance(1240.0, 2195.0)), Some(Distance(1330.0, 2355.0))],
+	[Some(Distance(1100.0, 1965.0)), Some(Distance(1185.0, 2115.0)), Some(Distance(1275.0, 2270.0)), Some(Distance(1370.0, 2435.0)), Some(Distance(1465.0, 2615.0))],
+	[Some(Distance(1215.0, 2180.0)), Some(Distance(1305.0, 2345.0)), Some(Distance(1405.0, 2520.0)), Some(Distance(1510.0, 2715.0)), Some(Distance(1620.0, 2920.0))],
+	[Some(Distance(1340.0, 2425.0)), Some(Distance(1445.0, 2615.0)), Some(Distance(1555.0, 2815.0)), Some(Distance(1675.0, 3040.0)), Some(Distance(1795.0, 3280.0))]
 ];
 
 const TAKE_OFF_AT_1900_LBS: [[Option<Distance>; 5]; 9] = [
-	[Some(Distance(505, 915)),   Some(Distance(540, 975)),   Some(Distance(580, 1035)),  Some(Distance(620, 1105)),  Some(Distance(665, 1175))],
-	[Some(Distance(550, 995)),   Some(Distance(590, 1060)),  Some(Distance(635, 1130)),  Some(Distance(680, 1205)),  Some(Distance(725, 1280))],
-	[Some(Distance(600, 1085)),  Some(Distance(645, 1155)),  Some(Distance(695, 1230)),  Some(Distance(745, 1315)),  Some(Distance(795, 1400))],
-	[Some(Distance(660, 1180)),  Some(Distance(710, 1260)),  Some(Distance(760, 1345)),  Some(Distance(815, 1435)),  Some(Distance(870, 1530))],
-	[Some(Distance(725, 1290)),  Some(Distance(775, 1380)),  Some(Distance(835, 1475)),  Some(Distance(895, 1575)),  Some(Distance(955, 1680))],
-	[Some(Distance(795, 1415)),  Some(Distance(855, 1515)),  Some(Distance(915, 1620)),  Some(Distance(985, 1735)),  Some(Distance(1055, 1850))],
-	[Some(Distance(870, 1555)),  Some(Distance(940, 1670)),  Some(Distance(1010, 1785)), Some(Distance(1080, 1910)), Some(Distance(1160, 2045))],
-	[Some(Distance(960, 1715)),  Some(Distance(1035, 1840)), Some(Distance(1110, 1975)), Some(Distance(1195, 2115)), Some(Distance(1280, 2265))],
-	[Some(Distance(1060, 1900)), Some(Distance(1140, 2040)), Some(Distance(1225, 2190)), Some(Distance(1320, 2350)), Some(Distance(1415, 2520))]
+	[Some(Distance(505.0, 915.0)),   Some(Distance(540.0, 975.0)),   Some(Distance(580.0, 1035.0)),  Some(Distance(620.0, 1105.0)),  Some(Distance(665.0, 1175.0))],
+	[Some(Distance(550.0, 995.0)),   Some(Distance(590.0, 1060.0)),  Some(Distance(635.0, 1130.0)),  Some(Distance(680.0, 1205.0)),  Some(Distance(725.0, 1280.0))],
+	[Some(Distance(600.0, 1085.0)),  Some(Distance(645.0, 1155.0)),  Some(Distance(695.0, 1230.0)),  Some(Distance(745.0, 1315.0)),  Some(Distance(795.0, 1400.0))],
+	[Some(Distance(660.0, 1180.0)),  Some(Distance(710.0, 1260.0)),  Some(Distance(760.0, 1345.0)),  Some(Distance(815.0, 1435.0)),  Some(Distance(870.0, 1530.0))],
+	[Some(Distance(725.0, 1290.0)),  Some(Distance(775.0, 1380.0)),  Some(Distance(835.0, 1475.0)),  Some(Distance(895.0, 1575.0)),  Some(Distance(955.0, 1680.0))],
+	[Some(Distance(795.0, 1415.0)),  Some(Distance(855.0, 1515.0)),  Some(Distance(915.0, 1620.0)),  Some(Distance(985.0, 1735.0)),  Some(Distance(1055.0, 1850.0))],
+	[Some(Distance(870.0, 1555.0)),  Some(Distance(940.0, 1670.0)),  Some(Distance(1010.0, 1785.0)), Some(Distance(1080.0, 1910.0)), Some(Distance(1160.0, 2045.0))],
+	[Some(Distance(960.0, 1715.0)),  Some(Distance(1035.0, 1840.0)), Some(Distance(1110.0, 1975.0)), Some(Distance(1195.0, 2115.0)), Some(Distance(1280.0, 2265.0))],
+	[Some(Distance(1060.0, 1900.0)), Some(Distance(1140.0, 2040.0)), Some(Distance(1225.0, 2190.0)), Some(Distance(1320.0, 2350.0)), Some(Distance(1415.0, 2520.0))]
 ];
 
 const LANDING_AT_2300_LBS: [[Distance; 5]; 9] = [
-	[ Distance(495, 1205), Distance(510, 1235), Distance(530, 1265), Distance(545, 1295), Distance(565, 1330)],
-	[ Distance(510, 1235), Distance(530, 1265), Distance(550, 1300), Distance(565, 1330), Distance(585, 1365)],
-	[ Distance(530, 1265), Distance(550, 1300), Distance(570, 1335), Distance(590, 1370), Distance(610, 1405)],
-	[ Distance(550, 1300), Distance(570, 1335), Distance(590, 1370), Distance(610, 1405), Distance(630, 1440)],
-	[ Distance(570, 1335), Distance(590, 1370), Distance(615, 1410), Distance(635, 1445), Distance(655, 1480)],
-	[ Distance(590, 1370), Distance(615, 1415), Distance(635, 1450), Distance(655, 1485), Distance(680, 1525)],
-	[ Distance(615, 1415), Distance(640, 1455), Distance(660, 1490), Distance(685, 1535), Distance(705, 1570)],
-	[ Distance(640, 1455), Distance(660, 1495), Distance(685, 1535), Distance(710, 1575), Distance(730, 1615)],
-	[ Distance(665, 1500), Distance(690, 1540), Distance(710, 1580), Distance(735, 1620), Distance(760, 1665)]
+	[ Distance(495.0, 1205.0), Distance(510.0, 1235.0), Distance(530.0, 1265.0), Distance(545.0, 1295.0), Distance(565.0, 1330.0)],
+	[ Distance(510.0, 1235.0), Distance(530.0, 1265.0), Distance(550.0, 1300.0), Distance(565.0, 1330.0), Distance(585.0, 1365.0)],
+	[ Distance(530.0, 1265.0), Distance(550.0, 1300.0), Distance(570.0, 1335.0), Distance(590.0, 1370.0), Distance(610.0, 1405.0)],
+	[ Distance(550.0, 1300.0), Distance(570.0, 1335.0), Distance(590.0, 1370.0), Distance(610.0, 1405.0), Distance(630.0, 1440.0)],
+	[ Distance(570.0, 1335.0), Distance(590.0, 1370.0), Distance(615.0, 1410.0), Distance(635.0, 1445.0), Distance(655.0, 1480.0)],
+	[ Distance(590.0, 1370.0), Distance(615.0, 1415.0), Distance(635.0, 1450.0), Distance(655.0, 1485.0), Distance(680.0, 1525.0)],
+	[ Distance(615.0, 1415.0), Distance(640.0, 1455.0), Distance(660.0, 1490.0), Distance(685.0, 1535.0), Distance(705.0, 1570.0)],
+	[ Distance(640.0, 1455.0), Distance(660.0, 1495.0), Distance(685.0, 1535.0), Distance(710.0, 1575.0), Distance(730.0, 1615.0)],
+	[ Distance(665.0, 1500.0), Distance(690.0, 1540.0), Distance(710.0, 1580.0), Distance(735.0, 1620.0), Distance(760.0, 1665.0)]
 ];
 
-struct AircraftWeightRowResult<T> {
-	presure_altitude_ft: i16,
-	lower_temperature_c: i16,
-	lower_distance: T,
-	upper_temperature_c: i16,
-	upper_distance: T
-}
-
-#[derive(Clone, Copy)]
-pub enum AircraftWeight {
-	At2300Lbs = 2300,
-	At2100Lbs = 2100,
-	At1900Lbs = 1900
-}
-
-fn calc_row_column(pressure_altitude: i16, temperature_c: i16) -> Result<(usize, usize), &'static str>{
-	let row = max(0, pressure_altitude / 1000) as usize;
-	if row > 8  {
-		return Err("Altitude out of bounds");
-	}
-
-	let column = max(0, temperature_c / 10) as usize;
-	if column > 5  {
-		return Err("Temperature out of bounds");
-	}
-
-	Ok((row, column))
-}
-
-impl AircraftWeight {
-	pub fn find_takeoff_weight(weight_lbs: i16) -> Result<AircraftWeight, &'static str> {
-		if weight_lbs > 2300 {
-			Err("Over max weight")
-		} else if weight_lbs > 2100 {
-			Ok(Self::At2300Lbs)			
-		} else if weight_lbs > 1900 {
-			Ok(Self::At2100Lbs)
-		} else {
-			Ok(Self::At1900Lbs)
-		}
-	}
+/// The take off charts are for the short field technique with flaps 10° and the landing chart for flaps 40° with power off.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+	Configuration { id: "short_field", name: "Short field, flaps 10° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+	Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.1, air_distance_factor: 1.15 },
+	Configuration { id: "full", name: "Flaps 40° (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+	Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.25, air_distance_factor: 1.3 },
+	Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.4, air_distance_factor: 1.45 },
+	Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+	Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
 
-	fn find_take_off_distance(&self, pressure_altitude: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Option<Distance>>, &'static str> {
-		let result = calc_row_column(pressure_altitude, temperature_c);
-		if result.is_err() {
-			return Err(result.err().unwrap());
-		}
+/// The POH speeds at 2300 lbs.
+pub const SPEEDS: SpeedTable = SpeedTable {
+	weights_lbs: &WEIGHTS_LBS,
+	speeds_kias: &[
+		(SpeedKind::Vr, &[50.0, 53.0, 55.0]),
+		(SpeedKind::Vx, &[54.0, 56.0, 59.0]),
+		(SpeedKind::Vy, &[66.0, 70.0, 73.0]),
+		(SpeedKind::Vref, &[55.0, 57.0, 60.0]),
+		(SpeedKind::Vs0, &[35.0, 37.0, 39.0]),
+		(SpeedKind::Vs1, &[43.0, 45.0, 47.0]),
+		(SpeedKind::Vg, &[59.0, 62.0, 65.0])
+	]
+};
+
+/// The maximum rate of climb chart at 2300 lbs, the rate is blank where the temperature is off the chart at altitude.
+const CLIMB_PRESSURE_ALTITUDES_FT: [f64; 7] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 12000.0];
+const CLIMB_TEMPERATURES_C: [f64; 4] = [-20.0, 0.0, 20.0, 40.0];
+const CLIMB_SPEEDS_KIAS: [f64; 7] = [73.0, 72.0, 71.0, 70.0, 69.0, 68.0, 67.0];
+const CLIMB_RATES_FPM: [[Option<f64>; 4]; 7] = [
+	[Some(875.0), Some(815.0), Some(755.0), Some(695.0)],
+	[Some(765.0), Some(705.0), Some(650.0), Some(590.0)],
+	[Some(655.0), Some(600.0), Some(545.0), Some(495.0)],
+	[Some(545.0), Some(495.0), Some(440.0), None],
+	[Some(440.0), Some(390.0), Some(335.0), None],
+	[Some(335.0), Some(285.0), Some(230.0), None],
+	[Some(230.0), Some(180.0), None,        None]
+];
 
-		let (row, column) = result.ok().unwrap();
-		if column == 4 && temperature_c % 10 != 0 {
-			return Err("Upper column out of bounds");
+lazy_static! {
+	pub static ref TABLES: Tables = Tables {
+		weights_lbs: &WEIGHTS_LBS,
+		take_off: Grid::new(
+			vec![Axis::new("weight", " lbs", &WEIGHTS_LBS), pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+			[TAKE_OFF_AT_1900_LBS, TAKE_OFF_AT_2100_LBS, TAKE_OFF_AT_2300_LBS].iter().flatten().flatten().copied().collect()
+		),
+		landing: Grid::new(
+			vec![pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+			LANDING_AT_2300_LBS.iter().flatten().map(|distance| Some(*distance)).collect()
+		),
+		climb_rate: Grid::new(
+			vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT), temperature_axis(&CLIMB_TEMPERATURES_C)],
+			CLIMB_RATES_FPM.iter().flatten().copied().collect()
+		),
+		climb_speed: Grid::new(vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT)], CLIMB_SPEEDS_KIAS.iter().map(|speed| Some(*speed)).collect()),
+		notes: TableNotes {
+			headwind_kts_per_10_percent: 9.0,
+			tailwind_kts_per_10_percent: 2.0,
+			max_tailwind_kts: MAX_TAILWIND_KTS,
+			take_off_grass_ground_roll_percentage: 0.15,
+			landing_grass_ground_roll_percentage: 0.45
 		}
+	};
+}
 
-		let upper_column = if temperature_c % 10 == 0 {
-			column
-		} else {
-			column + 1
-		};
+#[cfg(test)]
+mod tests {
+	use crate::{
+		data::performance::{aircraft::{assert_distance, cessna_tables::Cessna}, error::PerformanceError},
+		math::{Length, Pressure, PressureAltitudeMethod, Temperature, Velocity}
+	};
 
-		let table = match self {
-			AircraftWeight::At2300Lbs => &TAKE_OFF_AT_2300_LBS,
-			AircraftWeight::At2100Lbs => &TAKE_OFF_AT_2100_LBS,
-			AircraftWeight::At1900Lbs => &TAKE_OFF_AT_1900_LBS
-		};
+	use super::*;
 
-		return Ok(AircraftWeightRowResult {
-			presure_altitude_ft: (row * 1000) as i16,
-			lower_temperature_c: (column * 10) as i16,
-			lower_distance: table[row][column],
-			upper_temperature_c: (upper_column * 10) as i16,
-			upper_distance: table[row][upper_column]
-		});
-	}
-	
-	fn take_off_distance_upper_bound(&self, pressure_altitude_ft: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Option<Distance>>, &'static str> {
-		self.find_take_off_distance(pressure_altitude_ft + 1000, temperature_c)
+	fn cessna(elevation_ft: f64, pressure: Pressure, temperature: Temperature) -> Cessna {
+		// The POH charts are indexed by pressure altitude, so the chart points are checked with 29.92" giving the elevation exactly.
+		Cessna::new(&TABLES, Velocity::Knots(0.0), Length::Feet(elevation_ft), pressure, PressureAltitudeMethod::RuleOfThumb, temperature)
 	}
-	
-	fn take_off_distance_lower_bound(&self, pressure_altitude_ft: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Option<Distance>>, &'static str> {
-		self.find_take_off_distance(pressure_altitude_ft, temperature_c)
-	}
-
-	fn find_landing_distance(&self, pressure_altitude_ft: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Distance>, &'static str> {
-		match self {
-			AircraftWeight::At2300Lbs => {
-				let result = calc_row_column(pressure_altitude_ft, temperature_c);
-				if result.is_err() {
-					return Err(result.err().unwrap());
-				}
 
-				let (row, column) = result.ok().unwrap();
-				if column == 4 && temperature_c % 10 != 0 {
-					return Err("Upper column out of bounds");
-				}
-		
-				let upper_column = if temperature_c % 10 == 0 {
-					column
-				} else {
-					column + 1
-				};
+	#[test]
+	fn take_off_matches_the_poh_at_charted_points() {
+		let take_off = cessna(2000.0, Pressure::InchesOfMercury(29.92), Temperature::Fahrenheit(68.0)).calc_take_off(2300);
+		assert_distance(take_off.final_distance(false), Distance(1075.0, 1895.0));
 
-				return Ok(AircraftWeightRowResult {
-					presure_altitude_ft: (row * 1000) as i16,
-					lower_temperature_c: (column * 10) as i16,
-					lower_distance: LANDING_AT_2300_LBS[row][column],
-					upper_temperature_c: (upper_column * 10) as i16,
-					upper_distance: LANDING_AT_2300_LBS[row][upper_column]
-				});
-			},
-			_=> Err("Performance not defined")
-		}
+		let take_off = cessna(5000.0, Pressure::InchesOfMercury(29.92), Temperature::Celsius(0.0)).calc_take_off(1900);
+		assert_distance(take_off.final_distance(false), Distance(795.0, 1415.0));
 	}
 
-	fn landing_distance_upper_bound(&self, pressure_altitude_ft: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Distance>, &'static str> {
-		self.find_landing_distance(pressure_altitude_ft + 1000, temperature_c)
+	#[test]
+	fn fahrenheit_inputs_do_not_shift_the_temperature() {
+		let celsius = cessna(3000.0, Pressure::InchesOfMercury(29.92), Temperature::Celsius(22.0)).calc_take_off(2300);
+		let fahrenheit = cessna(3000.0, Pressure::InchesOfMercury(29.92), Temperature::Fahrenheit(71.6)).calc_take_off(2300);
+		assert_distance(fahrenheit.final_distance(false), celsius.final_distance(false));
+		assert_distance(celsius.final_distance(false), Distance(1198.0, 2115.0));
 	}
-	
-	fn landing_distance_lower_bound(&self, pressure_altitude_ft: i16, temperature_c: i16) -> Result<AircraftWeightRowResult<Distance>, &'static str> {
-		self.find_landing_distance(pressure_altitude_ft, temperature_c)
-	}
-}
 
-pub struct Corrections {
-    pub wind_correction_percentage: f64,
-	pub grass_ground_roll_percentage: f64,
-    pub distance_corrected_for_wind: Distance,
-	pub grass_offset: i16,
-    pub distance_corrected_for_grass: Distance
-}
-
-pub struct Performance {
-	pub pressure_altitude_tween_percentage: f64,
-	pub temperature_c_tween_percentage: f64,
-	pub lower_temperature_c: i16,
-	pub upper_temperature_c: i16,
-	pub distance_rows: [PerformanceRow; 3],
-	pub corrections: Corrections,
-	pub aircraft_weight_lbs: Option<i16>
-}
-
-pub struct Cessna172M {
-	pub headwind: Velocity,
-	pub pressure_in_hg: f32,
-	pub elevation_ft: i16,
-	pub pressure_altitude_ft: i16,
-	pub temperature_c: i16
-}
-
-impl Cessna172M {
-	pub fn new(headwind: Velocity, elevation_ft: i16, pressure: Pressure, temperature_c: i16) -> Self {
-		let pressure_altitude_ft = pressure.altitude(elevation_ft);
-		let pressure_in_hg = pressure.in_hg();
-
-		Cessna172M {
-			headwind,
-			pressure_in_hg,
-			elevation_ft,
-			pressure_altitude_ft,
-			temperature_c
-		}
+	#[test]
+	fn hectopascals_match_inches_of_mercury() {
+		let in_hg = cessna(4001.0, Pressure::InchesOfMercury(30.12), Temperature::Celsius(15.0)).calc_landing();
+		let hpa = cessna(4001.0, Pressure::Hectopascals(30.12 * 33.8639), Temperature::Celsius(15.0)).calc_landing();
+		assert_distance(hpa.final_distance(false), in_hg.final_distance(false));
 	}
 
-	fn calc_corrections(&self, distance_at_elevation: Distance, grass_ground_roll_percentage: f64) -> Corrections {
-		let headwind_kts = self.headwind.knots();
-		let wind_correction_percentage = if headwind_kts > 0 {
-			1.0 - (0.1 * (headwind_kts as f64 / 9.0))
-		} else {
-			1.0 + (0.1 * (headwind_kts as f64 / 2.0))
-		};
-
-		let distance_corrected_for_wind = Distance::new_from_f64(
-			distance_at_elevation.ground_run() as f64 * wind_correction_percentage, 
-			distance_at_elevation.clear_50_ft_obstacle() as f64 * wind_correction_percentage);
+	#[test]
+	fn landing_matches_the_poh_at_charted_points() {
+		let landing = cessna(0.0, Pressure::InchesOfMercury(29.92), Temperature::Celsius(0.0)).calc_landing();
+		assert_distance(landing.final_distance(false), Distance(495.0, 1205.0));
 
-		let grass_offset = (distance_corrected_for_wind.ground_run() as f64 * grass_ground_roll_percentage).round() as i16;
-		let distance_corrected_for_grass = Distance(distance_corrected_for_wind.ground_run() + grass_offset, distance_corrected_for_wind.clear_50_ft_obstacle() + grass_offset); 
-
-		Corrections {
-			wind_correction_percentage,
-			grass_ground_roll_percentage,
-			distance_corrected_for_wind,
-			grass_offset,
-			distance_corrected_for_grass
-		}
+		let landing = cessna(8000.0, Pressure::InchesOfMercury(29.92), Temperature::Celsius(40.0)).calc_landing();
+		assert_distance(landing.final_distance(false), Distance(760.0, 1665.0));
 	}
 
-	fn calc_performance(&self, lower_row: AircraftWeightRowResult<Distance>, upper_row: AircraftWeightRowResult<Distance>, grass_ground_roll_percentage: f64) -> Performance {
-		let pressure_altitude_tween_percentage = (self.pressure_altitude_ft as f64).percent_i16(lower_row.presure_altitude_ft, upper_row.presure_altitude_ft);
-		let temperature_c_tween_percentage = (self.temperature_c as f64).percent_i16(lower_row.lower_temperature_c, lower_row.upper_temperature_c);
-
-		let lower_row_middle_tween = temperature_c_tween_percentage.percent_of_distance(lower_row.lower_distance, lower_row.upper_distance);
-		let upper_row_middle_tween = temperature_c_tween_percentage.percent_of_distance(upper_row.lower_distance, upper_row.upper_distance);
-
-		let middle_row_lower_tween = pressure_altitude_tween_percentage.percent_of_distance(lower_row.lower_distance, upper_row.lower_distance);
-		let middle_row_upper_tween = pressure_altitude_tween_percentage.percent_of_distance(lower_row.upper_distance, upper_row.upper_distance);
-		let distance_at_elevation = pressure_altitude_tween_percentage.percent_of_distance(lower_row_middle_tween, upper_row_middle_tween);
-
-		let distance_rows = [
-            PerformanceRow::new_labeled(lower_row.presure_altitude_ft, lower_row.lower_distance, lower_row_middle_tween, lower_row.upper_distance),
-            PerformanceRow::new_labeled(self.pressure_altitude_ft, middle_row_lower_tween, distance_at_elevation, middle_row_upper_tween),
-            PerformanceRow::new_labeled(upper_row.presure_altitude_ft, upper_row.lower_distance, upper_row_middle_tween, upper_row.upper_distance)
-        ];
-
-		Performance {
-			pressure_altitude_tween_percentage,
-			temperature_c_tween_percentage,
-			lower_temperature_c: lower_row.lower_temperature_c,
-			upper_temperature_c: upper_row.upper_temperature_c,
-			distance_rows,
-			corrections: self.calc_corrections(distance_at_elevation, grass_ground_roll_percentage),
-			aircraft_weight_lbs: None
-		}
+	#[test]
+	fn deleted_cells_are_climb_limited() {
+		let result = cessna(7000.0, Pressure::InchesOfMercury(29.92), Temperature::Celsius(35.0)).try_calc_take_off(2300);
+		assert!(matches!(result, Err(PerformanceError::ClimbLimited { aircraft_weight_lbs: 2300, pressure_altitude_ft: 7000, temperature_c: 30 })));
 	}
 
-	fn convert_to_definate_row_result(&self, row: AircraftWeightRowResult<Option<Distance>>) -> AircraftWeightRowResult<Distance> {
-		AircraftWeightRowResult {
-			presure_altitude_ft: row.presure_altitude_ft,
-			lower_temperature_c: row.lower_temperature_c,
-			lower_distance: row.lower_distance.expect("To get the lower distance data"),
-			upper_temperature_c: row.upper_temperature_c,
-			upper_distance: row.upper_distance.expect("to get the upper distance data")
-		}
+	fn cessna_with_wind(headwind_kts: f64) -> Result<Cessna, PerformanceError> {
+		Cessna::try_new(&TABLES, Velocity::Knots(headwind_kts), Length::Feet(2000.0), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(20.0))
 	}
 
-	pub fn calc_take_off(&self, aircraft_weight_lbs: i16) -> Performance {
-		let takeoff_weight = AircraftWeight::find_takeoff_weight(aircraft_weight_lbs).expect("To get the takeoff weight");
-		let lower_row_optional = takeoff_weight.take_off_distance_lower_bound(self.pressure_altitude_ft, self.temperature_c).expect("To get the lower bound performance numbers");
-		let upper_row_optional = takeoff_weight.take_off_distance_upper_bound(self.pressure_altitude_ft, self.temperature_c).expect("To get the upper bound performance numbers");
-
-		let lower_row = self.convert_to_definate_row_result(lower_row_optional);
-		let upper_row = self.convert_to_definate_row_result(upper_row_optional);
-
-		let mut result = self.calc_performance(lower_row, upper_row, 0.15);
-
-		result.aircraft_weight_lbs = Some(takeoff_weight as i16);
+	#[test]
+	fn calm_wind_is_not_corrected() {
+		let take_off = cessna_with_wind(0.0).unwrap().calc_take_off(2300);
+		assert_eq!(take_off.corrections.wind_correction_percentage, 1.0);
+		assert_distance(take_off.final_distance(false), Distance(1075.0, 1895.0));
+	}
 
-		result
+	#[test]
+	fn headwind_decreases_distances_10_percent_per_9_kts() {
+		let take_off = cessna_with_wind(4.5).unwrap().calc_take_off(2300);
+		assert_distance(take_off.final_distance(false), Distance(1075.0 * 0.95, 1895.0 * 0.95));
 	}
 
-	pub fn calc_landing(&self) -> Performance {
-		let lower_row = AircraftWeight::At2300Lbs.landing_distance_lower_bound(self.pressure_altitude_ft, self.temperature_c).expect("To get the landing performance");
-		let upper_row = AircraftWeight::At2300Lbs.landing_distance_upper_bound(self.pressure_altitude_ft, self.temperature_c).expect("To get the landing performance");
+	#[test]
+	fn tailwind_up_to_10_kts_increases_distances_10_percent_per_2_kts() {
+		let take_off = cessna_with_wind(-10.0).unwrap().calc_take_off(2300);
+		assert_distance(take_off.final_distance(false), Distance(1075.0 * 1.5, 1895.0 * 1.5));
+		assert!(cessna_with_wind(-10.0).unwrap().try_calc_landing().is_ok());
+	}
 
-		self.calc_performance(lower_row, upper_row, 0.45)
+	#[test]
+	fn tailwind_over_10_kts_is_not_available() {
+		assert_eq!(cessna_with_wind(-10.5).err(), Some(PerformanceError::TailwindLimit { tailwind_kts: 10.5, limit_kts: 10.0 }));
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,148 @@
+/*
+NOTES:
+1. Short field technique as specified in Section 4.
+2. Prior to takeoff from fields above 3000 feet elevation, the mixture should be leaned to give maximum RPM in a full throttle, static run-up.
+3. Decrease distances 10% for each 9 knots headwind. For operation with tail winds up to 10 knots, increase distances by 10% for each 2 knots.
+4. For operation on dry, grass runway, increase distances by 15% of the "ground roll" figure.
+
+1. Short field technique as specified in Section 4.
+2. Decrease distances 10% for each 9 knots headwind. For operation with tail winds up to 10 knots, increase distances by 10% for each 2 knots.
+3. For operation on dry, grass runway, increase distances by 45% of the "ground roll" figure.
+4. If a landing with flaps up is necessary, increase the approach speed by 7 KIAS and allow for 35% longer distances.
+*/
+
+use lazy_static::lazy_static;
+
+use crate::data::performance::{configuration::{Configuration, ConfigurationGroup}, distance::Distance, grid::{Axis, Grid}, limits::ChartLimits, speeds::{SpeedKind, SpeedTable}};
+
+use super::cessna_tables::{pressure_altitude_axis, temperature_axis, TableNotes, Tables};
+
+const WEIGHTS_LBS: [f64; 3] = [2200.0, 2400.0, 2550.0];
+const PRESSURE_ALTITUDES_FT: [f64; 9] = [0.0, 1000.0, 2000.0, 3000.0, 4000.0, 5000.0, 6000.0, 7000.0, 8000.0];
+const TEMPERATURES_C: [f64; 5] = [0.0, 10.0, 20.0, 30.0, 40.0];
+const MAX_TAILWIND_KTS: f64 = 10.0;
+
+/// Pressure altitudes and temperatures below the tables are clamped to the first row and column, note 3 allows tailwinds up to 10 knots.
+pub const LIMITS: ChartLimits = ChartLimits {
+    max_weight_lbs: WEIGHTS_LBS[2] as i16,
+    take_off_by_weight: true,
+    by_pressure_altitude: true,
+    min_altitude_ft: None,
+    max_altitude_ft: Some(PRESSURE_ALTITUDES_FT[8]),
+    max_temperature_c: Some(TEMPERATURES_C[4]),
+    min_headwind_kts: -MAX_TAILWIND_KTS,
+    max_take_off_headwind_kts: None
+};
+
+const TAKE_OFF_AT_2550_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(845.0, 1510.0)), Some(Distance(910.0, 1625.0)), Some(Distance(975.0, 1745.0)), Some(Distance(1050.0, 1875.0)), Some(Distance(1130.0, 2015.0))],
+    [Some(Distance(925.0, 1660.0)), Some(Distance(995.0, 1785.0)), Some(Distance(1070.0, 1920.0)), Some(Distance(1150.0, 2065.0)), Some(Distance(1235.0, 2220.0))],
+    [Some(Distance(1015.0, 1825.0)), Some(Distance(1090.0, 1965.0)), Some(Distance(1170.0, 2110.0)), Some(Distance(1260.0, 2270.0)), Some(Distance(1355.0, 2440.0))],
+    [Some(Distance(1110.0, 2010.0)), Some(Distance(1195.0, 2160.0)), Some(Distance(1280.0, 2325.0)), Some(Distance(1380.0, 2495.0)), Some(Distance(1480.0, 2685.0))],
+    [Some(Distance(1215.0, 2210.0)), Some(Distance(1305.0, 2375.0)), Some(Distance(1405.0, 2555.0)), Some(Distance(1510.0, 2745.0)), Some(Distance(1620.0, 2950.0))],
+    [Some(Distance(1330.0, 2430.0)), Some(Distance(1430.0, 2615.0)), Some(Distance(1535.0, 2810.0)), Some(Distance(1655.0, 3020.0)), Some(Distance(1775.0, 3250.0))],
+    [Some(Distance(1455.0, 2675.0)), Some(Distance(1565.0, 2875.0)), Some(Distance(1685.0, 3090.0)), Some(Distance(1810.0, 3325.0)), Some(Distance(1945.0, 3570.0))],
+    [Some(Distance(1595.0, 2945.0)), Some(Distance(1715.0, 3165.0)), Some(Distance(1845.0, 3400.0)), Some(Distance(1980.0, 3655.0)), Some(Distance(2130.0, 3930.0))],
+    [Some(Distance(1745.0, 3235.0)), Some(Distance(1875.0, 3480.0)), Some(Distance(2020.0, 3740.0)), Some(Distance(2170.0, 4020.0)), Some(Distance(2330.0, 4325.0))]
+];
+
+const TAKE_OFF_AT_2400_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(745.0, 1330.0)), Some(Distance(800.0, 1430.0)), Some(Distance(860.0, 1535.0)), Some(Distance(925.0, 1650.0)), Some(Distance(995.0, 1775.0))],
+    [Some(Distance(815.0, 1460.0)), Some(Distance(875.0, 1570.0)), Some(Distance(940.0, 1690.0)), Some(Distance(1010.0, 1815.0)), Some(Distance(1090.0, 1955.0))],
+    [Some(Distance(890.0, 1610.0)), Some(Distance(960.0, 1730.0)), Some(Distance(1030.0, 1860.0)), Some(Distance(1110.0, 2000.0)), Some(Distance(1190.0, 2150.0))],
+    [Some(Distance(975.0, 1770.0)), Some(Distance(1050.0, 1900.0)), Some(Distance(1130.0, 2045.0)), Some(Distance(1215.0, 2200.0)), Some(Distance(1305.0, 2365.0))],
+    [Some(Distance(1070.0, 1945.0)), Some(Distance(1150.0, 2090.0)), Some(Distance(1235.0, 2250.0)), Some(Distance(1330.0, 2420.0)), Some(Distance(1430.0, 2600.0))],
+    [Some(Distance(1170.0, 2140.0)), Some(Distance(1260.0, 2300.0)), Some(Distance(1355.0, 2475.0)), Some(Distance(1455.0, 2660.0)), Some(Distance(1565.0, 2860.0))],
+    [Some(Distance(1280.0, 2355.0)), Some(Distance(1380.0, 2530.0)), Some(Distance(1480.0, 2720.0)), Some(Distance(1595.0, 2925.0)), Some(Distance(1715.0, 3145.0))],
+    [Some(Distance(1405.0, 2590.0)), Some(Distance(1510.0, 2785.0)), Some(Distance(1625.0, 2995.0)), Some(Distance(1745.0, 3220.0)), Some(Distance(1875.0, 3460.0))],
+    [Some(Distance(1540.0, 2850.0)), Some(Distance(1655.0, 3065.0)), Some(Distance(1775.0, 3295.0)), Some(Distance(1910.0, 3540.0)), Some(Distance(2055.0, 3805.0))]
+];
+
+const TAKE_OFF_AT_2200_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(620.0, 1105.0)), Some(Distance(665.0, 1190.0)), Some(Distance(715.0, 1280.0)), Some(Distance(770.0, 1375.0)), Some(Distance(830.0, 1480.0))],
+    [Some(Distance(680.0, 1220.0)), Some(Distance(730.0, 1310.0)), Some(Distance(785.0, 1410.0)), Some(Distance(845.0, 1515.0)), Some(Distance(905.0, 1625.0))],
+    [Some(Distance(745.0, 1340.0)), Some(Distance(800.0, 1440.0)), Some(Distance(860.0, 1550.0)), Some(Distance(925.0, 1665.0)), Some(Distance(990.0, 1790.0))],
+    [Some(Distance(815.0, 1475.0)), Some(Distance(875.0, 1585.0)), Some(Distance(940.0, 1705.0)), Some(Distance(1010.0, 1830.0)), Some(Distance(1085.0, 1970.0))],
+    [Some(Distance(890.0, 1620.0)), Some(Distance(960.0, 1745.0)), Some(Distance(1030.0, 1875.0)), Some(Distance(1105.0, 2015.0)), Some(Distance(1190.0, 2165.0))],
+    [Some(Distance(975.0, 1785.0)), Some(Distance(1050.0, 1915.0)), Some(Distance(1125.0, 2060.0)), Some(Distance(1210.0, 2215.0)), Some(Distance(1305.0, 2380.0))],
+    [Some(Distance(1070.0, 1960.0)), Some(Distance(1150.0, 2110.0)), Some(Distance(1235.0, 2265.0)), Some(Distance(1325.0, 2435.0)), Some(Distance(1425.0, 2620.0))],
+    [Some(Distance(1170.0, 2160.0)), Some(Distance(1260.0, 2320.0)), Some(Distance(1350.0, 2495.0)), Some(Distance(1455.0, 2680.0)), Some(Distance(1560.0, 2880.0))],
+    [Some(Distance(1280.0, 2375.0)), Some(Distance(1375.0, 2550.0)), Some(Distance(1480.0, 2745.0)), Some(Distance(1590.0, 2950.0)), Some(Distance(1710.0, 3170.0))]
+];
+
+const LANDING_AT_2550_LBS: [[Distance; 5]; 9] = [
+    [Distance(545.0, 1290.0), Distance(565.0, 1320.0), Distance(580.0, 1355.0), Distance(600.0, 1390.0), Distance(620.0, 1425.0)],
+    [Distance(565.0, 1330.0), Distance(585.0, 1360.0), Distance(600.0, 1395.0), Distance(620.0, 1430.0), Distance(645.0, 1465.0)],
+    [Distance(585.0, 1370.0), Distance(605.0, 1405.0), Distance(625.0, 1440.0), Distance(645.0, 1475.0), Distance(665.0, 1510.0)],
+    [Distance(605.0, 1410.0), Distance(625.0, 1445.0), Distance(645.0, 1480.0), Distance(670.0, 1520.0), Distance(690.0, 1555.0)],
+    [Distance(630.0, 1450.0), Distance(650.0, 1490.0), Distance(670.0, 1525.0), Distance(690.0, 1565.0), Distance(715.0, 1605.0)],
+    [Distance(650.0, 1495.0), Distance(670.0, 1535.0), Distance(695.0, 1570.0), Distance(715.0, 1610.0), Distance(740.0, 1650.0)],
+    [Distance(675.0, 1540.0), Distance(695.0, 1580.0), Distance(720.0, 1620.0), Distance(745.0, 1660.0), Distance(765.0, 1700.0)],
+    [Distance(700.0, 1585.0), Distance(720.0, 1625.0), Distance(745.0, 1665.0), Distance(770.0, 1710.0), Distance(795.0, 1750.0)],
+    [Distance(725.0, 1635.0), Distance(745.0, 1675.0), Distance(770.0, 1715.0), Distance(795.0, 1760.0), Distance(825.0, 1805.0)]
+];
+
+/// The take off tables are for the short field technique with flaps 10° and the landing table for flaps 30° with power off. Landing note 4 gives the flaps up landing.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+    Configuration { id: "short_field", name: "Short field, flaps 10° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.1, air_distance_factor: 1.15 },
+    Configuration { id: "full", name: "Flaps 30° (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.2, air_distance_factor: 1.25 },
+    Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.35, air_distance_factor: 1.35 },
+    Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
+
+/// The POH speeds at 2550 lbs.
+pub const SPEEDS: SpeedTable = SpeedTable {
+    weights_lbs: &WEIGHTS_LBS,
+    speeds_kias: &[
+        (SpeedKind::Vr, &[51.0, 53.0, 55.0]),
+        (SpeedKind::Vx, &[58.0, 60.0, 62.0]),
+        (SpeedKind::Vy, &[69.0, 72.0, 74.0]),
+        (SpeedKind::Vref, &[57.0, 59.0, 61.0]),
+        (SpeedKind::Vs0, &[37.0, 39.0, 40.0]),
+        (SpeedKind::Vs1, &[45.0, 47.0, 48.0]),
+        (SpeedKind::Vg, &[63.0, 66.0, 68.0])
+    ]
+};
+
+/// The maximum rate of climb chart at 2550 lbs, the rate is blank where the temperature is off the chart at altitude.
+const CLIMB_PRESSURE_ALTITUDES_FT: [f64; 7] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 12000.0];
+const CLIMB_TEMPERATURES_C: [f64; 4] = [-20.0, 0.0, 20.0, 40.0];
+const CLIMB_SPEEDS_KIAS: [f64; 7] = [74.0, 73.0, 72.0, 71.0, 70.0, 69.0, 68.0];
+const CLIMB_RATES_FPM: [[Option<f64>; 4]; 7] = [
+    [Some(855.0), Some(785.0), Some(715.0), Some(645.0)],
+    [Some(745.0), Some(675.0), Some(605.0), Some(535.0)],
+    [Some(635.0), Some(565.0), Some(495.0), None],
+    [Some(525.0), Some(455.0), Some(385.0), None],
+    [Some(415.0), Some(345.0), Some(275.0), None],
+    [Some(305.0), Some(235.0), Some(165.0), None],
+    [Some(195.0), Some(125.0), None, None]
+];
+
+lazy_static! {
+    pub static ref TABLES: Tables = Tables {
+        weights_lbs: &WEIGHTS_LBS,
+        take_off: Grid::new(
+            vec![Axis::new("weight", " lbs", &WEIGHTS_LBS), pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            [TAKE_OFF_AT_2200_LBS, TAKE_OFF_AT_2400_LBS, TAKE_OFF_AT_2550_LBS].iter().flatten().flatten().copied().collect()
+        ),
+        landing: Grid::new(
+            vec![pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            LANDING_AT_2550_LBS.iter().flatten().map(|distance| Some(*distance)).collect()
+        ),
+        climb_rate: Grid::new(
+            vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT), temperature_axis(&CLIMB_TEMPERATURES_C)],
+            CLIMB_RATES_FPM.iter().flatten().copied().collect()
+        ),
+        climb_speed: Grid::new(vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT)], CLIMB_SPEEDS_KIAS.iter().map(|speed| Some(*speed)).collect()),
+        notes: TableNotes {
+            headwind_kts_per_10_percent: 9.0,
+            tailwind_kts_per_10_percent: 2.0,
+            max_tailwind_kts: MAX_TAILWIND_KTS,
+            take_off_grass_ground_roll_percentage: 0.15,
+            landing_grass_ground_roll_percentage: 0.45
+        }
+    };
+}
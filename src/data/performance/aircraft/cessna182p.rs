@@ -0,0 +1,147 @@
+/*
+NOTES:
+1. Maximum performance technique as specified in Section 4.
+2. Prior to takeoff from fields above 3000 feet elevation, the mixture should be leaned to give maximum RPM in a full throttle, static run-up.
+3. Decrease distances 10% for each 9 knots headwind. For operation with tailwinds up to 10 knots, increase distances by 10% for each 2 knots.
+4. For operation on a dry, grass runway, increase distances by 15% of the "ground roll" figure.
+
+1. Maximum performance technique as specified in Section 4.
+2. Decrease distances 10% for each 9 knots headwind. For operation with tailwinds up to 10 knots, increase distances by 10% for each 2 knots.
+3. For operation on a dry, grass runway, increase distances by 45% of the "ground roll" figure.
+*/
+
+use lazy_static::lazy_static;
+
+use crate::data::performance::{configuration::{Configuration, ConfigurationGroup}, distance::Distance, grid::{Axis, Grid}, limits::ChartLimits, speeds::{SpeedKind, SpeedTable}};
+
+use super::cessna_tables::{pressure_altitude_axis, temperature_axis, TableNotes, Tables};
+
+const WEIGHTS_LBS: [f64; 3] = [2400.0, 2700.0, 2950.0];
+const PRESSURE_ALTITUDES_FT: [f64; 9] = [0.0, 1000.0, 2000.0, 3000.0, 4000.0, 5000.0, 6000.0, 7000.0, 8000.0];
+const TEMPERATURES_C: [f64; 5] = [0.0, 10.0, 20.0, 30.0, 40.0];
+const MAX_TAILWIND_KTS: f64 = 10.0;
+
+/// Pressure altitudes and temperatures below the tables are clamped to the first row and column, note 3 allows tailwinds up to 10 knots.
+pub const LIMITS: ChartLimits = ChartLimits {
+    max_weight_lbs: WEIGHTS_LBS[2] as i16,
+    take_off_by_weight: true,
+    by_pressure_altitude: true,
+    min_altitude_ft: None,
+    max_altitude_ft: Some(PRESSURE_ALTITUDES_FT[8]),
+    max_temperature_c: Some(TEMPERATURES_C[4]),
+    min_headwind_kts: -MAX_TAILWIND_KTS,
+    max_take_off_headwind_kts: None
+};
+
+const TAKE_OFF_AT_2950_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(705.0, 1350.0)), Some(Distance(760.0, 1450.0)), Some(Distance(815.0, 1560.0)), Some(Distance(875.0, 1675.0)), Some(Distance(940.0, 1805.0))],
+    [Some(Distance(770.0, 1485.0)), Some(Distance(830.0, 1595.0)), Some(Distance(890.0, 1715.0)), Some(Distance(960.0, 1845.0)), Some(Distance(1030.0, 1985.0))],
+    [Some(Distance(845.0, 1635.0)), Some(Distance(910.0, 1755.0)), Some(Distance(975.0, 1890.0)), Some(Distance(1050.0, 2030.0)), Some(Distance(1130.0, 2180.0))],
+    [Some(Distance(925.0, 1795.0)), Some(Distance(995.0, 1930.0)), Some(Distance(1070.0, 2075.0)), Some(Distance(1150.0, 2230.0)), Some(Distance(1235.0, 2400.0))],
+    [Some(Distance(1015.0, 1975.0)), Some(Distance(1090.0, 2125.0)), Some(Distance(1170.0, 2285.0)), Some(Distance(1260.0, 2455.0)), Some(Distance(1355.0, 2640.0))],
+    [Some(Distance(1110.0, 2175.0)), Some(Distance(1195.0, 2335.0)), Some(Distance(1285.0, 2515.0)), Some(Distance(1380.0, 2700.0)), Some(Distance(1480.0, 2905.0))],
+    [Some(Distance(1215.0, 2390.0)), Some(Distance(1305.0, 2570.0)), Some(Distance(1405.0, 2765.0)), Some(Distance(1510.0, 2970.0)), Some(Distance(1625.0, 3195.0))],
+    [Some(Distance(1330.0, 2630.0)), Some(Distance(1430.0, 2830.0)), Some(Distance(1540.0, 3040.0)), Some(Distance(1655.0, 3270.0)), Some(Distance(1775.0, 3515.0))],
+    [Some(Distance(1455.0, 2895.0)), Some(Distance(1565.0, 3110.0)), Some(Distance(1685.0, 3345.0)), Some(Distance(1810.0, 3595.0)), Some(Distance(1945.0, 3865.0))]
+];
+
+const TAKE_OFF_AT_2700_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(585.0, 1120.0)), Some(Distance(630.0, 1205.0)), Some(Distance(675.0, 1295.0)), Some(Distance(725.0, 1395.0)), Some(Distance(780.0, 1495.0))],
+    [Some(Distance(640.0, 1235.0)), Some(Distance(690.0, 1325.0)), Some(Distance(740.0, 1425.0)), Some(Distance(795.0, 1530.0)), Some(Distance(855.0, 1645.0))],
+    [Some(Distance(700.0, 1355.0)), Some(Distance(755.0, 1460.0)), Some(Distance(810.0, 1565.0)), Some(Distance(870.0, 1685.0)), Some(Distance(935.0, 1810.0))],
+    [Some(Distance(770.0, 1490.0)), Some(Distance(825.0, 1605.0)), Some(Distance(890.0, 1725.0)), Some(Distance(955.0, 1855.0)), Some(Distance(1025.0, 1990.0))],
+    [Some(Distance(840.0, 1640.0)), Some(Distance(905.0, 1765.0)), Some(Distance(975.0, 1895.0)), Some(Distance(1045.0, 2040.0)), Some(Distance(1125.0, 2190.0))],
+    [Some(Distance(920.0, 1805.0)), Some(Distance(990.0, 1940.0)), Some(Distance(1065.0, 2085.0)), Some(Distance(1145.0, 2245.0)), Some(Distance(1230.0, 2410.0))],
+    [Some(Distance(1010.0, 1985.0)), Some(Distance(1085.0, 2135.0)), Some(Distance(1165.0, 2295.0)), Some(Distance(1255.0, 2465.0)), Some(Distance(1350.0, 2650.0))],
+    [Some(Distance(1105.0, 2185.0)), Some(Distance(1190.0, 2350.0)), Some(Distance(1275.0, 2525.0)), Some(Distance(1375.0, 2715.0)), Some(Distance(1475.0, 2915.0))],
+    [Some(Distance(1210.0, 2405.0)), Some(Distance(1300.0, 2585.0)), Some(Distance(1400.0, 2775.0)), Some(Distance(1505.0, 2985.0)), Some(Distance(1615.0, 3210.0))]
+];
+
+const TAKE_OFF_AT_2400_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(455.0, 875.0)), Some(Distance(490.0, 940.0)), Some(Distance(530.0, 1010.0)), Some(Distance(570.0, 1085.0)), Some(Distance(610.0, 1170.0))],
+    [Some(Distance(500.0, 965.0)), Some(Distance(540.0, 1035.0)), Some(Distance(580.0, 1115.0)), Some(Distance(620.0, 1195.0)), Some(Distance(670.0, 1285.0))],
+    [Some(Distance(550.0, 1060.0)), Some(Distance(590.0, 1140.0)), Some(Distance(635.0, 1225.0)), Some(Distance(680.0, 1315.0)), Some(Distance(730.0, 1415.0))],
+    [Some(Distance(600.0, 1165.0)), Some(Distance(645.0, 1250.0)), Some(Distance(695.0, 1345.0)), Some(Distance(745.0, 1445.0)), Some(Distance(800.0, 1555.0))],
+    [Some(Distance(655.0, 1280.0)), Some(Distance(705.0, 1380.0)), Some(Distance(760.0, 1480.0)), Some(Distance(815.0, 1590.0)), Some(Distance(880.0, 1710.0))],
+    [Some(Distance(720.0, 1410.0)), Some(Distance(775.0, 1515.0)), Some(Distance(830.0, 1630.0)), Some(Distance(895.0, 1750.0)), Some(Distance(960.0, 1885.0))],
+    [Some(Distance(790.0, 1550.0)), Some(Distance(845.0, 1665.0)), Some(Distance(910.0, 1790.0)), Some(Distance(980.0, 1925.0)), Some(Distance(1050.0, 2070.0))],
+    [Some(Distance(865.0, 1705.0)), Some(Distance(925.0, 1835.0)), Some(Distance(995.0, 1970.0)), Some(Distance(1070.0, 2120.0)), Some(Distance(1150.0, 2280.0))],
+    [Some(Distance(945.0, 1875.0)), Some(Distance(1015.0, 2015.0)), Some(Distance(1090.0, 2170.0)), Some(Distance(1175.0, 2330.0)), Some(Distance(1260.0, 2505.0))]
+];
+
+const LANDING_AT_2950_LBS: [[Distance; 5]; 9] = [
+    [Distance(540.0, 1275.0), Distance(560.0, 1305.0), Distance(575.0, 1340.0), Distance(595.0, 1375.0), Distance(615.0, 1405.0)],
+    [Distance(560.0, 1315.0), Distance(580.0, 1345.0), Distance(595.0, 1380.0), Distance(615.0, 1415.0), Distance(635.0, 1450.0)],
+    [Distance(580.0, 1355.0), Distance(600.0, 1385.0), Distance(620.0, 1420.0), Distance(640.0, 1455.0), Distance(660.0, 1495.0)],
+    [Distance(600.0, 1395.0), Distance(620.0, 1430.0), Distance(640.0, 1465.0), Distance(660.0, 1500.0), Distance(685.0, 1540.0)],
+    [Distance(620.0, 1435.0), Distance(645.0, 1470.0), Distance(665.0, 1510.0), Distance(685.0, 1545.0), Distance(710.0, 1585.0)],
+    [Distance(645.0, 1480.0), Distance(665.0, 1515.0), Distance(690.0, 1555.0), Distance(710.0, 1590.0), Distance(735.0, 1630.0)],
+    [Distance(670.0, 1520.0), Distance(690.0, 1560.0), Distance(710.0, 1600.0), Distance(735.0, 1640.0), Distance(760.0, 1680.0)],
+    [Distance(690.0, 1570.0), Distance(715.0, 1605.0), Distance(740.0, 1645.0), Distance(760.0, 1690.0), Distance(790.0, 1730.0)],
+    [Distance(715.0, 1615.0), Distance(740.0, 1655.0), Distance(765.0, 1695.0), Distance(790.0, 1740.0), Distance(815.0, 1785.0)]
+];
+
+/// The take off tables are for the short field technique with flaps 20° and the landing table for flaps 40° with power off.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+    Configuration { id: "short_field", name: "Short field, flaps 20° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.15, air_distance_factor: 1.2 },
+    Configuration { id: "full", name: "Flaps 40° (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.25, air_distance_factor: 1.3 },
+    Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.4, air_distance_factor: 1.45 },
+    Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
+
+/// The POH speeds at 2950 lbs.
+pub const SPEEDS: SpeedTable = SpeedTable {
+    weights_lbs: &WEIGHTS_LBS,
+    speeds_kias: &[
+        (SpeedKind::Vr, &[45.0, 48.0, 50.0]),
+        (SpeedKind::Vx, &[55.0, 58.0, 61.0]),
+        (SpeedKind::Vy, &[70.0, 75.0, 78.0]),
+        (SpeedKind::Vref, &[54.0, 57.0, 60.0]),
+        (SpeedKind::Vs0, &[43.0, 46.0, 48.0]),
+        (SpeedKind::Vs1, &[49.0, 52.0, 54.0]),
+        (SpeedKind::Vg, &[63.0, 67.0, 70.0])
+    ]
+};
+
+/// The maximum rate of climb chart at 2950 lbs, the rate is blank where the temperature is off the chart at altitude.
+const CLIMB_PRESSURE_ALTITUDES_FT: [f64; 7] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 12000.0];
+const CLIMB_TEMPERATURES_C: [f64; 4] = [-20.0, 0.0, 20.0, 40.0];
+const CLIMB_SPEEDS_KIAS: [f64; 7] = [78.0, 77.0, 76.0, 75.0, 74.0, 73.0, 72.0];
+const CLIMB_RATES_FPM: [[Option<f64>; 4]; 7] = [
+    [Some(1030.0), Some(965.0), Some(900.0), Some(835.0)],
+    [Some(905.0), Some(840.0), Some(775.0), Some(710.0)],
+    [Some(780.0), Some(715.0), Some(650.0), None],
+    [Some(655.0), Some(590.0), Some(525.0), None],
+    [Some(530.0), Some(465.0), Some(400.0), None],
+    [Some(405.0), Some(340.0), Some(275.0), None],
+    [Some(280.0), Some(215.0), Some(150.0), None]
+];
+
+lazy_static! {
+    pub static ref TABLES: Tables = Tables {
+        weights_lbs: &WEIGHTS_LBS,
+        take_off: Grid::new(
+            vec![Axis::new("weight", " lbs", &WEIGHTS_LBS), pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            [TAKE_OFF_AT_2400_LBS, TAKE_OFF_AT_2700_LBS, TAKE_OFF_AT_2950_LBS].iter().flatten().flatten().copied().collect()
+        ),
+        landing: Grid::new(
+            vec![pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            LANDING_AT_2950_LBS.iter().flatten().map(|distance| Some(*distance)).collect()
+        ),
+        climb_rate: Grid::new(
+            vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT), temperature_axis(&CLIMB_TEMPERATURES_C)],
+            CLIMB_RATES_FPM.iter().flatten().copied().collect()
+        ),
+        climb_speed: Grid::new(vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT)], CLIMB_SPEEDS_KIAS.iter().map(|speed| Some(*speed)).collect()),
+        notes: TableNotes {
+            headwind_kts_per_10_percent: 9.0,
+            tailwind_kts_per_10_percent: 2.0,
+            max_tailwind_kts: MAX_TAILWIND_KTS,
+            take_off_grass_ground_roll_percentage: 0.15,
+            landing_grass_ground_roll_percentage: 0.45
+        }
+    };
+}
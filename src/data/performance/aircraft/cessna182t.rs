@@ -0,0 +1,148 @@
+/*
+NOTES:
+1. Short field technique as specified in Section 4.
+2. Prior to takeoff from fields above 3000 feet elevation, the mixture should be leaned to give maximum RPM in a full throttle, static run-up.
+3. Decrease distances 10% for each 11 knots headwind. For operation with tail winds up to 10 knots, increase distances by 10% for each 2 knots.
+4. For operation on dry, grass runway, increase distances by 15% of the "ground roll" figure.
+
+1. Short field technique as specified in Section 4.
+2. Decrease distances 10% for each 11 knots headwind. For operation with tail winds up to 10 knots, increase distances by 10% for each 2 knots.
+3. For operation on dry, grass runway, increase distances by 40% of the "ground roll" figure.
+4. If a landing with flaps up is necessary, increase the approach speed by 10 KIAS and allow for 40% longer distances.
+*/
+
+use lazy_static::lazy_static;
+
+use crate::data::performance::{configuration::{Configuration, ConfigurationGroup}, distance::Distance, grid::{Axis, Grid}, limits::ChartLimits, speeds::{SpeedKind, SpeedTable}};
+
+use super::cessna_tables::{pressure_altitude_axis, temperature_axis, TableNotes, Tables};
+
+const WEIGHTS_LBS: [f64; 3] = [2500.0, 2800.0, 3100.0];
+const PRESSURE_ALTITUDES_FT: [f64; 9] = [0.0, 1000.0, 2000.0, 3000.0, 4000.0, 5000.0, 6000.0, 7000.0, 8000.0];
+const TEMPERATURES_C: [f64; 5] = [0.0, 10.0, 20.0, 30.0, 40.0];
+const MAX_TAILWIND_KTS: f64 = 10.0;
+
+/// Pressure altitudes and temperatures below the tables are clamped to the first row and column, note 3 allows tailwinds up to 10 knots.
+pub const LIMITS: ChartLimits = ChartLimits {
+    max_weight_lbs: WEIGHTS_LBS[2] as i16,
+    take_off_by_weight: true,
+    by_pressure_altitude: true,
+    min_altitude_ft: None,
+    max_altitude_ft: Some(PRESSURE_ALTITUDES_FT[8]),
+    max_temperature_c: Some(TEMPERATURES_C[4]),
+    min_headwind_kts: -MAX_TAILWIND_KTS,
+    max_take_off_headwind_kts: None
+};
+
+const TAKE_OFF_AT_3100_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(795.0, 1460.0)), Some(Distance(855.0, 1570.0)), Some(Distance(920.0, 1685.0)), Some(Distance(990.0, 1815.0)), Some(Distance(1060.0, 1950.0))],
+    [Some(Distance(870.0, 1605.0)), Some(Distance(935.0, 1725.0)), Some(Distance(1005.0, 1855.0)), Some(Distance(1080.0, 1995.0)), Some(Distance(1165.0, 2145.0))],
+    [Some(Distance(955.0, 1765.0)), Some(Distance(1025.0, 1900.0)), Some(Distance(1100.0, 2040.0)), Some(Distance(1185.0, 2195.0)), Some(Distance(1275.0, 2360.0))],
+    [Some(Distance(1045.0, 1945.0)), Some(Distance(1120.0, 2090.0)), Some(Distance(1205.0, 2245.0)), Some(Distance(1295.0, 2415.0)), Some(Distance(1395.0, 2595.0))],
+    [Some(Distance(1145.0, 2140.0)), Some(Distance(1230.0, 2300.0)), Some(Distance(1320.0, 2470.0)), Some(Distance(1420.0, 2655.0)), Some(Distance(1525.0, 2855.0))],
+    [Some(Distance(1250.0, 2350.0)), Some(Distance(1345.0, 2530.0)), Some(Distance(1445.0, 2715.0)), Some(Distance(1555.0, 2920.0)), Some(Distance(1670.0, 3140.0))],
+    [Some(Distance(1370.0, 2585.0)), Some(Distance(1475.0, 2780.0)), Some(Distance(1585.0, 2990.0)), Some(Distance(1700.0, 3215.0)), Some(Distance(1830.0, 3455.0))],
+    [Some(Distance(1500.0, 2845.0)), Some(Distance(1615.0, 3060.0)), Some(Distance(1735.0, 3290.0)), Some(Distance(1865.0, 3535.0)), Some(Distance(2005.0, 3800.0))],
+    [Some(Distance(1645.0, 3130.0)), Some(Distance(1765.0, 3365.0)), Some(Distance(1900.0, 3615.0)), Some(Distance(2040.0, 3890.0)), Some(Distance(2195.0, 4180.0))]
+];
+
+const TAKE_OFF_AT_2800_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(640.0, 1180.0)), Some(Distance(690.0, 1265.0)), Some(Distance(740.0, 1365.0)), Some(Distance(800.0, 1465.0)), Some(Distance(855.0, 1575.0))],
+    [Some(Distance(705.0, 1295.0)), Some(Distance(755.0, 1395.0)), Some(Distance(810.0, 1500.0)), Some(Distance(875.0, 1610.0)), Some(Distance(940.0, 1730.0))],
+    [Some(Distance(770.0, 1425.0)), Some(Distance(830.0, 1535.0)), Some(Distance(890.0, 1650.0)), Some(Distance(955.0, 1770.0)), Some(Distance(1030.0, 1905.0))],
+    [Some(Distance(845.0, 1570.0)), Some(Distance(905.0, 1685.0)), Some(Distance(975.0, 1815.0)), Some(Distance(1045.0, 1950.0)), Some(Distance(1125.0, 2095.0))],
+    [Some(Distance(925.0, 1725.0)), Some(Distance(990.0, 1855.0)), Some(Distance(1065.0, 1995.0)), Some(Distance(1145.0, 2145.0)), Some(Distance(1235.0, 2305.0))],
+    [Some(Distance(1010.0, 1900.0)), Some(Distance(1085.0, 2040.0)), Some(Distance(1170.0, 2195.0)), Some(Distance(1255.0, 2360.0)), Some(Distance(1350.0, 2535.0))],
+    [Some(Distance(1105.0, 2090.0)), Some(Distance(1190.0, 2245.0)), Some(Distance(1280.0, 2415.0)), Some(Distance(1375.0, 2595.0)), Some(Distance(1480.0, 2790.0))],
+    [Some(Distance(1210.0, 2300.0)), Some(Distance(1305.0, 2470.0)), Some(Distance(1400.0, 2655.0)), Some(Distance(1505.0, 2855.0)), Some(Distance(1620.0, 3070.0))],
+    [Some(Distance(1325.0, 2525.0)), Some(Distance(1425.0, 2715.0)), Some(Distance(1535.0, 2920.0)), Some(Distance(1650.0, 3140.0)), Some(Distance(1770.0, 3375.0))]
+];
+
+const TAKE_OFF_AT_2500_LBS: [[Option<Distance>; 5]; 9] = [
+    [Some(Distance(505.0, 930.0)), Some(Distance(545.0, 1000.0)), Some(Distance(585.0, 1075.0)), Some(Distance(630.0, 1155.0)), Some(Distance(675.0, 1240.0))],
+    [Some(Distance(555.0, 1020.0)), Some(Distance(595.0, 1100.0)), Some(Distance(640.0, 1180.0)), Some(Distance(690.0, 1270.0)), Some(Distance(740.0, 1365.0))],
+    [Some(Distance(605.0, 1125.0)), Some(Distance(650.0, 1210.0)), Some(Distance(700.0, 1300.0)), Some(Distance(755.0, 1395.0)), Some(Distance(810.0, 1500.0))],
+    [Some(Distance(665.0, 1235.0)), Some(Distance(715.0, 1330.0)), Some(Distance(770.0, 1430.0)), Some(Distance(825.0, 1535.0)), Some(Distance(885.0, 1650.0))],
+    [Some(Distance(730.0, 1360.0)), Some(Distance(780.0, 1465.0)), Some(Distance(840.0, 1570.0)), Some(Distance(905.0, 1690.0)), Some(Distance(970.0, 1815.0))],
+    [Some(Distance(795.0, 1495.0)), Some(Distance(855.0, 1610.0)), Some(Distance(920.0, 1730.0)), Some(Distance(990.0, 1860.0)), Some(Distance(1065.0, 2000.0))],
+    [Some(Distance(870.0, 1645.0)), Some(Distance(940.0, 1770.0)), Some(Distance(1010.0, 1905.0)), Some(Distance(1085.0, 2045.0)), Some(Distance(1165.0, 2200.0))],
+    [Some(Distance(955.0, 1810.0)), Some(Distance(1025.0, 1945.0)), Some(Distance(1105.0, 2095.0)), Some(Distance(1185.0, 2250.0)), Some(Distance(1275.0, 2420.0))],
+    [Some(Distance(1045.0, 1990.0)), Some(Distance(1125.0, 2140.0)), Some(Distance(1210.0, 2300.0)), Some(Distance(1300.0, 2475.0)), Some(Distance(1395.0, 2660.0))]
+];
+
+const LANDING_AT_3100_LBS: [[Distance; 5]; 9] = [
+    [Distance(590.0, 1300.0), Distance(610.0, 1330.0), Distance(630.0, 1365.0), Distance(650.0, 1400.0), Distance(670.0, 1435.0)],
+    [Distance(610.0, 1340.0), Distance(630.0, 1370.0), Distance(650.0, 1405.0), Distance(675.0, 1440.0), Distance(695.0, 1480.0)],
+    [Distance(635.0, 1380.0), Distance(655.0, 1415.0), Distance(675.0, 1450.0), Distance(700.0, 1485.0), Distance(720.0, 1520.0)],
+    [Distance(655.0, 1420.0), Distance(680.0, 1455.0), Distance(700.0, 1490.0), Distance(725.0, 1530.0), Distance(745.0, 1570.0)],
+    [Distance(680.0, 1465.0), Distance(700.0, 1500.0), Distance(725.0, 1535.0), Distance(750.0, 1575.0), Distance(775.0, 1615.0)],
+    [Distance(705.0, 1505.0), Distance(725.0, 1545.0), Distance(750.0, 1585.0), Distance(775.0, 1625.0), Distance(800.0, 1665.0)],
+    [Distance(730.0, 1550.0), Distance(755.0, 1590.0), Distance(780.0, 1630.0), Distance(805.0, 1670.0), Distance(830.0, 1715.0)],
+    [Distance(755.0, 1600.0), Distance(780.0, 1640.0), Distance(805.0, 1680.0), Distance(835.0, 1720.0), Distance(860.0, 1765.0)],
+    [Distance(785.0, 1645.0), Distance(810.0, 1690.0), Distance(835.0, 1730.0), Distance(865.0, 1775.0), Distance(890.0, 1820.0)]
+];
+
+/// The take off tables are for the short field technique with flaps 20° and the landing table for full flaps with power off. Landing note 4 gives the flaps up landing.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+    Configuration { id: "short_field", name: "Short field, flaps 20° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.15, air_distance_factor: 1.2 },
+    Configuration { id: "full", name: "Flaps full (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.25, air_distance_factor: 1.3 },
+    Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.4, air_distance_factor: 1.4 },
+    Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
+
+/// The POH speeds at 3100 lbs.
+pub const SPEEDS: SpeedTable = SpeedTable {
+    weights_lbs: &WEIGHTS_LBS,
+    speeds_kias: &[
+        (SpeedKind::Vr, &[45.0, 48.0, 50.0]),
+        (SpeedKind::Vx, &[57.0, 60.0, 63.0]),
+        (SpeedKind::Vy, &[72.0, 76.0, 80.0]),
+        (SpeedKind::Vref, &[54.0, 57.0, 60.0]),
+        (SpeedKind::Vs0, &[44.0, 47.0, 49.0]),
+        (SpeedKind::Vs1, &[48.0, 51.0, 54.0]),
+        (SpeedKind::Vg, &[68.0, 72.0, 76.0])
+    ]
+};
+
+/// The maximum rate of climb chart at 3100 lbs, the rate is blank where the temperature is off the chart at altitude.
+const CLIMB_PRESSURE_ALTITUDES_FT: [f64; 7] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 12000.0];
+const CLIMB_TEMPERATURES_C: [f64; 4] = [-20.0, 0.0, 20.0, 40.0];
+const CLIMB_SPEEDS_KIAS: [f64; 7] = [80.0, 79.0, 78.0, 77.0, 76.0, 75.0, 74.0];
+const CLIMB_RATES_FPM: [[Option<f64>; 4]; 7] = [
+    [Some(1000.0), Some(935.0), Some(870.0), Some(805.0)],
+    [Some(880.0), Some(815.0), Some(750.0), Some(685.0)],
+    [Some(760.0), Some(695.0), Some(630.0), None],
+    [Some(640.0), Some(575.0), Some(510.0), None],
+    [Some(520.0), Some(455.0), Some(390.0), None],
+    [Some(400.0), Some(335.0), Some(270.0), None],
+    [Some(280.0), Some(215.0), Some(150.0), None]
+];
+
+lazy_static! {
+    pub static ref TABLES: Tables = Tables {
+        weights_lbs: &WEIGHTS_LBS,
+        take_off: Grid::new(
+            vec![Axis::new("weight", " lbs", &WEIGHTS_LBS), pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            [TAKE_OFF_AT_2500_LBS, TAKE_OFF_AT_2800_LBS, TAKE_OFF_AT_3100_LBS].iter().flatten().flatten().copied().collect()
+        ),
+        landing: Grid::new(
+            vec![pressure_altitude_axis(&PRESSURE_ALTITUDES_FT), temperature_axis(&TEMPERATURES_C)],
+            LANDING_AT_3100_LBS.iter().flatten().map(|distance| Some(*distance)).collect()
+        ),
+        climb_rate: Grid::new(
+            vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT), temperature_axis(&CLIMB_TEMPERATURES_C)],
+            CLIMB_RATES_FPM.iter().flatten().copied().collect()
+        ),
+        climb_speed: Grid::new(vec![pressure_altitude_axis(&CLIMB_PRESSURE_ALTITUDES_FT)], CLIMB_SPEEDS_KIAS.iter().map(|speed| Some(*speed)).collect()),
+        notes: TableNotes {
+            headwind_kts_per_10_percent: 11.0,
+            tailwind_kts_per_10_percent: 2.0,
+            max_tailwind_kts: MAX_TAILWIND_KTS,
+            take_off_grass_ground_roll_percentage: 0.15,
+            landing_grass_ground_roll_percentage: 0.4
+        }
+    };
+}
@@ -0,0 +1,235 @@
+//! The take off and landing tables of the later Cessna POHs, by pressure altitude and temperature for a few weights, with the
+//! wind and grass corrections from the notes under them.
+
+use crate::{
+    data::performance::{climb::Climb, distance::Distance, error::PerformanceError, grid::{Axis, Extrapolation, Grid, GridError, Lookup}, performance_row::PerformanceRow},
+    math::{Length, Pressure, PressureAltitudeMethod, Temperature, Velocity}
+};
+
+/// The notes printed under the take off and landing tables.
+pub struct TableNotes {
+    /// Decrease distances 10% for each this many knots of headwind.
+    pub headwind_kts_per_10_percent: f64,
+    /// Increase distances 10% for each this many knots of tailwind, up to the maximum tailwind.
+    pub tailwind_kts_per_10_percent: f64,
+    pub max_tailwind_kts: f64,
+    /// For a dry, grass runway increase distances by this much of the ground roll.
+    pub take_off_grass_ground_roll_percentage: f64,
+    pub landing_grass_ground_roll_percentage: f64
+}
+
+/// The take off tables are by weight, the landing table and climb chart are for the maximum weight.
+pub struct Tables {
+    /// Ascending, the last is the maximum weight.
+    pub weights_lbs: &'static [f64],
+    pub take_off: Grid<Distance>,
+    pub landing: Grid<Distance>,
+    pub climb_rate: Grid<f64>,
+    pub climb_speed: Grid<f64>,
+    pub notes: TableNotes
+}
+
+/// Pressure altitudes and temperatures below the tables are clamped to the first row and column.
+pub fn pressure_altitude_axis(pressure_altitudes_ft: &[f64]) -> Axis {
+    Axis::new("pressure altitude", "'", pressure_altitudes_ft).extrapolate(Extrapolation::Clamp, Extrapolation::Error)
+}
+
+pub fn temperature_axis(temperatures_c: &[f64]) -> Axis {
+    Axis::new("temperature", "°C", temperatures_c).extrapolate(Extrapolation::Clamp, Extrapolation::Error)
+}
+
+impl Tables {
+    /// The weight of the table to use, the next heavier weight than the aircraft's.
+    pub fn find_take_off_weight(&self, weight_lbs: i16) -> Result<f64, &'static str> {
+        self.weights_lbs.iter()
+            .find(|table_weight_lbs| weight_lbs as f64 <= **table_weight_lbs)
+            .copied()
+            .ok_or("Over max weight")
+    }
+}
+
+pub struct Corrections {
+    pub wind_correction_percentage: f64,
+    pub grass_ground_roll_percentage: f64,
+    pub distance_corrected_for_wind: Distance,
+    pub grass_offset: f64,
+    pub distance_corrected_for_grass: Distance
+}
+
+pub struct Performance {
+    pub pressure_altitude_tween_percentage: f64,
+    pub temperature_c_tween_percentage: f64,
+    pub lower_temperature_c: f64,
+    pub upper_temperature_c: f64,
+    pub distance_rows: [PerformanceRow; 3],
+    pub lookup: Lookup<Distance>,
+    pub corrections: Corrections,
+    pub aircraft_weight_lbs: Option<i16>
+}
+
+impl Performance {
+    pub fn final_distance(&self, is_grass: bool) -> Distance {
+        if is_grass {
+            self.corrections.distance_corrected_for_grass
+        } else {
+            self.corrections.distance_corrected_for_wind
+        }
+    }
+}
+
+pub struct Cessna {
+    pub tables: &'static Tables,
+    pub headwind: Velocity,
+    pub pressure_in_hg: f64,
+    pub elevation_ft: f64,
+    pub pressure_altitude_ft: f64,
+    pub pressure_altitude_method: PressureAltitudeMethod,
+    pub temperature_c: f64
+}
+
+impl Cessna {
+    pub fn new(tables: &'static Tables, headwind: Velocity, elevation: Length, pressure: Pressure, pressure_altitude_method: PressureAltitudeMethod, temperature: Temperature) -> Self {
+        Cessna {
+            tables,
+            headwind,
+            pressure_in_hg: pressure.in_hg(),
+            elevation_ft: elevation.feet(),
+            pressure_altitude_ft: pressure.altitude(elevation, pressure_altitude_method).feet(),
+            pressure_altitude_method,
+            temperature_c: temperature.celsius()
+        }
+    }
+
+    pub fn try_new(tables: &'static Tables, headwind: Velocity, elevation: Length, pressure: Pressure, pressure_altitude_method: PressureAltitudeMethod, temperature: Temperature) -> Result<Self, PerformanceError> {
+        let tailwind_kts = -headwind.knots();
+        let limit_kts = tables.notes.max_tailwind_kts;
+        if tailwind_kts > limit_kts {
+            return Err(PerformanceError::TailwindLimit { tailwind_kts, limit_kts });
+        }
+
+        Ok(Self::new(tables, headwind, elevation, pressure, pressure_altitude_method, temperature))
+    }
+
+    pub fn pressure_hpa(&self) -> f64 {
+        Pressure::InchesOfMercury(self.pressure_in_hg).hpa()
+    }
+
+    fn calc_corrections(&self, distance_at_elevation: Distance, grass_ground_roll_percentage: f64) -> Corrections {
+        let notes = &self.tables.notes;
+        let headwind_kts = self.headwind.knots();
+        let wind_correction_percentage = if headwind_kts > 0.0 {
+            1.0 - (0.1 * (headwind_kts / notes.headwind_kts_per_10_percent))
+        } else {
+            1.0 + (0.1 * (-headwind_kts / notes.tailwind_kts_per_10_percent))
+        };
+
+        let distance_corrected_for_wind = distance_at_elevation.scale(wind_correction_percentage);
+
+        let grass_offset = distance_corrected_for_wind.ground_run_ft() * grass_ground_roll_percentage;
+        let distance_corrected_for_grass = distance_corrected_for_wind.offset(grass_offset);
+
+        Corrections {
+            wind_correction_percentage,
+            grass_ground_roll_percentage,
+            distance_corrected_for_wind,
+            grass_offset,
+            distance_corrected_for_grass
+        }
+    }
+
+    /// Interpolates temperature then pressure altitude, any leading axes of the grid are held at the start of the point.
+    fn calc_performance(&self, grid: &Grid<Distance>, fixed: &[f64], grass_ground_roll_percentage: f64) -> Result<Performance, GridError> {
+        let pressure_altitude_axis = fixed.len();
+        let temperature_axis = pressure_altitude_axis + 1;
+        let point = [fixed, &[self.pressure_altitude_ft, self.temperature_c]].concat();
+
+        let lookup = grid.lookup(&point)?;
+        let pressure_altitude = lookup.brackets[pressure_altitude_axis];
+        let temperature = lookup.brackets[temperature_axis];
+        let [lower_row, middle_row, upper_row] = grid.cross_section(&point, pressure_altitude_axis, temperature_axis)?;
+
+        let distance_rows = [
+            PerformanceRow::new_labeled(pressure_altitude.lower, lower_row[0], lower_row[1], lower_row[2]),
+            PerformanceRow::new_labeled(self.pressure_altitude_ft, middle_row[0], middle_row[1], middle_row[2]),
+            PerformanceRow::new_labeled(pressure_altitude.upper, upper_row[0], upper_row[1], upper_row[2])
+        ];
+
+        Ok(Performance {
+            pressure_altitude_tween_percentage: pressure_altitude.fraction,
+            temperature_c_tween_percentage: temperature.fraction,
+            lower_temperature_c: temperature.lower,
+            upper_temperature_c: temperature.upper,
+            distance_rows,
+            corrections: self.calc_corrections(lookup.value, grass_ground_roll_percentage),
+            lookup,
+            aircraft_weight_lbs: None
+        })
+    }
+
+    pub fn try_calc_take_off(&self, aircraft_weight_lbs: i16) -> Result<Performance, PerformanceError> {
+        let take_off_weight_lbs = self.tables.find_take_off_weight(aircraft_weight_lbs)?;
+        let mut result = self.calc_performance(&self.tables.take_off, &[take_off_weight_lbs], self.tables.notes.take_off_grass_ground_roll_percentage).map_err(|err| match err {
+            GridError::MissingCell { point } => PerformanceError::ClimbLimited {
+                aircraft_weight_lbs: point[0].2 as i16,
+                pressure_altitude_ft: point[1].2 as i16,
+                temperature_c: point[2].2 as i16
+            },
+            err => err.into()
+        })?;
+
+        result.aircraft_weight_lbs = Some(take_off_weight_lbs as i16);
+
+        Ok(result)
+    }
+
+    pub fn calc_take_off(&self, aircraft_weight_lbs: i16) -> Performance {
+        self.try_calc_take_off(aircraft_weight_lbs).expect("To get the take off performance")
+    }
+
+    pub fn try_calc_landing(&self) -> Result<Performance, PerformanceError> {
+        Ok(self.calc_performance(&self.tables.landing, &[], self.tables.notes.landing_grass_ground_roll_percentage)?)
+    }
+
+    pub fn calc_landing(&self) -> Performance {
+        self.try_calc_landing().expect("To get the landing performance")
+    }
+
+    /// The chart is for the maximum weight, which is conservative for a lighter aircraft.
+    pub fn calc_climb(&self) -> Result<Climb, PerformanceError> {
+        let rate_fpm = self.tables.climb_rate.lookup(&[self.pressure_altitude_ft, self.temperature_c])?.value;
+        let speed_kias = self.tables.climb_speed.lookup(&[self.pressure_altitude_ft])?.value;
+
+        Ok(Climb { rate_fpm, speed_kias })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::performance::aircraft::{cessna172m, cessna172s, cessna182p, cessna182t};
+
+    use super::*;
+
+    fn cessna(tables: &'static Tables, headwind_kts: f64) -> Result<Cessna, PerformanceError> {
+        Cessna::try_new(tables, Velocity::Knots(headwind_kts), Length::Feet(2000.0), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(20.0))
+    }
+
+    /// The behaviour each model shares, driven by its own weights and notes.
+    #[test]
+    fn each_model_reads_its_weights_and_wind_notes() {
+        for (aircraft, tables) in [("172M", &*cessna172m::TABLES), ("172S", &*cessna172s::TABLES), ("182P", &*cessna182p::TABLES), ("182T", &*cessna182t::TABLES)] {
+            let max_weight_lbs = tables.weights_lbs[tables.weights_lbs.len() - 1] as i16;
+
+            for weights_lbs in tables.weights_lbs.windows(2) {
+                let take_off = cessna(tables, 0.0).unwrap().calc_take_off(weights_lbs[0] as i16 + 1);
+                assert_eq!(take_off.aircraft_weight_lbs, Some(weights_lbs[1] as i16), "{aircraft} reads the next heavier table");
+            }
+            assert!(cessna(tables, 0.0).unwrap().try_calc_take_off(max_weight_lbs + 1).is_err(), "{aircraft} has no table over its maximum weight");
+
+            let take_off = cessna(tables, tables.notes.headwind_kts_per_10_percent).unwrap().calc_take_off(max_weight_lbs);
+            assert!((take_off.corrections.wind_correction_percentage - 0.9).abs() < 1e-9, "{aircraft} decreases distances 10% per its headwind note");
+
+            let tailwind_kts = tables.notes.max_tailwind_kts + 0.5;
+            assert_eq!(cessna(tables, -tailwind_kts).err(), Some(PerformanceError::TailwindLimit { tailwind_kts, limit_kts: tables.notes.max_tailwind_kts }), "{aircraft} limits the tailwind");
+        }
+    }
+}
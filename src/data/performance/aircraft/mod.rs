@@ -1,2 +1,23 @@
 pub mod cessna150j;
-pub mod cessna172m;
\ No newline at end of file
+pub mod cessna172m;
+pub mod cessna172s;
+pub mod cessna182p;
+pub mod cessna182t;
+pub mod cessna_tables;
+pub mod pa28161;
+pub mod profile;
+#[cfg(test)]
+mod reference_tests;
+
+#[cfg(test)]
+use super::distance::Distance;
+
+/// How closely the model tests expect a charted distance, well under the whole feet the charts are read to.
+#[cfg(test)]
+const TOLERANCE_FT: f64 = 0.01;
+
+#[cfg(test)]
+fn assert_distance(actual: Distance, expected: Distance) {
+    assert!((actual.ground_run_ft() - expected.ground_run_ft()).abs() < TOLERANCE_FT, "ground run {actual} != {expected}");
+    assert!((actual.clear_50_ft_obstacle_ft() - expected.clear_50_ft_obstacle_ft()).abs() < TOLERANCE_FT, "50' obstacle {actual} != {expected}");
+}
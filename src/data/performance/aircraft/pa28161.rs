@@ -0,0 +1,306 @@
+/*
+NOTES:
+The take off ground roll and 50 ft obstacle charts (flaps 25°, short field) and the landing charts (flaps 40°, power off) are graphs.
+They are entered with the pressure altitude and outside air temperature to find the density altitude, then across to the weight and
+then to the headwind or tailwind component. The graphs have been read at 0, 2000, 4000, 6000 and 8000 ft density altitude for the
+1800, 2100 and 2325 lbs weight lines and at 5 knots tailwind, calm and 5, 10 and 15 knots headwind. Between the lines is interpolated.
+
+The graphs have no lines for a tailwind over 5 knots or a headwind over 15 knots.
+The POH gives no correction for a grass runway, the rule of thumb of adding 20% of the ground roll is used instead.
+*/
+
+use lazy_static::lazy_static;
+
+use crate::{
+    data::performance::{climb::{self, Climb}, configuration::{Configuration, ConfigurationGroup}, distance::Distance, error::PerformanceError, grid::{Axis, Extrapolation, Grid, GridError, Lookup}, limits::ChartLimits, performance_row::PerformanceRow, speeds::{SpeedKind, SpeedTable}},
+    math::{Length, Pressure, PressureAltitudeMethod, Temperature, Velocity}
+};
+
+const WEIGHTS_LBS: [f64; 3] = [1800.0, 2100.0, 2325.0];
+const DENSITY_ALTITUDES_FT: [f64; 5] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0];
+const HEADWINDS_KTS: [f64; 5] = [-5.0, 0.0, 5.0, 10.0, 15.0];
+const MAX_TAILWIND_KTS: f64 = 5.0;
+const GRASS_GROUND_ROLL_PERCENTAGE: f64 = 0.2;
+
+/// The graphs are entered by density altitude, which is checked by the lookup once worked out. The landing graphs are read at the
+/// maximum weight when no weight is given.
+pub const LIMITS: ChartLimits = ChartLimits {
+    max_weight_lbs: WEIGHTS_LBS[2] as i16,
+    take_off_by_weight: true,
+    by_pressure_altitude: true,
+    min_altitude_ft: None,
+    max_altitude_ft: None,
+    max_temperature_c: None,
+    min_headwind_kts: -MAX_TAILWIND_KTS,
+    max_take_off_headwind_kts: None
+};
+
+const TAKE_OFF_AT_1800_LBS: [[Distance; 5]; 5] = [
+    [Distance(730.0, 1085.0),  Distance(615.0, 940.0),   Distance(510.0, 805.0),   Distance(410.0, 675.0),   Distance(325.0, 560.0)],
+    [Distance(885.0, 1290.0),  Distance(745.0, 1115.0),  Distance(615.0, 955.0),   Distance(500.0, 805.0),   Distance(395.0, 665.0)],
+    [Distance(1070.0, 1530.0), Distance(900.0, 1325.0),  Distance(745.0, 1135.0),  Distance(605.0, 955.0),   Distance(475.0, 790.0)],
+    [Distance(1295.0, 1820.0), Distance(1090.0, 1575.0), Distance(900.0, 1350.0),  Distance(730.0, 1135.0),  Distance(575.0, 940.0)],
+    [Distance(1570.0, 2165.0), Distance(1320.0, 1875.0), Distance(1090.0, 1600.0), Distance(885.0, 1350.0),  Distance(695.0, 1115.0)]
+];
+
+const TAKE_OFF_AT_2100_LBS: [[Distance; 5]; 5] = [
+    [Distance(965.0, 1435.0),  Distance(810.0, 1240.0),  Distance(670.0, 1060.0),  Distance(545.0, 895.0),   Distance(430.0, 740.0)],
+    [Distance(1170.0, 1700.0), Distance(980.0, 1475.0),  Distance(810.0, 1260.0),  Distance(660.0, 1060.0),  Distance(520.0, 880.0)],
+    [Distance(1415.0, 2025.0), Distance(1190.0, 1750.0), Distance(980.0, 1495.0),  Distance(795.0, 1260.0),  Distance(630.0, 1045.0)],
+    [Distance(1710.0, 2405.0), Distance(1440.0, 2080.0), Distance(1190.0, 1780.0), Distance(965.0, 1500.0),  Distance(760.0, 1240.0)],
+    [Distance(2070.0, 2855.0), Distance(1740.0, 2470.0), Distance(1440.0, 2115.0), Distance(1165.0, 1780.0), Distance(920.0, 1475.0)]
+];
+
+const TAKE_OFF_AT_2325_LBS: [[Distance; 5]; 5] = [
+    [Distance(1160.0, 1720.0), Distance(975.0, 1490.0),  Distance(805.0, 1275.0),  Distance(655.0, 1075.0),  Distance(515.0, 890.0)],
+    [Distance(1405.0, 2045.0), Distance(1180.0, 1770.0), Distance(975.0, 1515.0),  Distance(790.0, 1275.0),  Distance(625.0, 1055.0)],
+    [Distance(1700.0, 2430.0), Distance(1425.0, 2105.0), Distance(1180.0, 1800.0), Distance(955.0, 1515.0),  Distance(755.0, 1255.0)],
+    [Distance(2055.0, 2885.0), Distance(1725.0, 2500.0), Distance(1425.0, 2135.0), Distance(1155.0, 1800.0), Distance(915.0, 1490.0)],
+    [Distance(2485.0, 3430.0), Distance(2090.0, 2970.0), Distance(1725.0, 2540.0), Distance(1400.0, 2140.0), Distance(1105.0, 1770.0)]
+];
+
+const LANDING_AT_1800_LBS: [[Distance; 5]; 5] = [
+    [Distance(575.0, 1025.0),  Distance(485.0, 910.0),   Distance(400.0, 795.0),   Distance(325.0, 690.0),   Distance(255.0, 590.0)],
+    [Distance(620.0, 1100.0),  Distance(520.0, 975.0),   Distance(430.0, 855.0),   Distance(350.0, 740.0),   Distance(275.0, 630.0)],
+    [Distance(660.0, 1180.0),  Distance(555.0, 1045.0),  Distance(460.0, 915.0),   Distance(370.0, 790.0),   Distance(295.0, 675.0)],
+    [Distance(710.0, 1260.0),  Distance(595.0, 1115.0),  Distance(495.0, 980.0),   Distance(400.0, 845.0),   Distance(315.0, 725.0)],
+    [Distance(760.0, 1350.0),  Distance(640.0, 1195.0),  Distance(530.0, 1050.0),  Distance(425.0, 905.0),   Distance(340.0, 775.0)]
+];
+
+const LANDING_AT_2100_LBS: [[Distance; 5]; 5] = [
+    [Distance(655.0, 1160.0),  Distance(550.0, 1030.0),  Distance(455.0, 900.0),   Distance(365.0, 780.0),   Distance(290.0, 665.0)],
+    [Distance(700.0, 1245.0),  Distance(590.0, 1100.0),  Distance(485.0, 965.0),   Distance(395.0, 835.0),   Distance(310.0, 715.0)],
+    [Distance(750.0, 1335.0),  Distance(630.0, 1180.0),  Distance(520.0, 1035.0),  Distance(420.0, 895.0),   Distance(335.0, 765.0)],
+    [Distance(800.0, 1430.0),  Distance(675.0, 1265.0),  Distance(555.0, 1105.0),  Distance(450.0, 960.0),   Distance(355.0, 820.0)],
+    [Distance(860.0, 1530.0),  Distance(720.0, 1355.0),  Distance(595.0, 1185.0),  Distance(485.0, 1025.0),  Distance(380.0, 875.0)]
+];
+
+const LANDING_AT_2325_LBS: [[Distance; 5]; 5] = [
+    [Distance(710.0, 1260.0),  Distance(595.0, 1115.0),  Distance(490.0, 975.0),   Distance(400.0, 845.0),   Distance(315.0, 720.0)],
+    [Distance(760.0, 1350.0),  Distance(635.0, 1195.0),  Distance(525.0, 1045.0),  Distance(425.0, 905.0),   Distance(335.0, 775.0)],
+    [Distance(815.0, 1445.0),  Distance(685.0, 1280.0),  Distance(565.0, 1120.0),  Distance(455.0, 970.0),   Distance(360.0, 830.0)],
+    [Distance(870.0, 1550.0),  Distance(730.0, 1370.0),  Distance(605.0, 1200.0),  Distance(490.0, 1040.0),  Distance(385.0, 885.0)],
+    [Distance(930.0, 1660.0),  Distance(785.0, 1470.0),  Distance(650.0, 1285.0),  Distance(525.0, 1115.0),  Distance(415.0, 950.0)]
+];
+
+/// The take off graphs are for the short field technique with flaps 25° and the landing graphs for flaps 40° with power off.
+pub const CONFIGURATIONS: [Configuration; 7] = [
+    Configuration { id: "short_field", name: "Short field, flaps 25° (book)", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "normal", name: "Normal, flaps 0°", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.15, air_distance_factor: 1.15 },
+    Configuration { id: "full", name: "Flaps 40° (book)", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "flaps_10", name: "Flaps 10°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.25, air_distance_factor: 1.3 },
+    Configuration { id: "flaps_0", name: "Flaps 0°", group: ConfigurationGroup::LandingFlaps, ground_run_factor: 1.4, air_distance_factor: 1.45 },
+    Configuration { id: "power_off", name: "Power off (book)", group: ConfigurationGroup::Approach, ground_run_factor: 1.0, air_distance_factor: 1.0 },
+    Configuration { id: "power_on", name: "Power on", group: ConfigurationGroup::Approach, ground_run_factor: 1.05, air_distance_factor: 1.2 }
+];
+
+/// The POH speeds at 2325 lbs.
+pub const SPEEDS: SpeedTable = SpeedTable {
+    weights_lbs: &WEIGHTS_LBS,
+    speeds_kias: &[
+        (SpeedKind::Vr, &[44.0, 48.0, 50.0]),
+        (SpeedKind::Vx, &[55.0, 60.0, 63.0]),
+        (SpeedKind::Vy, &[70.0, 75.0, 79.0]),
+        (SpeedKind::Vref, &[58.0, 63.0, 66.0]),
+        (SpeedKind::Vs0, &[39.0, 42.0, 44.0]),
+        (SpeedKind::Vs1, &[44.0, 48.0, 50.0]),
+        (SpeedKind::Vg, &[64.0, 69.0, 73.0])
+    ]
+};
+
+/// The climb graph at 2325 lbs by density altitude, flown at Vy.
+const CLIMB_DENSITY_ALTITUDES_FT: [f64; 7] = [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 12000.0];
+const CLIMB_RATES_FPM: [f64; 7] = [710.0, 620.0, 530.0, 440.0, 350.0, 260.0, 170.0];
+const CLIMB_SPEED_KIAS: f64 = 79.0;
+
+fn density_altitude_axis() -> Axis {
+    Axis::new("density altitude", "'", &DENSITY_ALTITUDES_FT).extrapolate(Extrapolation::Clamp, Extrapolation::Error)
+}
+
+fn grid(tables: [[[Distance; 5]; 5]; 3]) -> Grid<Distance> {
+    Grid::new(
+        vec![
+            Axis::new("weight", " lbs", &WEIGHTS_LBS).extrapolate(Extrapolation::Clamp, Extrapolation::Error),
+            density_altitude_axis(),
+            Axis::new("headwind", " kts", &HEADWINDS_KTS).extrapolate(Extrapolation::Error, Extrapolation::Clamp)
+        ],
+        tables.iter().flatten().flatten().map(|distance| Some(*distance)).collect()
+    )
+}
+
+lazy_static! {
+    static ref TAKE_OFF_GRID: Grid<Distance> = grid([TAKE_OFF_AT_1800_LBS, TAKE_OFF_AT_2100_LBS, TAKE_OFF_AT_2325_LBS]);
+
+    static ref LANDING_GRID: Grid<Distance> = grid([LANDING_AT_1800_LBS, LANDING_AT_2100_LBS, LANDING_AT_2325_LBS]);
+
+    static ref CLIMB_RATE_GRID: Grid<f64> = Grid::new(
+        vec![Axis::new("density altitude", "'", &CLIMB_DENSITY_ALTITUDES_FT).extrapolate(Extrapolation::Clamp, Extrapolation::Error)],
+        CLIMB_RATES_FPM.iter().map(|rate| Some(*rate)).collect()
+    );
+}
+
+pub struct Corrections {
+    pub grass_ground_roll_percentage: f64,
+    pub grass_offset: f64,
+    pub distance_corrected_for_grass: Distance
+}
+
+pub struct Performance {
+    pub aircraft_weight_lbs: f64,
+    pub density_altitude_tween_percentage: f64,
+    pub headwind_tween_percentage: f64,
+    pub lower_headwind_kts: f64,
+    pub upper_headwind_kts: f64,
+    pub distance_rows: [PerformanceRow; 3],
+    pub lookup: Lookup<Distance>,
+    pub corrections: Corrections
+}
+
+impl Performance {
+    pub fn final_distance(&self, is_grass: bool) -> Distance {
+        if is_grass {
+            self.corrections.distance_corrected_for_grass
+        } else {
+            self.lookup.value
+        }
+    }
+}
+
+pub struct Pa28161 {
+    pub headwind: Velocity,
+    pub pressure_in_hg: f64,
+    pub elevation_ft: f64,
+    pub pressure_altitude_ft: f64,
+    pub pressure_altitude_method: PressureAltitudeMethod,
+    pub temperature_c: f64,
+    /// The standard temperature at the pressure altitude.
+    pub standard_temperature_c: f64,
+    pub density_altitude_ft: f64
+}
+
+impl Pa28161 {
+    pub fn new(headwind: Velocity, elevation: Length, pressure: Pressure, pressure_altitude_method: PressureAltitudeMethod, temperature: Temperature) -> Self {
+        let pressure_altitude_ft = pressure.altitude(elevation, pressure_altitude_method).feet();
+        let temperature_c = temperature.celsius();
+
+        Pa28161 {
+            headwind,
+            pressure_in_hg: pressure.in_hg(),
+            elevation_ft: elevation.feet(),
+            pressure_altitude_ft,
+            pressure_altitude_method,
+            temperature_c,
+            standard_temperature_c: Temperature::standard_temperature(Length::Feet(pressure_altitude_ft)).celsius(),
+            density_altitude_ft: climb::density_altitude_ft(pressure_altitude_ft, temperature_c)
+        }
+    }
+
+    pub fn try_new(headwind: Velocity, elevation: Length, pressure: Pressure, pressure_altitude_method: PressureAltitudeMethod, temperature: Temperature) -> Result<Self, PerformanceError> {
+        let tailwind_kts = -headwind.knots();
+        if tailwind_kts > MAX_TAILWIND_KTS {
+            return Err(PerformanceError::TailwindLimit { tailwind_kts, limit_kts: MAX_TAILWIND_KTS });
+        }
+
+        Ok(Self::new(headwind, elevation, pressure, pressure_altitude_method, temperature))
+    }
+
+    pub fn pressure_hpa(&self) -> f64 {
+        Pressure::InchesOfMercury(self.pressure_in_hg).hpa()
+    }
+
+    /// Interpolates the headwind then density altitude then weight, the rows are shown at the weight of the point.
+    fn calc_performance(&self, grid: &Grid<Distance>, aircraft_weight_lbs: f64) -> Result<Performance, GridError> {
+        let point = [aircraft_weight_lbs, self.density_altitude_ft, self.headwind.knots()];
+
+        let lookup = grid.lookup(&point)?;
+        let density_altitude = lookup.brackets[1];
+        let headwind = lookup.brackets[2];
+        let [lower_row, middle_row, upper_row] = grid.cross_section(&point, 1, 2)?;
+
+        let distance_rows = [
+            PerformanceRow::new_labeled(density_altitude.lower, lower_row[0], lower_row[1], lower_row[2]),
+            PerformanceRow::new_labeled(self.density_altitude_ft, middle_row[0], middle_row[1], middle_row[2]),
+            PerformanceRow::new_labeled(density_altitude.upper, upper_row[0], upper_row[1], upper_row[2])
+        ];
+
+        let grass_offset = lookup.value.ground_run_ft() * GRASS_GROUND_ROLL_PERCENTAGE;
+
+        Ok(Performance {
+            aircraft_weight_lbs,
+            density_altitude_tween_percentage: density_altitude.fraction,
+            headwind_tween_percentage: headwind.fraction,
+            lower_headwind_kts: headwind.lower,
+            upper_headwind_kts: headwind.upper,
+            distance_rows,
+            corrections: Corrections {
+                grass_ground_roll_percentage: GRASS_GROUND_ROLL_PERCENTAGE,
+                grass_offset,
+                distance_corrected_for_grass: lookup.value.offset(grass_offset)
+            },
+            lookup
+        })
+    }
+
+    pub fn try_calc_take_off(&self, aircraft_weight_lbs: i16) -> Result<Performance, PerformanceError> {
+        Ok(self.calc_performance(&TAKE_OFF_GRID, aircraft_weight_lbs as f64)?)
+    }
+
+    pub fn calc_take_off(&self, aircraft_weight_lbs: i16) -> Performance {
+        self.try_calc_take_off(aircraft_weight_lbs).expect("To get the take off performance")
+    }
+
+    pub fn try_calc_landing(&self, aircraft_weight_lbs: Option<i16>) -> Result<Performance, PerformanceError> {
+        Ok(self.calc_performance(&LANDING_GRID, aircraft_weight_lbs.map(f64::from).unwrap_or(WEIGHTS_LBS[2]))?)
+    }
+
+    pub fn calc_landing(&self, aircraft_weight_lbs: Option<i16>) -> Performance {
+        self.try_calc_landing(aircraft_weight_lbs).expect("To get the landing performance")
+    }
+
+    /// The graph is for the maximum weight, which is conservative for a lighter aircraft.
+    pub fn calc_climb(&self) -> Result<Climb, PerformanceError> {
+        let rate_fpm = CLIMB_RATE_GRID.lookup(&[self.density_altitude_ft])?.value;
+        Ok(Climb { rate_fpm, speed_kias: CLIMB_SPEED_KIAS })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::performance::aircraft::{assert_distance, TOLERANCE_FT};
+
+    use super::*;
+
+    /// At 29.92" the rule of thumb gives the elevation as the pressure altitude, and at the standard temperature the density altitude.
+    fn piper(headwind_kts: f64, elevation_ft: f64, temperature_c: f64) -> Pa28161 {
+        Pa28161::new(Velocity::Knots(headwind_kts), Length::Feet(elevation_ft), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(temperature_c))
+    }
+
+    #[test]
+    fn density_altitude_adds_120_ft_per_degree_above_standard() {
+        assert!((piper(0.0, 2000.0, 11.0).density_altitude_ft - 2000.0).abs() < TOLERANCE_FT);
+        assert!((piper(0.0, 2000.0, 21.0).density_altitude_ft - 3200.0).abs() < TOLERANCE_FT);
+    }
+
+    #[test]
+    fn graphs_match_at_the_read_points() {
+        assert_distance(piper(0.0, 0.0, 15.0).calc_take_off(2325).final_distance(false), Distance(975.0, 1490.0));
+        assert_distance(piper(10.0, 4000.0, 7.0).calc_take_off(2100).final_distance(false), Distance(795.0, 1260.0));
+        assert_distance(piper(-5.0, 8000.0, -1.0).calc_landing(Some(1800)).final_distance(false), Distance(760.0, 1350.0));
+        assert_distance(piper(0.0, 0.0, 15.0).calc_landing(None).final_distance(false), Distance(595.0, 1115.0));
+    }
+
+    #[test]
+    fn weights_between_the_lines_are_interpolated() {
+        let take_off = piper(0.0, 0.0, 15.0).calc_take_off(1950);
+        assert_distance(take_off.final_distance(false), Distance(712.5, 1090.0));
+        assert_distance(take_off.final_distance(true), Distance(855.0, 1232.5));
+    }
+
+    #[test]
+    fn winds_off_the_graphs() {
+        assert_distance(piper(20.0, 0.0, 15.0).calc_take_off(2325).final_distance(false), Distance(515.0, 890.0));
+        assert_eq!(Pa28161::try_new(Velocity::Knots(-6.0), Length::Feet(0.0), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(15.0)).err(),
+            Some(PerformanceError::TailwindLimit { tailwind_kts: 6.0, limit_kts: 5.0 }));
+        assert!(piper(0.0, 7000.0, 30.0).try_calc_take_off(2325).is_err());
+    }
+}
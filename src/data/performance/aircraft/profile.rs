@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::performance::distance::Distance;
+
+/// Tail-number specific adjustments layered on top of the book numbers of the aircraft model, saved as part of its fleet record.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct AircraftProfile {
+    pub tail_number: String,
+    /// Added to the book distances, never below 0 so a profile can not make the aircraft better than the book.
+    #[serde(default)]
+    pub take_off_factor_percentage: f64,
+    #[serde(default)]
+    pub landing_factor_percentage: f64,
+    #[serde(default)]
+    pub modifications: String,
+    #[serde(default)]
+    pub stc_notes: String,
+    /// Floats or an amphibian, so water lanes can be used.
+    #[serde(default)]
+    pub is_seaplane: bool
+}
+
+pub fn is_valid_factor_percentage(factor_percentage: f64) -> bool {
+    factor_percentage.is_finite() && factor_percentage >= 0.0
+}
+
+/// A factor saved before they were validated is ignored rather than shortening the book distances.
+fn apply_factor(distance: Distance, factor_percentage: f64) -> Distance {
+    if !is_valid_factor_percentage(factor_percentage) {
+        return distance;
+    }
+
+    let factor = 1.0 + factor_percentage / 100.0;
+    distance.scale(factor)
+}
+
+impl AircraftProfile {
+    pub fn factor_percentage(&self, is_take_off: bool) -> f64 {
+        if is_take_off { self.take_off_factor_percentage } else { self.landing_factor_percentage }
+    }
+
+    pub fn apply(&self, is_take_off: bool, distance: Distance) -> Distance {
+        apply_factor(distance, self.factor_percentage(is_take_off))
+    }
+}
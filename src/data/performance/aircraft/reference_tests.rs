@@ -0,0 +1,295 @@
+//! Reference cases from the POH charts and notes, worked by hand, plus checks that the distances move the right way
+//! as the conditions change anywhere between the charted points.
+
+use serde::Deserialize;
+
+use crate::{
+    data::performance::distance::Distance,
+    math::{Length, Pressure, PressureAltitudeMethod, Temperature, Velocity}
+};
+
+use super::{cessna150j::Cessna150J, cessna172m, cessna172s, cessna182p, cessna182t, cessna_tables::{Cessna, Tables}, pa28161::Pa28161};
+
+const REFERENCE_CASES: &str = include_str!("reference_cases.csv");
+/// Allows for rounding between neighbouring sweep points, far below a foot.
+const MONOTONIC_TOLERANCE_FT: f64 = 1e-9;
+
+#[derive(Deserialize)]
+struct ReferenceCase {
+    aircraft: String,
+    mode: String,
+    elevation_ft: f64,
+    pressure_in_hg: Option<f64>,
+    temperature_f: Option<f64>,
+    temperature_c: Option<f64>,
+    headwind_kts: f64,
+    weight_lbs: Option<i16>,
+    is_grass: bool,
+    ground_run_ft: f64,
+    clear_50_ft_obstacle_ft: f64,
+    tolerance_ft: f64,
+    source: String
+}
+
+impl ReferenceCase {
+    fn temperature(&self) -> Temperature {
+        self.temperature_c.map(Temperature::Celsius)
+            .or(self.temperature_f.map(Temperature::Fahrenheit))
+            .expect("A reference case to have a temperature")
+    }
+
+    fn calc(&self) -> Distance {
+        let headwind = Velocity::Knots(self.headwind_kts);
+        let elevation = Length::Feet(self.elevation_ft);
+
+        match self.aircraft.as_str() {
+            "cessna150j" => {
+                let cessna = Cessna150J::new(headwind, self.temperature(), elevation, Temperature::standard_temperature(elevation));
+                match self.mode.as_str() {
+                    "take_off" => cessna.calc_take_off().correction.final_distance(self.is_grass),
+                    _ => cessna.calc_landing().correction.final_distance(self.is_grass)
+                }
+            },
+            aircraft @ ("cessna172m" | "cessna172s" | "cessna182p" | "cessna182t") => {
+                // The tables are by pressure altitude, at 29.92" the rule of thumb gives the elevation exactly.
+                let pressure = Pressure::InchesOfMercury(self.pressure_in_hg.expect("A Cessna table reference case to have a pressure"));
+                let cessna = Cessna::new(cessna_tables(aircraft), headwind, elevation, pressure, PressureAltitudeMethod::RuleOfThumb, self.temperature());
+                match self.mode.as_str() {
+                    "take_off" => cessna.calc_take_off(self.weight_lbs.expect("A Cessna table take off reference case to have a weight")),
+                    _ => cessna.calc_landing()
+                }.final_distance(self.is_grass)
+            },
+            "pa28161" => {
+                // The graphs are by density altitude, at 29.92" and the standard temperature it is the elevation.
+                let pressure = Pressure::InchesOfMercury(self.pressure_in_hg.expect("A PA-28-161 reference case to have a pressure"));
+                let piper = Pa28161::new(headwind, elevation, pressure, PressureAltitudeMethod::RuleOfThumb, self.temperature());
+                match self.mode.as_str() {
+                    "take_off" => piper.calc_take_off(self.weight_lbs.expect("A PA-28-161 take off reference case to have a weight")),
+                    _ => piper.calc_landing(self.weight_lbs)
+                }.final_distance(self.is_grass)
+            },
+            aircraft => panic!("Unknown aircraft {aircraft} in the reference cases")
+        }
+    }
+}
+
+fn reference_cases() -> Vec<ReferenceCase> {
+    csv::Reader::from_reader(REFERENCE_CASES.as_bytes())
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .expect("To read the reference cases")
+}
+
+#[test]
+fn reference_cases_match() {
+    let cases = reference_cases();
+    assert!(!cases.is_empty());
+
+    let failures: Vec<String> = cases.iter()
+        .filter_map(|case| {
+            let actual = case.calc();
+            let is_match = (actual.ground_run_ft() - case.ground_run_ft).abs() <= case.tolerance_ft
+                && (actual.clear_50_ft_obstacle_ft() - case.clear_50_ft_obstacle_ft).abs() <= case.tolerance_ft;
+            (!is_match).then(|| format!("{} {} ({}): expected {}' / {}', got {:.2}' / {:.2}'",
+                case.aircraft, case.mode, case.source, case.ground_run_ft, case.clear_50_ft_obstacle_ft, actual.ground_run_ft(), actual.clear_50_ft_obstacle_ft()))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_aircraft_and_mode_has_reference_cases() {
+    let cases = reference_cases();
+    for (aircraft, mode) in [("cessna150j", "take_off"), ("cessna150j", "landing"), ("cessna172m", "take_off"), ("cessna172m", "landing"), ("cessna172s", "take_off"), ("cessna172s", "landing"), ("cessna182p", "take_off"), ("cessna182p", "landing"), ("cessna182t", "take_off"), ("cessna182t", "landing"), ("pa28161", "take_off"), ("pa28161", "landing")] {
+        assert!(cases.iter().any(|case| case.aircraft == aircraft && case.mode == mode), "No reference cases for {aircraft} {mode}");
+    }
+}
+
+fn sweep(from: f64, to: f64, step: f64) -> Vec<f64> {
+    (0..).map(|i| from + step * i as f64).take_while(|value| *value <= to).collect()
+}
+
+/// Each distance along the sweep is at least the one before, or at most when `is_decreasing`.
+fn assert_monotonic(label: &str, values: &[f64], distances: &[Option<Distance>], is_decreasing: bool) {
+    for i in 1..distances.len() {
+        let (Some(before), Some(after)) = (distances[i - 1], distances[i]) else {
+            continue;
+        };
+        for (name, before, after) in [("ground run", before.ground_run_ft(), after.ground_run_ft()), ("50' obstacle", before.clear_50_ft_obstacle_ft(), after.clear_50_ft_obstacle_ft())] {
+            let is_ordered = if is_decreasing {
+                after <= before + MONOTONIC_TOLERANCE_FT
+            } else {
+                after + MONOTONIC_TOLERANCE_FT >= before
+            };
+            assert!(is_ordered, "{label}: {name} went from {before:.2}' at {} to {after:.2}' at {}", values[i - 1], values[i]);
+        }
+    }
+}
+
+fn cessna150j(elevation_ft: f64, temperature_f: f64, headwind_kts: f64) -> Cessna150J {
+    let elevation = Length::Feet(elevation_ft);
+    Cessna150J::new(Velocity::Knots(headwind_kts), Temperature::Fahrenheit(temperature_f), elevation, Temperature::standard_temperature(elevation))
+}
+
+const CESSNA_TABLES: [&str; 4] = ["cessna172m", "cessna172s", "cessna182p", "cessna182t"];
+
+fn cessna_tables(aircraft: &str) -> &'static Tables {
+    match aircraft {
+        "cessna172m" => &cessna172m::TABLES,
+        "cessna172s" => &cessna172s::TABLES,
+        "cessna182p" => &cessna182p::TABLES,
+        "cessna182t" => &cessna182t::TABLES,
+        aircraft => panic!("{aircraft} has no Cessna tables")
+    }
+}
+
+fn cessna(tables: &'static Tables, pressure_altitude_ft: f64, temperature_c: f64, headwind_kts: f64) -> Cessna {
+    Cessna::new(tables, Velocity::Knots(headwind_kts), Length::Feet(pressure_altitude_ft), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(temperature_c))
+}
+
+fn pa28161(pressure_altitude_ft: f64, temperature_c: f64, headwind_kts: f64) -> Pa28161 {
+    Pa28161::new(Velocity::Knots(headwind_kts), Length::Feet(pressure_altitude_ft), Pressure::InchesOfMercury(29.92), PressureAltitudeMethod::RuleOfThumb, Temperature::Celsius(temperature_c))
+}
+
+fn cessna150j_distances(is_take_off: bool, is_grass: bool, cessnas: impl Iterator<Item = Cessna150J>) -> Vec<Option<Distance>> {
+    cessnas.map(|cessna| Some(if is_take_off {
+        cessna.calc_take_off().correction.final_distance(is_grass)
+    } else {
+        cessna.calc_landing().correction.final_distance(is_grass)
+    })).collect()
+}
+
+/// Deleted chart cells are skipped, they have no distance to compare.
+fn cessna_distances(weight_lbs: Option<i16>, is_grass: bool, cessnas: impl Iterator<Item = Cessna>) -> Vec<Option<Distance>> {
+    cessnas.map(|cessna| match weight_lbs {
+        Some(weight_lbs) => cessna.try_calc_take_off(weight_lbs),
+        None => cessna.try_calc_landing()
+    }.ok().map(|performance| performance.final_distance(is_grass))).collect()
+}
+
+#[test]
+fn cessna150j_distances_increase_with_elevation() {
+    let elevations = sweep(0.0, 7500.0, 125.0);
+    for (is_take_off, is_grass, headwind_kts, temperature_f) in [(true, false, 0.0, 59.0), (true, true, 7.5, 80.0), (false, false, 0.0, 59.0), (false, true, 12.0, 95.0)] {
+        let distances = cessna150j_distances(is_take_off, is_grass, elevations.iter().map(|elevation_ft| cessna150j(*elevation_ft, temperature_f, headwind_kts)));
+        assert_monotonic("150J by elevation", &elevations, &distances, false);
+    }
+}
+
+#[test]
+fn cessna150j_distances_increase_with_temperature() {
+    let temperatures = sweep(-20.0, 110.0, 2.5);
+    for (is_take_off, elevation_ft, headwind_kts) in [(true, 0.0, 0.0), (true, 3750.0, 15.0), (false, 0.0, 0.0), (false, 6000.0, 8.0)] {
+        let distances = cessna150j_distances(is_take_off, false, temperatures.iter().map(|temperature_f| cessna150j(elevation_ft, *temperature_f, headwind_kts)));
+        assert_monotonic("150J by temperature", &temperatures, &distances, false);
+    }
+}
+
+#[test]
+fn cessna150j_distances_decrease_with_headwind() {
+    let headwinds = sweep(0.0, 20.0, 0.5);
+    for (is_take_off, elevation_ft, temperature_f) in [(true, 0.0, 59.0), (true, 4000.0, 90.0), (false, 0.0, 59.0), (false, 6500.0, 70.0)] {
+        let distances = cessna150j_distances(is_take_off, false, headwinds.iter().map(|headwind_kts| cessna150j(elevation_ft, temperature_f, *headwind_kts)));
+        assert_monotonic("150J by headwind", &headwinds, &distances, true);
+    }
+}
+
+/// The heaviest table, the sweeps take their weights below it so the same cases cover every aircraft.
+fn max_weight_lbs(tables: &Tables) -> i16 {
+    tables.weights_lbs[tables.weights_lbs.len() - 1] as i16
+}
+
+#[test]
+fn cessna_table_distances_increase_with_pressure_altitude() {
+    let altitudes = sweep(0.0, 8000.0, 125.0);
+    for aircraft in CESSNA_TABLES {
+        let tables = cessna_tables(aircraft);
+        let max_weight_lbs = max_weight_lbs(tables);
+        for (weight_lbs, is_grass, temperature_c, headwind_kts) in [(Some(max_weight_lbs), false, 15.0, 0.0), (Some(max_weight_lbs - 300), true, 35.0, -5.0), (None, false, 15.0, 0.0), (None, true, 40.0, 9.0)] {
+            let distances = cessna_distances(weight_lbs, is_grass, altitudes.iter().map(|altitude_ft| cessna(tables, *altitude_ft, temperature_c, headwind_kts)));
+            assert_monotonic(&format!("{aircraft} by pressure altitude"), &altitudes, &distances, false);
+        }
+    }
+}
+
+#[test]
+fn cessna_table_distances_increase_with_temperature() {
+    let temperatures = sweep(-10.0, 40.0, 1.0);
+    for aircraft in CESSNA_TABLES {
+        let tables = cessna_tables(aircraft);
+        let max_weight_lbs = max_weight_lbs(tables);
+        for (weight_lbs, altitude_ft, headwind_kts) in [(Some(max_weight_lbs), 0.0, 0.0), (Some(max_weight_lbs - 350), 5500.0, 12.0), (None, 0.0, 0.0), (None, 7250.0, -10.0)] {
+            let distances = cessna_distances(weight_lbs, false, temperatures.iter().map(|temperature_c| cessna(tables, altitude_ft, *temperature_c, headwind_kts)));
+            assert_monotonic(&format!("{aircraft} by temperature"), &temperatures, &distances, false);
+        }
+    }
+}
+
+#[test]
+fn cessna_table_take_off_distances_increase_with_weight() {
+    for aircraft in CESSNA_TABLES {
+        let tables = cessna_tables(aircraft);
+        let max_weight_lbs = max_weight_lbs(tables);
+        let weights = sweep(f64::from(max_weight_lbs - 700), f64::from(max_weight_lbs), 10.0);
+        for (altitude_ft, temperature_c) in [(0.0, 0.0), (3500.0, 25.0), (8000.0, 10.0)] {
+            let cessna = cessna(tables, altitude_ft, temperature_c, 0.0);
+            let distances: Vec<Option<Distance>> = weights.iter()
+                .map(|weight_lbs| cessna.try_calc_take_off(*weight_lbs as i16).ok().map(|performance| performance.final_distance(false)))
+                .collect();
+            assert_monotonic(&format!("{aircraft} by weight"), &weights, &distances, false);
+        }
+    }
+}
+
+#[test]
+fn cessna_table_distances_decrease_with_headwind() {
+    for aircraft in CESSNA_TABLES {
+        let tables = cessna_tables(aircraft);
+        let max_weight_lbs = max_weight_lbs(tables);
+        let headwinds = sweep(-tables.notes.max_tailwind_kts, 30.0, 0.5);
+        for (weight_lbs, altitude_ft, temperature_c) in [(Some(max_weight_lbs), 0.0, 15.0), (Some(max_weight_lbs - 200), 6000.0, 20.0), (None, 0.0, 15.0), (None, 4500.0, 35.0)] {
+            let distances = cessna_distances(weight_lbs, false, headwinds.iter().map(|headwind_kts| cessna(tables, altitude_ft, temperature_c, *headwind_kts)));
+            assert_monotonic(&format!("{aircraft} by headwind"), &headwinds, &distances, true);
+        }
+    }
+}
+
+/// Density altitudes above the graphs are skipped, they have no distance to compare.
+fn pa28161_distances(is_take_off: bool, weight_lbs: i16, is_grass: bool, pipers: impl Iterator<Item = Pa28161>) -> Vec<Option<Distance>> {
+    pipers.map(|piper| if is_take_off {
+        piper.try_calc_take_off(weight_lbs)
+    } else {
+        piper.try_calc_landing(Some(weight_lbs))
+    }.ok().map(|performance| performance.final_distance(is_grass))).collect()
+}
+
+#[test]
+fn pa28161_distances_increase_with_density_altitude() {
+    let temperatures = sweep(-20.0, 45.0, 1.0);
+    for (is_take_off, weight_lbs, is_grass, altitude_ft, headwind_kts) in [(true, 2325, false, 0.0, 0.0), (true, 1950, true, 4500.0, 7.0), (false, 2325, false, 0.0, 0.0), (false, 2200, true, 3000.0, -5.0)] {
+        let distances = pa28161_distances(is_take_off, weight_lbs, is_grass, temperatures.iter().map(|temperature_c| pa28161(altitude_ft, *temperature_c, headwind_kts)));
+        assert_monotonic("PA-28-161 by temperature", &temperatures, &distances, false);
+    }
+}
+
+#[test]
+fn pa28161_distances_increase_with_weight() {
+    let weights = sweep(1600.0, 2325.0, 5.0);
+    for (is_take_off, altitude_ft, temperature_c) in [(true, 0.0, 15.0), (true, 5000.0, 25.0), (false, 0.0, 15.0), (false, 7000.0, 0.0)] {
+        let piper = pa28161(altitude_ft, temperature_c, 0.0);
+        let distances: Vec<Option<Distance>> = weights.iter()
+            .map(|weight_lbs| if is_take_off { piper.try_calc_take_off(*weight_lbs as i16) } else { piper.try_calc_landing(Some(*weight_lbs as i16)) }.ok().map(|performance| performance.final_distance(false)))
+            .collect();
+        assert_monotonic("PA-28-161 by weight", &weights, &distances, false);
+    }
+}
+
+#[test]
+fn pa28161_distances_decrease_with_headwind() {
+    let headwinds = sweep(-5.0, 25.0, 0.5);
+    for (is_take_off, weight_lbs, altitude_ft, temperature_c) in [(true, 2325, 0.0, 15.0), (true, 2000, 6000.0, 10.0), (false, 2325, 0.0, 15.0), (false, 1850, 4500.0, 30.0)] {
+        let distances = pa28161_distances(is_take_off, weight_lbs, false, headwinds.iter().map(|headwind_kts| pa28161(altitude_ft, temperature_c, *headwind_kts)));
+        assert_monotonic("PA-28-161 by headwind", &headwinds, &distances, true);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::data::obstacles::Obstacle;
+
+use super::distance::Distance;
+
+/// Feet per minute covered at one knot.
+const FEET_PER_MINUTE_PER_KNOT: f64 = 6076.12 / 60.0;
+/// The rule of thumb of 120' of density altitude for each degree Celsius above standard.
+const DENSITY_ALTITUDE_FT_PER_C: f64 = 120.0;
+/// The rule of thumb of true airspeed increasing 2% per 1000' of density altitude.
+const TRUE_AIRSPEED_INCREASE_PER_FT: f64 = 0.02 / 1000.0;
+
+/// The book rate of climb for the conditions and the indicated airspeed it is flown at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Climb {
+    pub rate_fpm: f64,
+    pub speed_kias: f64
+}
+
+pub fn density_altitude_ft(pressure_altitude_ft: f64, temperature_c: f64) -> f64 {
+    let standard_temperature_c = 15.0 - 2.0 * pressure_altitude_ft / 1000.0;
+    pressure_altitude_ft + DENSITY_ALTITUDE_FT_PER_C * (temperature_c - standard_temperature_c)
+}
+
+impl Climb {
+    /// Feet gained per foot travelled over the ground once the aircraft has cleared 50'.
+    pub fn gradient(&self, density_altitude_ft: f64, headwind_kts: f64) -> f64 {
+        let true_airspeed_kts = self.speed_kias * (1.0 + TRUE_AIRSPEED_INCREASE_PER_FT * density_altitude_ft.max(0.0));
+        let groundspeed_kts = (true_airspeed_kts - headwind_kts).max(1.0);
+        self.rate_fpm.max(0.0) / (groundspeed_kts * FEET_PER_MINUTE_PER_KNOT)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clearance {
+    pub obstacle: Obstacle,
+    /// The aircraft's height above the departure end of the runway as it passes the obstacle.
+    pub aircraft_height_ft: f64,
+    pub margin_ft: f64
+}
+
+impl Clearance {
+    pub fn is_cleared(&self) -> bool {
+        self.margin_ft > 0.0
+    }
+}
+
+/// The aircraft's height at a distance from brake release: on the ground for the ground run, climbing
+/// in a straight line to 50' at the 50' obstacle distance and then at the climb gradient.
+pub fn height_at_ft(distance: Distance, gradient: f64, distance_from_brake_release_ft: f64) -> f64 {
+    let ground_run_ft = distance.ground_run_ft();
+    let clear_50_ft_obstacle_ft = distance.clear_50_ft_obstacle_ft();
+
+    if distance_from_brake_release_ft <= ground_run_ft {
+        0.0
+    } else if distance_from_brake_release_ft <= clear_50_ft_obstacle_ft {
+        50.0 * (distance_from_brake_release_ft - ground_run_ft) / (clear_50_ft_obstacle_ft - ground_run_ft)
+    } else {
+        50.0 + gradient * (distance_from_brake_release_ft - clear_50_ft_obstacle_ft)
+    }
+}
+
+/// The margin over each obstacle for a take off from the start of a runway of the given length.
+pub fn clearances(distance: Distance, gradient: f64, runway_length_ft: f64, obstacles: &[Obstacle]) -> Vec<Clearance> {
+    obstacles.iter()
+        .map(|obstacle| {
+            let aircraft_height_ft = height_at_ft(distance, gradient, runway_length_ft + obstacle.distance_ft);
+            Clearance { obstacle: obstacle.clone(), aircraft_height_ft, margin_ft: aircraft_height_ft - obstacle.height_ft }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 0.01;
+
+    fn obstacle(height_ft: f64, distance_ft: f64) -> Obstacle {
+        Obstacle { name: String::new(), height_ft, distance_ft }
+    }
+
+    #[test]
+    fn gradient_uses_the_groundspeed() {
+        let climb = Climb { rate_fpm: 730.0, speed_kias: 80.0 };
+        assert!((climb.gradient(0.0, 0.0) - 0.0901).abs() < 0.0001);
+        assert!(climb.gradient(0.0, 20.0) > climb.gradient(0.0, 0.0));
+        assert!(climb.gradient(5000.0, 0.0) < climb.gradient(0.0, 0.0));
+        assert!((density_altitude_ft(5000.0, 25.0) - 7400.0).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn clearance_follows_the_take_off_path() {
+        let distance = Distance(1000.0, 2000.0);
+        let runway_length_ft = 1500.0;
+        let result = clearances(distance, 0.1, runway_length_ft, &[obstacle(10.0, 0.0), obstacle(40.0, 250.0), obstacle(200.0, 1500.0), obstacle(60.0, 100.0)]);
+
+        assert!((result[0].aircraft_height_ft - 25.0).abs() < TOLERANCE);
+        assert!((result[0].margin_ft - 15.0).abs() < TOLERANCE);
+        assert!((result[1].aircraft_height_ft - 37.5).abs() < TOLERANCE);
+        assert!(!result[1].is_cleared());
+        assert!((result[2].aircraft_height_ft - 150.0).abs() < TOLERANCE);
+        assert!((result[2].margin_ft + 50.0).abs() < TOLERANCE);
+        assert!(!result[3].is_cleared());
+    }
+}
@@ -0,0 +1,75 @@
+use super::distance::Distance;
+
+/// The choices that change the distances, one configuration is picked from each group that applies to the take off or landing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigurationGroup {
+    TakeOffTechnique,
+    LandingFlaps,
+    Approach
+}
+
+impl ConfigurationGroup {
+    pub const ALL: [ConfigurationGroup; 3] = [ConfigurationGroup::TakeOffTechnique, ConfigurationGroup::LandingFlaps, ConfigurationGroup::Approach];
+
+    pub fn field(self) -> &'static str {
+        match self {
+            ConfigurationGroup::TakeOffTechnique => "take_off_technique",
+            ConfigurationGroup::LandingFlaps => "landing_flaps",
+            ConfigurationGroup::Approach => "approach"
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigurationGroup::TakeOffTechnique => "Take Off Technique",
+            ConfigurationGroup::LandingFlaps => "Landing Flaps",
+            ConfigurationGroup::Approach => "Approach"
+        }
+    }
+
+    pub fn is_take_off(self) -> bool {
+        self == ConfigurationGroup::TakeOffTechnique
+    }
+}
+
+/// A flap setting or technique, as factors on the book distances. The book configuration has factors of 1 and is listed first in its group,
+/// the others are rules of thumb rather than POH figures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Configuration {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub group: ConfigurationGroup,
+    pub ground_run_factor: f64,
+    /// Scales the air distance between lift-off or touchdown and 50'.
+    pub air_distance_factor: f64
+}
+
+impl Configuration {
+    pub fn is_book(&self) -> bool {
+        self.ground_run_factor == 1.0 && self.air_distance_factor == 1.0
+    }
+
+    pub fn apply(&self, distance: Distance) -> Distance {
+        let ground_run_ft = distance.ground_run_ft() * self.ground_run_factor;
+        let air_distance_ft = (distance.clear_50_ft_obstacle_ft() - distance.ground_run_ft()) * self.air_distance_factor;
+        Distance(ground_run_ft, ground_run_ft + air_distance_ft)
+    }
+}
+
+pub fn apply_all(configurations: &[&Configuration], distance: Distance) -> Distance {
+    configurations.iter().fold(distance, |distance, configuration| configuration.apply(distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factors_scale_the_ground_run_and_air_distance() {
+        let normal = Configuration { id: "normal", name: "Normal", group: ConfigurationGroup::TakeOffTechnique, ground_run_factor: 1.1, air_distance_factor: 1.2 };
+        let distance = normal.apply(Distance(1000.0, 1500.0));
+        assert!((distance.ground_run_ft() - 1100.0).abs() < 0.01);
+        assert!((distance.clear_50_ft_obstacle_ft() - 1700.0).abs() < 0.01);
+        assert!(!normal.is_book());
+    }
+}
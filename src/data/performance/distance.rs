@@ -1,16 +1,37 @@
-#[derive(Clone, Copy)]
-pub struct Distance(pub i16, pub i16);
+use std::fmt;
+
+/// Distances are kept in feet at full precision, the accessors without a unit suffix round to the nearest foot for display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Distance(pub f64, pub f64);
 
 impl Distance {
-    pub fn new_from_f64(ground_run: f64, clear_50_ft_obstacle: f64) -> Distance {
-        Distance(ground_run.round() as i16, clear_50_ft_obstacle.round() as i16)
+    pub fn scale(self, factor: f64) -> Distance {
+        Distance(self.0 * factor, self.1 * factor)
     }
-    
-    pub fn ground_run(&self) -> i16 {
+
+    pub fn offset(self, offset_ft: f64) -> Distance {
+        Distance(self.0 + offset_ft, self.1 + offset_ft)
+    }
+
+    pub fn ground_run_ft(&self) -> f64 {
         self.0
     }
 
-    pub fn clear_50_ft_obstacle(&self) -> i16 {
+    pub fn clear_50_ft_obstacle_ft(&self) -> f64 {
         self.1
     }
+
+    pub fn ground_run(&self) -> i16 {
+        self.0.round() as i16
+    }
+
+    pub fn clear_50_ft_obstacle(&self) -> i16 {
+        self.1.round() as i16
+    }
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}' / {}'", self.ground_run(), self.clear_50_ft_obstacle())
+    }
 }
\ No newline at end of file
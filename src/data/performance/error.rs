@@ -0,0 +1,38 @@
+use std::fmt;
+
+use super::grid::GridError;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PerformanceError {
+    Message(&'static str),
+    /// POH note 4 on the 172M take off chart, the cell was deleted because the climb after lift-off is below 150 fpm.
+    ClimbLimited { aircraft_weight_lbs: i16, pressure_altitude_ft: i16, temperature_c: i16 },
+    /// A tailwind beyond what the POH corrects for, zero where the charts have no tailwind correction at all.
+    TailwindLimit { tailwind_kts: f64, limit_kts: f64 }
+}
+
+impl From<&'static str> for PerformanceError {
+    fn from(message: &'static str) -> Self {
+        PerformanceError::Message(message)
+    }
+}
+
+impl From<GridError> for PerformanceError {
+    fn from(err: GridError) -> Self {
+        PerformanceError::Message(err.message())
+    }
+}
+
+impl fmt::Display for PerformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerformanceError::Message(message) => write!(f, "{message}"),
+            PerformanceError::ClimbLimited { aircraft_weight_lbs, pressure_altitude_ft, temperature_c } => write!(f,
+                "Performance not available — climb below 150 fpm at {pressure_altitude_ft}' pressure altitude and {temperature_c}°C for {aircraft_weight_lbs} lbs"),
+            PerformanceError::TailwindLimit { tailwind_kts, limit_kts } if *limit_kts == 0.0 => write!(f,
+                "Performance not available — the charts give no correction for the {tailwind_kts:.0} kts tailwind"),
+            PerformanceError::TailwindLimit { tailwind_kts, limit_kts } => write!(f,
+                "Performance not available — the {tailwind_kts:.0} kts tailwind is over the {limit_kts:.0} kts the charts correct for")
+        }
+    }
+}
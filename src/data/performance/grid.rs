@@ -0,0 +1,392 @@
+use std::fmt;
+
+use crate::math::FloatingCalcs;
+
+use super::distance::Distance;
+
+/// What to do when a value falls outside of the values an axis was charted for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Extrapolation {
+    Error,
+    Clamp,
+    LinearWithWarning
+}
+
+pub trait Interpolate: Copy {
+    fn interpolate(fraction: f64, lower: Self, upper: Self) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(fraction: f64, lower: Self, upper: Self) -> Self {
+        fraction.percent_of(lower, upper)
+    }
+}
+
+impl Interpolate for Distance {
+    fn interpolate(fraction: f64, lower: Self, upper: Self) -> Self {
+        fraction.percent_of_distance(lower, upper)
+    }
+}
+
+#[derive(Debug)]
+pub enum GridError {
+    OutOfRange { axis: &'static str, unit: &'static str, value: f64, min: f64, max: f64 },
+    MissingCell { point: Vec<(&'static str, &'static str, f64)> }
+}
+
+impl GridError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            GridError::OutOfRange { .. } => "Outside the range of the performance tables",
+            GridError::MissingCell { .. } => "Performance not available"
+        }
+    }
+}
+
+fn format_point(point: &[(&'static str, &'static str, f64)]) -> String {
+    point.iter()
+        .map(|(name, unit, value)| format!("{name} {value}{unit}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::OutOfRange { axis, unit, value, min, max } => write!(f, "The {axis} of {value:.0}{unit} is outside of the charted {min}{unit} to {max}{unit}."),
+            GridError::MissingCell { point } => write!(f, "No performance is charted at {}.", format_point(point))
+        }
+    }
+}
+
+pub struct Axis {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub values: Vec<f64>,
+    pub below: Extrapolation,
+    pub above: Extrapolation
+}
+
+/// Where a value falls on an axis. An exact match on a charted value uses the same index for both bounds.
+#[derive(Clone, Copy)]
+pub struct Bracket {
+    pub value: f64,
+    pub lower_index: usize,
+    pub upper_index: usize,
+    pub lower: f64,
+    pub upper: f64,
+    pub fraction: f64
+}
+
+impl Axis {
+    pub fn new(name: &'static str, unit: &'static str, values: &[f64]) -> Self {
+        assert!(!values.is_empty() && values.windows(2).all(|pair| pair[0] < pair[1]), "The {name} values to be ascending");
+
+        Axis {
+            name,
+            unit,
+            values: values.to_vec(),
+            below: Extrapolation::Error,
+            above: Extrapolation::Error
+        }
+    }
+
+    pub fn extrapolate(mut self, below: Extrapolation, above: Extrapolation) -> Self {
+        self.below = below;
+        self.above = above;
+        self
+    }
+
+    fn between(&self, value: f64, lower_index: usize, upper_index: usize) -> Bracket {
+        let lower = self.values[lower_index];
+        let upper = self.values[upper_index];
+        Bracket { value, lower_index, upper_index, lower, upper, fraction: value.percent(lower, upper) }
+    }
+
+    fn out_of_range(&self, value: f64, policy: Extrapolation, edge_index: usize, next_index: usize, warnings: &mut Vec<String>) -> Result<Bracket, GridError> {
+        let min = self.values[0];
+        let max = self.values[self.values.len() - 1];
+        match policy {
+            Extrapolation::Error => Err(GridError::OutOfRange { axis: self.name, unit: self.unit, value, min, max }),
+            Extrapolation::Clamp => Ok(self.between(value, edge_index, edge_index)),
+            Extrapolation::LinearWithWarning => {
+                warnings.push(format!("The {} of {value:.0}{} is outside of the charted {min}{} to {max}{}, the values were extrapolated.", self.name, self.unit, self.unit, self.unit));
+                Ok(self.between(value, edge_index.min(next_index), edge_index.max(next_index)))
+            }
+        }
+    }
+
+    pub fn bracket(&self, value: f64, warnings: &mut Vec<String>) -> Result<Bracket, GridError> {
+        let last = self.values.len() - 1;
+        let out_of_range = || GridError::OutOfRange { axis: self.name, unit: self.unit, value, min: self.values[0], max: self.values[last] };
+
+        if value.is_nan() {
+            return Err(out_of_range());
+        }
+
+        if let Some(index) = self.values.iter().position(|charted| *charted == value) {
+            return Ok(self.between(value, index, index));
+        }
+
+        if value < self.values[0] {
+            return self.out_of_range(value, self.below, 0, last.min(1), warnings);
+        }
+
+        if value > self.values[last] {
+            return self.out_of_range(value, self.above, last, last.saturating_sub(1), warnings);
+        }
+
+        match self.values.iter().position(|charted| *charted > value) {
+            Some(upper_index) if upper_index > 0 => Ok(self.between(value, upper_index - 1, upper_index)),
+            _ => Err(out_of_range())
+        }
+    }
+}
+
+/// One linear interpolation performed while looking up a value, kept so the working can be displayed.
+pub struct TraceStep<T> {
+    pub axis: &'static str,
+    pub unit: &'static str,
+    pub fixed: Vec<(&'static str, &'static str, f64)>,
+    pub bracket: Bracket,
+    pub lower_value: T,
+    pub upper_value: T,
+    pub value: T
+}
+
+impl<T: fmt::Display> fmt::Display for TraceStep<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.fixed.is_empty() {
+            write!(f, "At {}: ", format_point(&self.fixed))?;
+        }
+
+        write!(f, "{} {:.0}{} is {:.2} of the way from {}{} ({}) to {}{} ({}) = {}",
+            self.axis,
+            self.bracket.value,
+            self.unit,
+            self.bracket.fraction,
+            self.bracket.lower,
+            self.unit,
+            self.lower_value,
+            self.bracket.upper,
+            self.unit,
+            self.upper_value,
+            self.value)
+    }
+}
+
+pub struct Lookup<T> {
+    pub value: T,
+    pub brackets: Vec<Bracket>,
+    pub trace: Vec<TraceStep<T>>,
+    pub warnings: Vec<String>
+}
+
+/// A table of charted values over any number of named axes, stored with the last axis varying fastest.
+/// Cells which were not charted are `None`, looking up a value that depends on one is an error.
+pub struct Grid<T> {
+    axes: Vec<Axis>,
+    cells: Vec<Option<T>>
+}
+
+impl<T: Interpolate> Grid<T> {
+    pub fn new(axes: Vec<Axis>, cells: Vec<Option<T>>) -> Self {
+        let size: usize = axes.iter().map(|axis| axis.values.len()).product();
+        assert_eq!(size, cells.len(), "The number of cells to match the axes");
+
+        Grid { axes, cells }
+    }
+
+    pub fn axis(&self, index: usize) -> &Axis {
+        &self.axes[index]
+    }
+
+    fn cell(&self, indices: &[usize]) -> Option<T> {
+        let offset = indices.iter().zip(&self.axes).fold(0, |offset, (index, axis)| offset * axis.values.len() + index);
+        self.cells[offset]
+    }
+
+    fn point(&self, indices: &[usize]) -> Vec<(&'static str, &'static str, f64)> {
+        indices.iter().zip(&self.axes).map(|(index, axis)| (axis.name, axis.unit, axis.values[*index])).collect()
+    }
+
+    /// Interpolates the last axis first, working outwards to the first axis.
+    fn interpolate(&self, brackets: &[Bracket], indices: &mut Vec<usize>, trace: &mut Vec<TraceStep<T>>) -> Result<T, GridError> {
+        let depth = indices.len();
+        if depth == self.axes.len() {
+            return self.cell(indices).ok_or_else(|| GridError::MissingCell { point: self.point(indices) });
+        }
+
+        let bracket = brackets[depth];
+
+        indices.push(bracket.lower_index);
+        let lower_value = self.interpolate(brackets, indices, trace)?;
+        indices.pop();
+
+        if bracket.lower_index == bracket.upper_index {
+            return Ok(lower_value);
+        }
+
+        indices.push(bracket.upper_index);
+        let upper_value = self.interpolate(brackets, indices, trace)?;
+        indices.pop();
+
+        let value = T::interpolate(bracket.fraction, lower_value, upper_value);
+        let axis = &self.axes[depth];
+
+        trace.push(TraceStep {
+            axis: axis.name,
+            unit: axis.unit,
+            fixed: self.point(indices),
+            bracket,
+            lower_value,
+            upper_value,
+            value
+        });
+
+        Ok(value)
+    }
+
+    pub fn lookup(&self, point: &[f64]) -> Result<Lookup<T>, GridError> {
+        assert_eq!(point.len(), self.axes.len(), "A value for every axis");
+
+        let mut warnings = vec![];
+        let brackets = self.axes.iter().zip(point)
+            .map(|(axis, value)| axis.bracket(*value, &mut warnings))
+            .collect::<Result<Vec<Bracket>, GridError>>()?;
+
+        let mut trace = vec![];
+        let value = self.interpolate(&brackets, &mut vec![], &mut trace)?;
+
+        Ok(Lookup { value, brackets, trace, warnings })
+    }
+
+    /// The lower bound, value and upper bound along two axes with every other axis held at the point, as rows by columns.
+    pub fn cross_section(&self, point: &[f64], row_axis: usize, column_axis: usize) -> Result<[[T; 3]; 3], GridError> {
+        let mut warnings = vec![];
+        let rows = self.axes[row_axis].bracket(point[row_axis], &mut warnings)?;
+        let columns = self.axes[column_axis].bracket(point[column_axis], &mut warnings)?;
+
+        let value_at = |row: f64, column: f64| -> Result<T, GridError> {
+            let mut at = point.to_vec();
+            at[row_axis] = row;
+            at[column_axis] = column;
+            Ok(self.lookup(&at)?.value)
+        };
+
+        let row = |row: f64| -> Result<[T; 3], GridError> {
+            Ok([value_at(row, columns.lower)?, value_at(row, columns.value)?, value_at(row, columns.upper)?])
+        };
+
+        Ok([row(rows.lower)?, row(rows.value)?, row(rows.upper)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_bracket(bracket: Bracket, lower_index: usize, upper_index: usize, fraction: f64) {
+        assert_eq!((bracket.lower_index, bracket.upper_index), (lower_index, upper_index));
+        assert!((bracket.fraction - fraction).abs() < 1e-9, "fraction {} != {fraction}", bracket.fraction);
+    }
+
+    fn axis(below: Extrapolation, above: Extrapolation) -> Axis {
+        Axis::new("altitude", "'", &[0.0, 1000.0, 2000.0]).extrapolate(below, above)
+    }
+
+    #[test]
+    fn a_charted_value_brackets_itself() {
+        let mut warnings = vec![];
+        assert_bracket(axis(Extrapolation::Error, Extrapolation::Error).bracket(1000.0, &mut warnings).unwrap(), 1, 1, 0.0);
+        assert_bracket(axis(Extrapolation::Error, Extrapolation::Error).bracket(1250.0, &mut warnings).unwrap(), 1, 2, 0.25);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn values_off_the_chart_follow_the_axis_policy() {
+        let mut warnings = vec![];
+        let error = axis(Extrapolation::Error, Extrapolation::Error).bracket(-500.0, &mut warnings).err().unwrap();
+        assert_eq!(error.to_string(), "The altitude of -500' is outside of the charted 0' to 2000'.");
+
+        assert_bracket(axis(Extrapolation::Clamp, Extrapolation::Clamp).bracket(-500.0, &mut warnings).unwrap(), 0, 0, 0.0);
+        assert_bracket(axis(Extrapolation::Clamp, Extrapolation::Clamp).bracket(2500.0, &mut warnings).unwrap(), 2, 2, 0.0);
+        assert!(warnings.is_empty());
+
+        assert_bracket(axis(Extrapolation::LinearWithWarning, Extrapolation::LinearWithWarning).bracket(2500.0, &mut warnings).unwrap(), 1, 2, 1.5);
+        assert_bracket(axis(Extrapolation::LinearWithWarning, Extrapolation::LinearWithWarning).bracket(-500.0, &mut warnings).unwrap(), 0, 1, -0.5);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].ends_with("the values were extrapolated."));
+    }
+
+    #[test]
+    fn a_single_value_axis_and_nan_do_not_underflow() {
+        let mut warnings = vec![];
+        let single = Axis::new("weight", " lbs", &[2300.0]).extrapolate(Extrapolation::Clamp, Extrapolation::LinearWithWarning);
+        assert_bracket(single.bracket(2300.0, &mut warnings).unwrap(), 0, 0, 0.0);
+        assert_bracket(single.bracket(2000.0, &mut warnings).unwrap(), 0, 0, 0.0);
+        assert_bracket(single.bracket(2400.0, &mut warnings).unwrap(), 0, 0, 0.0);
+
+        for axis in [single, axis(Extrapolation::Clamp, Extrapolation::LinearWithWarning)] {
+            assert!(matches!(axis.bracket(f64::NAN, &mut warnings), Err(GridError::OutOfRange { .. })));
+        }
+    }
+
+    /// value = weight + altitude / 100 + temperature / 10, so every interpolation is exact.
+    fn grid(missing: Option<usize>) -> Grid<f64> {
+        let weights = [1000.0, 2000.0];
+        let altitudes = [0.0, 1000.0, 2000.0];
+        let temperatures = [0.0, 20.0];
+        let mut cells = vec![];
+        for weight in weights {
+            for altitude in altitudes {
+                for temperature in temperatures {
+                    cells.push(Some(weight + altitude / 100.0 + temperature / 10.0));
+                }
+            }
+        }
+        if let Some(index) = missing {
+            cells[index] = None;
+        }
+
+        Grid::new(vec![Axis::new("weight", " lbs", &weights), Axis::new("altitude", "'", &altitudes), Axis::new("temperature", "°C", &temperatures)], cells)
+    }
+
+    #[test]
+    fn lookups_interpolate_every_axis_and_trace_the_working() {
+        let lookup = grid(None).lookup(&[1500.0, 500.0, 10.0]).unwrap();
+        assert!((lookup.value - (1500.0 + 5.0 + 1.0)).abs() < 1e-9);
+        assert_eq!(lookup.brackets.len(), 3);
+        // Temperature at each of the four weight and altitude corners, altitude at both weights, then weight.
+        assert_eq!(lookup.trace.len(), 7);
+        assert_eq!(lookup.trace[6].axis, "weight");
+        assert!(lookup.trace[0].fixed == vec![("weight", " lbs", 1000.0), ("altitude", "'", 0.0)]);
+        assert_eq!(lookup.trace[0].to_string(), "At weight 1000 lbs, altitude 0': temperature 10°C is 0.50 of the way from 0°C (1000) to 20°C (1002) = 1001");
+    }
+
+    #[test]
+    fn charted_points_are_read_without_interpolating() {
+        let lookup = grid(None).lookup(&[2000.0, 1000.0, 20.0]).unwrap();
+        assert_eq!(lookup.value, 2012.0);
+        assert!(lookup.trace.is_empty());
+    }
+
+    #[test]
+    fn a_missing_cell_is_only_an_error_when_it_is_needed() {
+        // The cell at 2000 lbs, 2000', 20°C.
+        let grid = grid(Some(11));
+        assert!(grid.lookup(&[1500.0, 500.0, 10.0]).is_ok());
+
+        let error = grid.lookup(&[1500.0, 1500.0, 10.0]).err().unwrap();
+        assert!(matches!(&error, GridError::MissingCell { point } if *point == vec![("weight", " lbs", 2000.0), ("altitude", "'", 2000.0), ("temperature", "°C", 20.0)]));
+        assert_eq!(error.to_string(), "No performance is charted at weight 2000 lbs, altitude 2000', temperature 20°C.");
+    }
+
+    #[test]
+    fn cross_sections_hold_the_other_axes_at_the_point() {
+        let [lower, middle, upper] = grid(None).cross_section(&[1500.0, 500.0, 10.0], 1, 2).unwrap();
+        assert_eq!(lower, [1500.0, 1501.0, 1502.0]);
+        assert_eq!(middle, [1505.0, 1506.0, 1507.0]);
+        assert_eq!(upper, [1510.0, 1511.0, 1512.0]);
+    }
+}
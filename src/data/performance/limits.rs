@@ -0,0 +1,16 @@
+/// The conditions an aircraft's charts cover, so inputs can be checked before a lookup instead of failing part way through it.
+pub struct ChartLimits {
+    /// The heaviest weight charted, the maximum gross weight.
+    pub max_weight_lbs: i16,
+    /// Whether take off distances are charted by weight, so a weight is needed for a take off.
+    pub take_off_by_weight: bool,
+    /// Whether the charts are entered by pressure altitude, so an altimeter setting is needed. Otherwise by field elevation.
+    pub by_pressure_altitude: bool,
+    /// The altitudes charted, None where the chart is clamped or extrapolated past its edge.
+    pub min_altitude_ft: Option<f64>,
+    pub max_altitude_ft: Option<f64>,
+    pub max_temperature_c: Option<f64>,
+    /// The headwinds charted or corrected for, negative for a tailwind. Only a take off chart is entered by headwind.
+    pub min_headwind_kts: f64,
+    pub max_take_off_headwind_kts: Option<f64>
+}
@@ -1,5 +1,12 @@
+pub mod abort;
 pub mod aircraft;
 pub mod atmosphere_bounds;
+pub mod climb;
+pub mod configuration;
 pub mod distance;
+pub mod error;
+pub mod grid;
 pub mod headwinds;
-pub mod performance_row;
\ No newline at end of file
+pub mod limits;
+pub mod performance_row;
+pub mod speeds;
\ No newline at end of file
@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use crate::math::FloatingCalcs;
+
+use super::grid::{Axis, Extrapolation};
+
+/// The speeds of a briefing, all in knots indicated.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum SpeedKind {
+    Vr,
+    Vx,
+    Vy,
+    Vref,
+    Vs0,
+    Vs1,
+    Vg
+}
+
+impl SpeedKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            SpeedKind::Vr => "Vr",
+            SpeedKind::Vx => "Vx",
+            SpeedKind::Vy => "Vy",
+            SpeedKind::Vref => "Vref",
+            SpeedKind::Vs0 => "Vs0",
+            SpeedKind::Vs1 => "Vs1",
+            SpeedKind::Vg => "Vg"
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            SpeedKind::Vr => "Rotation",
+            SpeedKind::Vx => "Best angle of climb",
+            SpeedKind::Vy => "Best rate of climb",
+            SpeedKind::Vref => "Final approach",
+            SpeedKind::Vs0 => "Stall, landing configuration",
+            SpeedKind::Vs1 => "Stall, clean",
+            SpeedKind::Vg => "Best glide"
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Speed {
+    pub kind: SpeedKind,
+    pub kias: f64
+}
+
+/// Each speed charted at a few weights, interpolated between them and held at the lightest and heaviest.
+/// The POHs give the speeds at the maximum weight, the lighter weights are scaled from them by the square root of the weight ratio.
+pub struct SpeedTable {
+    pub weights_lbs: &'static [f64],
+    pub speeds_kias: &'static [(SpeedKind, &'static [f64])]
+}
+
+impl SpeedTable {
+    /// The speeds with the charted weight they were read at, a weight off the table is held at its lightest or heaviest weight.
+    pub fn at(&self, weight_lbs: f64) -> (f64, Vec<Speed>) {
+        let bracket = Axis::new("weight", " lbs", self.weights_lbs)
+            .extrapolate(Extrapolation::Clamp, Extrapolation::Clamp)
+            .bracket(weight_lbs, &mut vec![])
+            .expect("To clamp the weight to the speed table");
+
+        let speeds = self.speeds_kias.iter()
+            .map(|(kind, speeds_kias)| Speed {
+                kind: *kind,
+                kias: bracket.fraction.percent_of(speeds_kias[bracket.lower_index], speeds_kias[bracket.upper_index])
+            })
+            .collect();
+        (weight_lbs.clamp(self.weights_lbs[0], self.max_weight_lbs()), speeds)
+    }
+
+    pub fn max_weight_lbs(&self) -> f64 {
+        self.weights_lbs[self.weights_lbs.len() - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: SpeedTable = SpeedTable {
+        weights_lbs: &[1900.0, 2300.0],
+        speeds_kias: &[(SpeedKind::Vr, &[50.0, 55.0]), (SpeedKind::Vg, &[59.0, 65.0])]
+    };
+
+    #[test]
+    fn speeds_are_interpolated_by_weight() {
+        let (weight_lbs, speeds) = TABLE.at(2100.0);
+        assert_eq!(weight_lbs, 2100.0);
+        assert_eq!(speeds[0], Speed { kind: SpeedKind::Vr, kias: 52.5 });
+        assert_eq!(speeds[1], Speed { kind: SpeedKind::Vg, kias: 62.0 });
+    }
+
+    #[test]
+    fn weights_off_the_table_are_held_at_its_ends() {
+        let (weight_lbs, speeds) = TABLE.at(1500.0);
+        assert_eq!(weight_lbs, 1900.0);
+        assert_eq!(speeds[0].kias, 50.0);
+
+        let (weight_lbs, speeds) = TABLE.at(2500.0);
+        assert_eq!(weight_lbs, 2300.0);
+        assert_eq!(speeds[1].kias, 65.0);
+    }
+}
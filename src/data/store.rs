@@ -0,0 +1,363 @@
+use lazy_static::lazy_static;
+
+use serde::{Deserialize, Serialize};
+
+use super::{airports::NARROW_RUNWAY_WIDTH_FT, performance::aircraft::profile::AircraftProfile};
+use crate::{config, math::units::Units};
+use std::{fs, io::ErrorKind, path::PathBuf, sync::Mutex, time::{SystemTime, UNIX_EPOCH}};
+
+pub const STORE_FILE: &str = "store.json";
+const MAX_HISTORY: usize = 500;
+
+lazy_static! {
+    pub static ref STORE: Mutex<Store> = Mutex::new(Store::load_or_empty(config::get().data_file(STORE_FILE)));
+}
+
+/// The profile fields are flattened so the saved records keep their shape.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct AircraftRecord {
+    #[serde(flatten)]
+    pub profile: AircraftProfile,
+    pub aircraft_type: String,
+    pub empty_weight_lbs: i16,
+    pub empty_cg_in: f32,
+    pub equipment: String
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct PilotProfile {
+    pub name: String,
+    pub max_crosswind_kts: Option<i16>,
+    pub max_tailwind_kts: Option<i16>,
+    pub min_runway_length_ft: Option<u16>,
+    pub min_runway_width_ft: Option<u16>,
+    /// Applies instead of max_crosswind_kts on runways narrower than NARROW_RUNWAY_WIDTH_FT.
+    pub max_narrow_runway_crosswind_kts: Option<i16>,
+    pub runway_margin_percentage: Option<f64>
+}
+
+impl PilotProfile {
+    pub fn check_minimums(&self, headwind_kts: f64, crosswind_kts: Option<f64>, runway_length_ft: Option<u16>, runway_width_ft: Option<u16>, required_ft: Option<i16>, units: &Units) -> Vec<String> {
+        let mut warnings = vec![];
+
+        if let Some(max_tailwind_kts) = self.max_tailwind_kts {
+            if -headwind_kts > max_tailwind_kts as f64 {
+                warnings.push(format!("Tailwind of {:.0} kts exceeds the personal minimum of {max_tailwind_kts} kts.", -headwind_kts));
+            }
+        }
+
+        let narrow_width_ft = runway_width_ft.filter(|width_ft| *width_ft > 0 && *width_ft < NARROW_RUNWAY_WIDTH_FT);
+        match (narrow_width_ft, self.max_narrow_runway_crosswind_kts, crosswind_kts) {
+            (Some(width_ft), Some(max_crosswind_kts), Some(crosswind_kts)) => {
+                if crosswind_kts.abs() > max_crosswind_kts as f64 {
+                    warnings.push(format!("Crosswind of {:.0} kts exceeds the personal minimum of {max_crosswind_kts} kts for a {} wide runway.", crosswind_kts.abs(), units.feet(width_ft)));
+                }
+            },
+            _ => if let (Some(max_crosswind_kts), Some(crosswind_kts)) = (self.max_crosswind_kts, crosswind_kts) {
+                if crosswind_kts.abs() > max_crosswind_kts as f64 {
+                    warnings.push(format!("Crosswind of {:.0} kts exceeds the personal minimum of {max_crosswind_kts} kts.", crosswind_kts.abs()));
+                }
+            }
+        }
+
+        if let (Some(min_runway_width_ft), Some(runway_width_ft)) = (self.min_runway_width_ft, runway_width_ft.filter(|width_ft| *width_ft > 0)) {
+            if runway_width_ft < min_runway_width_ft {
+                warnings.push(format!("Runway width of {} is below the personal minimum of {}.", units.feet(runway_width_ft), units.feet(min_runway_width_ft)));
+            }
+        }
+
+        if let (Some(min_runway_length_ft), Some(runway_length_ft)) = (self.min_runway_length_ft, runway_length_ft) {
+            if runway_length_ft < min_runway_length_ft {
+                warnings.push(format!("Runway length of {} is below the personal minimum of {}.", units.feet(runway_length_ft), units.feet(min_runway_length_ft)));
+            }
+        }
+
+        if let (Some(runway_margin_percentage), Some(runway_length_ft), Some(required_ft)) = (self.runway_margin_percentage, runway_length_ft, required_ft) {
+            let required_with_margin_ft = (required_ft as f64 * (1.0 + runway_margin_percentage / 100.0)).round();
+            if required_with_margin_ft > runway_length_ft as f64 {
+                warnings.push(format!("{} plus the personal margin of {runway_margin_percentage}% is {}, more than the {} runway.",
+                    units.feet(required_ft), units.feet(required_with_margin_ft), units.feet(runway_length_ft)));
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Which page made a calculation, the records saved before there was more than one are runway calculations.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalculationKind {
+    #[default]
+    Runway,
+    FlightPlan,
+    Card
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CalculationRecord {
+    pub id: u32,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub kind: CalculationKind,
+    pub aircraft_type: String,
+    pub tail_number: Option<String>,
+    pub pilot: Option<String>,
+    pub is_take_off: bool,
+    pub query: String,
+    pub ground_run_ft: Option<i16>,
+    pub clear_50_ft_obstacle_ft: Option<i16>,
+    pub verdict: String
+}
+
+impl CalculationRecord {
+    pub fn formatted_timestamp(&self) -> String {
+        format_utc(self.timestamp)
+    }
+
+    pub fn mode(&self) -> &'static str {
+        match (self.kind, self.is_take_off) {
+            (CalculationKind::Card, _) => "Card",
+            (_, true) => "Take Off",
+            (_, false) => "Landing"
+        }
+    }
+
+    /// Where the calculation can be made again, a flight plan is only posted so it has none.
+    pub fn recalculate_href(&self) -> Option<String> {
+        match self.kind {
+            CalculationKind::Runway => Some(format!("/runway?{}", self.query)),
+            CalculationKind::Card => Some(format!("/card?{}", self.query)),
+            CalculationKind::FlightPlan => None
+        }
+    }
+
+    /// The remaining query parameters the calculation was made with, as (name, value) pairs.
+    pub fn inputs(&self) -> Vec<(String, String)> {
+        serde_urlencoded::from_str::<Vec<(String, String)>>(&self.query)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(name, _)| !matches!(name.as_str(), "aircraft_type" | "is_take_off" | "tail_number" | "pilot"))
+            .map(|(name, value)| (name.replace('_', " "), value))
+            .collect()
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Store {
+    #[serde(skip)]
+    path: PathBuf,
+    /// Why the file could not be read, saving is refused so it is not overwritten before it is repaired.
+    #[serde(skip)]
+    load_error: Option<String>,
+    pub aircraft: Vec<AircraftRecord>,
+    pub pilots: Vec<PilotProfile>,
+    pub history: Vec<CalculationRecord>
+}
+
+impl Store {
+    pub fn load(path: PathBuf) -> Result<Store, String> {
+        let mut store: Store = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|err| format!("{} is not a valid store: {err}", path.display()))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => Store::default(),
+            Err(err) => return Err(format!("Unable to read {}: {err}", path.display()))
+        };
+
+        store.path = path;
+        Ok(store)
+    }
+
+    /// Config validation reports an unreadable store at startup, this keeps the calculations working if it breaks afterwards.
+    fn load_or_empty(path: PathBuf) -> Store {
+        Store::load(path.clone()).unwrap_or_else(|error| {
+            eprintln!("Warning: {error}, continuing with an empty store that will not be saved.");
+            Store { path, load_error: Some(error), ..Store::default() }
+        })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(error) = &self.load_error {
+            return Err(format!("Not saved as {error}."));
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, json).map_err(|err| format!("Unable to save {}: {err}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path).map_err(|err| format!("Unable to save {}: {err}", self.path.display()))
+    }
+
+    /// Makes the change to a copy and keeps it only once the copy is saved, so a failed save changes nothing.
+    fn update<T>(&mut self, change: impl FnOnce(&mut Store) -> T) -> Result<T, String> {
+        let mut updated = self.clone();
+        let result = change(&mut updated);
+        updated.save()?;
+        *self = updated;
+        Ok(result)
+    }
+
+    pub fn find_aircraft(&self, tail_number: &str) -> Option<&AircraftRecord> {
+        self.aircraft.iter().find(|aircraft| aircraft.profile.tail_number.eq_ignore_ascii_case(tail_number))
+    }
+
+    pub fn find_pilot(&self, name: &str) -> Option<&PilotProfile> {
+        self.pilots.iter().find(|pilot| pilot.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn find_calculation(&self, id: u32) -> Option<&CalculationRecord> {
+        self.history.iter().find(|record| record.id == id)
+    }
+
+    pub fn save_aircraft(&mut self, aircraft: AircraftRecord) -> Result<(), String> {
+        self.update(|store| {
+            store.aircraft.retain(|existing| !existing.profile.tail_number.eq_ignore_ascii_case(&aircraft.profile.tail_number));
+            store.aircraft.push(aircraft);
+            store.aircraft.sort_by(|a, b| a.profile.tail_number.cmp(&b.profile.tail_number));
+        })
+    }
+
+    pub fn delete_aircraft(&mut self, tail_number: &str) -> Result<(), String> {
+        self.update(|store| store.aircraft.retain(|existing| !existing.profile.tail_number.eq_ignore_ascii_case(tail_number)))
+    }
+
+    pub fn save_pilot(&mut self, pilot: PilotProfile) -> Result<(), String> {
+        self.update(|store| {
+            store.pilots.retain(|existing| !existing.name.eq_ignore_ascii_case(&pilot.name));
+            store.pilots.push(pilot);
+            store.pilots.sort_by(|a, b| a.name.cmp(&b.name));
+        })
+    }
+
+    pub fn delete_pilot(&mut self, name: &str) -> Result<(), String> {
+        self.update(|store| store.pilots.retain(|existing| !existing.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// Appends the calculation, assigning its id and timestamp. Only the most recent entries are kept.
+    /// The same inputs give the same result, so a calculation already in the history is not recorded again.
+    pub fn record_calculation(&mut self, mut record: CalculationRecord) -> Result<u32, String> {
+        if let Some(existing) = self.history.iter().find(|existing| existing.kind == record.kind && existing.query == record.query) {
+            return Ok(existing.id);
+        }
+
+        record.id = self.history.iter().map(|existing| existing.id).max().unwrap_or(0) + 1;
+        record.timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+
+        let id = record.id;
+        self.update(|store| {
+            store.history.push(record);
+            if store.history.len() > MAX_HISTORY {
+                store.history.remove(0);
+            }
+        })?;
+        Ok(id)
+    }
+}
+
+pub fn format_utc(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds_of_day = timestamp % 86400;
+
+    // Civil from days, http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}Z", seconds_of_day / 3600, (seconds_of_day % 3600) / 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::math::units::LengthUnit;
+
+    use super::*;
+
+    #[test]
+    fn aircraft_records_keep_their_saved_shape() {
+        let json = r#"{ "tail_number": "N172SP", "aircraft_type": "cessna172m", "empty_weight_lbs": 1500, "empty_cg_in": 39.5, "equipment": "GPS", "take_off_factor_percentage": 10.0 }"#;
+        let record: AircraftRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.profile.tail_number, "N172SP");
+        assert_eq!(record.profile.take_off_factor_percentage, 10.0);
+        assert_eq!(record.profile.landing_factor_percentage, 0.0);
+
+        let saved = serde_json::to_value(&record).unwrap();
+        assert_eq!(saved["tail_number"], "N172SP");
+        assert_eq!(saved["aircraft_type"], "cessna172m");
+    }
+
+    #[test]
+    fn minimums_are_reported_in_the_display_units() {
+        let pilot = PilotProfile { name: String::from("Sam"), min_runway_length_ft: Some(3000), runway_margin_percentage: Some(50.0), ..PilotProfile::default() };
+        let metres = Units { length_unit: LengthUnit::Metres, ..Units::IMPERIAL };
+        assert_eq!(pilot.check_minimums(0.0, None, Some(2500), None, Some(2000), &metres), [
+            "Runway length of 762 m is below the personal minimum of 914 m.",
+            "610 m plus the personal margin of 50% is 914 m, more than the 762 m runway."
+        ]);
+    }
+
+    #[test]
+    fn a_corrupt_store_is_an_error_and_is_not_overwritten() {
+        let path = env::temp_dir().join(format!("aircraft-performance-store-{}.json", std::process::id()));
+        fs::write(&path, "{ \"aircraft\": [").unwrap();
+
+        assert!(Store::load(path.clone()).err().is_some_and(|error| error.contains("is not a valid store")));
+
+        let mut store = Store::load_or_empty(path.clone());
+        assert!(store.aircraft.is_empty());
+        assert!(store.save_pilot(PilotProfile { name: String::from("Sam"), ..PilotProfile::default() }).unwrap_err().starts_with("Not saved as"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ \"aircraft\": [");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn only_a_missing_store_starts_empty() {
+        let missing = env::temp_dir().join(format!("aircraft-performance-missing-{}.json", std::process::id()));
+        assert!(Store::load(missing).is_ok_and(|store| store.aircraft.is_empty()));
+
+        // A directory cannot be read as a file, like any other unreadable store it is an error rather than empty.
+        assert!(Store::load(env::temp_dir()).err().is_some_and(|error| error.starts_with("Unable to read")));
+    }
+
+    #[test]
+    fn a_repeated_calculation_is_recorded_once() {
+        let path = env::temp_dir().join(format!("aircraft-performance-history-{}.json", std::process::id()));
+        let mut store = Store::load(path.clone()).unwrap();
+        let record = |kind, query: &str| CalculationRecord {
+            id: 0,
+            timestamp: 0,
+            kind,
+            aircraft_type: String::from("cessna172m"),
+            tail_number: None,
+            pilot: None,
+            is_take_off: true,
+            query: query.to_string(),
+            ground_run_ft: None,
+            clear_50_ft_obstacle_ft: None,
+            verdict: String::new()
+        };
+
+        let id = store.record_calculation(record(CalculationKind::Runway, "elevation_ft=912")).unwrap();
+        assert_eq!(store.record_calculation(record(CalculationKind::Runway, "elevation_ft=912")).unwrap(), id);
+        assert_ne!(store.record_calculation(record(CalculationKind::Card, "elevation_ft=912")).unwrap(), id);
+        assert_ne!(store.record_calculation(record(CalculationKind::Runway, "elevation_ft=1000")).unwrap(), id);
+        assert_eq!(store.history.len(), 3);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_failed_save_leaves_the_store_unchanged() {
+        let path = env::temp_dir().join(format!("aircraft-performance-no-such-dir-{}", std::process::id())).join(STORE_FILE);
+        let mut store = Store::load(path).unwrap();
+
+        assert!(store.save_pilot(PilotProfile { name: String::from("Sam"), ..PilotProfile::default() }).unwrap_err().starts_with("Unable to save"));
+        assert!(store.pilots.is_empty());
+    }
+}
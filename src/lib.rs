@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+
+pub mod cli;
+pub mod config;
+pub mod data;
+pub mod pages;
+pub mod math;
+pub mod pdf;
+
+use axum::{extract::Query, Router};
+use tower_http::services::ServeDir;
+
+use config::Config;
+use pages::aircraft_pages::QueryPerformanceParameters;
+
+pub fn router(config: Config) -> Router {
+    config::init(config);
+
+    Router::new()
+        .nest_service("/", ServeDir::new(&config::get().static_dir))
+        .route("/airport", axum::routing::post(pages::airport::post))
+        .route("/airport/:identifier/departure/:aircraft_type", axum::routing::get(pages::airport::get_departure))
+        .route("/airport/:identifier/arrival/:aircraft_type", axum::routing::get(pages::airport::get_arrival))
+        .route("/aircraft/cessna150j/take-off", axum::routing::get(pages::aircraft_pages::cessna150j::get_for_take_off))
+        .route("/aircraft/cessna150j/landing", axum::routing::get(pages::aircraft_pages::cessna150j::get_for_landing))
+        .route("/aircraft/cessna172m/take-off", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_take_off("cessna172m", query)))
+        .route("/aircraft/cessna172m/landing", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_landing("cessna172m", query)))
+        .route("/aircraft/cessna172s/take-off", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_take_off("cessna172s", query)))
+        .route("/aircraft/cessna172s/landing", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_landing("cessna172s", query)))
+        .route("/aircraft/cessna182p/take-off", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_take_off("cessna182p", query)))
+        .route("/aircraft/cessna182p/landing", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_landing("cessna182p", query)))
+        .route("/aircraft/cessna182t/take-off", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_take_off("cessna182t", query)))
+        .route("/aircraft/cessna182t/landing", axum::routing::get(|query: Query<QueryPerformanceParameters>| pages::aircraft_pages::cessna_tables::get_for_landing("cessna182t", query)))
+        .route("/aircraft/pa28161/take-off", axum::routing::get(pages::aircraft_pages::pa28161::get_for_take_off))
+        .route("/aircraft/pa28161/landing", axum::routing::get(pages::aircraft_pages::pa28161::get_for_landing))
+        .route("/aircraft/:aircraft_type/speeds", axum::routing::get(pages::aircraft_pages::get_speeds))
+        .route("/performance.html", axum::routing::get(pages::performance::get))
+        .route("/runway", axum::routing::post(pages::runway::post))
+        .route("/runway", axum::routing::get(pages::runway::get))
+        .route("/runway.pdf", axum::routing::get(pages::runway::get_pdf))
+        .route("/plan", axum::routing::post(pages::flight_plan::post))
+        .route("/card", axum::routing::get(pages::card::get))
+        .route("/card.csv", axum::routing::get(pages::card::get_csv))
+        .route("/fleet", axum::routing::get(pages::profiles::get_fleet))
+        .route("/fleet", axum::routing::post(pages::profiles::post_fleet))
+        .route("/fleet/delete", axum::routing::post(pages::profiles::delete_fleet))
+        .route("/pilots", axum::routing::get(pages::profiles::get_pilots))
+        .route("/pilots", axum::routing::post(pages::profiles::post_pilots))
+        .route("/pilots/delete", axum::routing::post(pages::profiles::delete_pilots))
+        .route("/history", axum::routing::get(pages::history::get))
+        .route("/history/:id", axum::routing::get(pages::history::get_entry))
+}
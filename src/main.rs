@@ -1,26 +1,32 @@
-#![allow(dead_code)]
-
-mod data;
-mod pages;
-mod math;
-
-use axum::Router;
-use tower_http::services::ServeDir;
+use aircraft_performance::{cli, config::{self, Config}, router};
 
 #[tokio::main]
 async fn main() {
-    let app = Router::new()
-        .nest_service("/", ServeDir::new("static"))
-        .route("/airport", axum::routing::post(pages::airport::post))
-        .route("/airport/:identifier/departure/:aircraft_type", axum::routing::get(pages::airport::get_departure))
-        .route("/airport/:identifier/arrival/:aircraft_type", axum::routing::get(pages::airport::get_arrival))
-        .route("/aircraft/cessna150j/take-off", axum::routing::get(pages::aircraft_pages::cessna150j::get_for_take_off))
-        .route("/aircraft/cessna150j/landing", axum::routing::get(pages::aircraft_pages::cessna150j::get_for_landing))
-        .route("/aircraft/cessna172m/take-off", axum::routing::get(pages::aircraft_pages::cessna172m::get_for_take_off))
-        .route("/aircraft/cessna172m/landing", axum::routing::get(pages::aircraft_pages::cessna172m::get_for_landing))
-        .route("/runway", axum::routing::post(pages::runway::post))
-        .route("/runway", axum::routing::get(pages::runway::get));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (config, args) = match Config::load(&args) {
+        Ok(loaded) => loaded,
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for error in errors {
+                eprintln!("  {error}");
+            }
+            std::process::exit(1);
+        }
+    };
+    config::init(config.clone());
+
+    if !args.is_empty() {
+        if let Err(message) = cli::run(&args) {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3030").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
-}
\ No newline at end of file
+    let listener = tokio::net::TcpListener::bind(&config.bind_address).await
+        .unwrap_or_else(|err| {
+            eprintln!("Unable to listen on {}: {err}", config.bind_address);
+            std::process::exit(1);
+        });
+    axum::serve(listener, router(config)).await.unwrap();
+}
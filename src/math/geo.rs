@@ -0,0 +1,53 @@
+use super::Length;
+
+const EARTH_MEAN_RADIUS_M: f64 = 6_371_008.8;
+
+/// A point on the earth in decimal degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64
+}
+
+impl Coordinates {
+    /// The initial great circle bearing in degrees true from here to the other point.
+    pub fn bearing_to(&self, other: &Coordinates) -> f64 {
+        let (latitude_1, latitude_2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let longitude_difference = (other.longitude - self.longitude).to_radians();
+
+        let y = longitude_difference.sin() * latitude_2.cos();
+        let x = latitude_1.cos() * latitude_2.sin() - latitude_1.sin() * latitude_2.cos() * longitude_difference.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    /// The great circle distance by the haversine formula, within a few feet over a runway.
+    pub fn distance_to(&self, other: &Coordinates) -> Length {
+        let (latitude_1, latitude_2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let latitude_difference = latitude_2 - latitude_1;
+        let longitude_difference = (other.longitude - self.longitude).to_radians();
+
+        let a = (latitude_difference / 2.0).sin().powi(2) + latitude_1.cos() * latitude_2.cos() * (longitude_difference / 2.0).sin().powi(2);
+        Length::Metres(2.0 * EARTH_MEAN_RADIUS_M * a.sqrt().asin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearing_follows_the_great_circle() {
+        let origin = Coordinates { latitude: 45.0, longitude: -93.0 };
+        assert!((origin.bearing_to(&Coordinates { latitude: 45.1, longitude: -93.0 }) - 0.0).abs() < 0.01);
+        assert!((origin.bearing_to(&Coordinates { latitude: 44.9, longitude: -93.0 }) - 180.0).abs() < 0.01);
+        assert!((origin.bearing_to(&Coordinates { latitude: 45.0, longitude: -92.9 }) - 90.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn distance_matches_the_runway_length() {
+        // KANE 18/36 is 4855' between thresholds.
+        let le = Coordinates { latitude: 45.15169906616211, longitude: -93.20989990234375 };
+        let he = Coordinates { latitude: 45.13840103149414, longitude: -93.21009826660156 };
+        assert!((le.distance_to(&he).feet() - 4855.0).abs() < 50.0, "{}", le.distance_to(&he).feet());
+    }
+}
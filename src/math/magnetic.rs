@@ -0,0 +1,168 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+
+use super::Length;
+
+/// The World Magnetic Model coefficients as published by NOAA, valid from 2025.0 to 2030.0.
+const WMM_COF: &str = include_str!("wmm2025.cof");
+const MAX_DEGREE: usize = 12;
+const REFERENCE_RADIUS_KM: f64 = 6371.2;
+const WGS84_SEMI_MAJOR_AXIS_KM: f64 = 6378.137;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+const SECONDS_PER_YEAR: f64 = 31_556_952.0;
+
+/// The Gauss coefficients g and h in nT and their yearly secular variation, indexed by degree n and order m.
+struct Model {
+    epoch: f64,
+    g: [[f64; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+    h: [[f64; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+    g_dot: [[f64; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+    h_dot: [[f64; MAX_DEGREE + 1]; MAX_DEGREE + 1]
+}
+
+lazy_static! {
+    static ref MODEL: Model = load_model();
+}
+
+fn load_model() -> Model {
+    let mut lines = WMM_COF.lines();
+    let epoch = lines.next()
+        .and_then(|header| header.split_whitespace().next())
+        .and_then(|epoch| epoch.parse().ok())
+        .expect("To read the WMM epoch");
+
+    let mut model = Model {
+        epoch,
+        g: [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+        h: [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+        g_dot: [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1],
+        h_dot: [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1]
+    };
+
+    for line in lines.take_while(|line| !line.starts_with("9999")) {
+        let values: Vec<f64> = line.split_whitespace().map(|value| value.parse().expect("To read a WMM coefficient")).collect();
+        let (n, m) = (values[0] as usize, values[1] as usize);
+        model.g[n][m] = values[2];
+        model.h[n][m] = values[3];
+        model.g_dot[n][m] = values[4];
+        model.h_dot[n][m] = values[5];
+    }
+
+    model
+}
+
+/// The current date as a decimal year, which is what the model's secular variation is given per.
+pub fn current_year() -> f64 {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs_f64()).unwrap_or_default();
+    1970.0 + seconds / SECONDS_PER_YEAR
+}
+
+/// The magnetic variation (declination) in degrees at a point on the WGS84 ellipsoid, east positive.
+/// Add it to a magnetic heading to get the true heading.
+pub fn declination(latitude: f64, longitude: f64, altitude: Length, year: f64) -> f64 {
+    let model = &*MODEL;
+    let years = year - model.epoch;
+
+    // Geodetic to geocentric spherical coordinates.
+    let latitude_rad = latitude.clamp(-89.999, 89.999).to_radians();
+    let longitude_rad = longitude.to_radians();
+    let altitude_km = altitude.feet() * 0.0003048;
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let prime_vertical_km = WGS84_SEMI_MAJOR_AXIS_KM / (1.0 - e2 * latitude_rad.sin().powi(2)).sqrt();
+    let p = (prime_vertical_km + altitude_km) * latitude_rad.cos();
+    let z = (prime_vertical_km * (1.0 - e2) + altitude_km) * latitude_rad.sin();
+    let r = (p * p + z * z).sqrt();
+    let geocentric_latitude_rad = (z / r).asin();
+
+    // Schmidt semi-normalised associated Legendre functions of the colatitude and their derivatives.
+    let cos_theta = geocentric_latitude_rad.sin();
+    let sin_theta = geocentric_latitude_rad.cos();
+    let mut legendre = [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1];
+    let mut legendre_derivative = [[0.0; MAX_DEGREE + 1]; MAX_DEGREE + 1];
+    legendre[0][0] = 1.0;
+    for n in 1..=MAX_DEGREE {
+        let nf = n as f64;
+        for m in 0..=n {
+            let mf = m as f64;
+            legendre[n][m] = if n == m {
+                let scale = if n == 1 { 1.0 } else { ((2.0 * nf - 1.0) / (2.0 * nf)).sqrt() };
+                scale * sin_theta * legendre[n - 1][m - 1]
+            } else {
+                let previous = if n >= 2 { legendre[n - 2][m] } else { 0.0 };
+                ((2.0 * nf - 1.0) * cos_theta * legendre[n - 1][m] - ((nf - 1.0).powi(2) - mf * mf).max(0.0).sqrt() * previous) / (nf * nf - mf * mf).sqrt()
+            };
+            let lower = if m < n { legendre[n - 1][m] } else { 0.0 };
+            legendre_derivative[n][m] = (nf * cos_theta * legendre[n][m] - (nf * nf - mf * mf).sqrt() * lower) / sin_theta;
+        }
+    }
+
+    // Field components in the geocentric frame, north, east and down.
+    let (mut north, mut east, mut down) = (0.0, 0.0, 0.0);
+    for n in 1..=MAX_DEGREE {
+        let radius_ratio = (REFERENCE_RADIUS_KM / r).powi(n as i32 + 2);
+        for m in 0..=n {
+            let g = model.g[n][m] + years * model.g_dot[n][m];
+            let h = model.h[n][m] + years * model.h_dot[n][m];
+            let (sin_m_longitude, cos_m_longitude) = (m as f64 * longitude_rad).sin_cos();
+
+            north += radius_ratio * (g * cos_m_longitude + h * sin_m_longitude) * legendre_derivative[n][m];
+            east += radius_ratio * m as f64 * (g * sin_m_longitude - h * cos_m_longitude) * legendre[n][m] / sin_theta;
+            down -= radius_ratio * (n as f64 + 1.0) * (g * cos_m_longitude + h * sin_m_longitude) * legendre[n][m];
+        }
+    }
+
+    // Rotate north back to the geodetic frame, east is unchanged.
+    let tilt = geocentric_latitude_rad - latitude_rad;
+    let north = north * tilt.cos() - down * tilt.sin();
+
+    east.atan2(north).to_degrees()
+}
+
+/// A heading in whole degrees as a compass shows it, 360 rather than 0 for north.
+pub fn normalize_heading(heading: f64) -> u16 {
+    match heading.round().rem_euclid(360.0) as u16 {
+        0 => 360,
+        heading => heading
+    }
+}
+
+/// The variation as charts print it, e.g. 12°E or 3°W.
+pub fn format_variation(variation: f64) -> String {
+    match variation.round() {
+        degrees if degrees > 0.0 => format!("{degrees:.0}°E"),
+        degrees if degrees < 0.0 => format!("{:.0}°W", degrees.abs()),
+        _ => String::from("0°")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declination_matches_published_values() {
+        // Values from the NOAA calculator for WMM2025 at 2025.0, sea level.
+        let cases = [
+            ("Minneapolis", 45.145, -93.211, -0.4),
+            ("Seattle", 47.45, -122.31, 15.0),
+            ("New York", 40.64, -73.78, -12.7),
+            ("London", 51.47, -0.45, 1.0),
+            ("Sydney", -33.95, 151.18, 12.8)
+        ];
+
+        for (place, latitude, longitude, expected) in cases {
+            let actual = declination(latitude, longitude, Length::Feet(0.0), 2025.0);
+            assert!((actual - expected).abs() < 0.5, "{place}: {actual:.2} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn headings_are_compass_headings() {
+        assert_eq!(normalize_heading(0.2), 360);
+        assert_eq!(normalize_heading(-12.0), 348);
+        assert_eq!(normalize_heading(365.4), 5);
+        assert_eq!(format_variation(-12.6), "13°W");
+        assert_eq!(format_variation(0.3), "0°");
+    }
+}
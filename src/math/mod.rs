@@ -1,81 +1,188 @@
+use serde::{Deserialize, Serialize};
+
 use crate::data::performance::distance::Distance;
 
+pub mod geo;
+pub mod magnetic;
+pub mod units;
 pub mod wind;
 
+const MILES_PER_HOUR_PER_KNOT: f64 = 1.150779;
+const METRES_PER_FOOT: f64 = 0.3048;
+const HECTOPASCALS_PER_IN_HG: f64 = 33.8639;
+const KILOGRAMS_PER_POUND: f64 = 0.45359237;
+const STANDARD_PRESSURE_IN_HG: f64 = 29.92;
+const STANDARD_PRESSURE_HPA: f64 = 1013.25;
+const STANDARD_TEMPERATURE_K: f64 = 288.15;
+const STANDARD_LAPSE_RATE_K_PER_M: f64 = 0.0065;
+/// R × L / (g × M) for dry air, the exponent of the troposphere's pressure/height relation.
+const STANDARD_PRESSURE_EXPONENT: f64 = 0.190263;
+
 #[derive(Debug, Clone, Copy)]
 pub enum Velocity {
-    Knots(i16),
-    MilesPerHour(i16)
+    Knots(f64),
+    MilesPerHour(f64)
 }
 
 impl Velocity {
-    pub fn knots(self) -> i16 {
+    pub fn knots(self) -> f64 {
         match self {
             Velocity::Knots(k) => k,
-            Velocity::MilesPerHour(mph) => (mph as f64 / 1.151).round() as i16,
+            Velocity::MilesPerHour(mph) => mph / MILES_PER_HOUR_PER_KNOT
         }
     }
 
-    pub fn miles_per_hour(self) -> i16 {
+    pub fn miles_per_hour(self) -> f64 {
         match self {
-            Velocity::Knots(k) => (k as f64 * 1.151).round() as i16,
+            Velocity::Knots(k) => k * MILES_PER_HOUR_PER_KNOT,
             Velocity::MilesPerHour(mph) => mph
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum Length {
+    Feet(f64),
+    Metres(f64)
+}
+
+impl Length {
+    pub fn feet(self) -> f64 {
+        match self {
+            Length::Feet(ft) => ft,
+            Length::Metres(m) => m / METRES_PER_FOOT
+        }
+    }
+
+    pub fn metres(self) -> f64 {
+        match self {
+            Length::Feet(ft) => ft * METRES_PER_FOOT,
+            Length::Metres(m) => m
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Weight {
+    Pounds(f64),
+    Kilograms(f64)
+}
+
+impl Weight {
+    pub fn pounds(self) -> f64 {
+        match self {
+            Weight::Pounds(lbs) => lbs,
+            Weight::Kilograms(kg) => kg / KILOGRAMS_PER_POUND
+        }
+    }
+
+    pub fn kilograms(self) -> f64 {
+        match self {
+            Weight::Pounds(lbs) => lbs * KILOGRAMS_PER_POUND,
+            Weight::Kilograms(kg) => kg
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Temperature {
-    Fahrenheit(i16),
-    Celsius(i16)
+    Fahrenheit(f64),
+    Celsius(f64)
 }
 
 impl Temperature {
-    pub fn celsius(self) -> i16 {
+    pub fn celsius(self) -> f64 {
         match self {
             Temperature::Celsius(c) => c,
-            Temperature::Fahrenheit(f) => ((f as f64 - 32.0) * 5.0 / 9.0).round() as i16
+            Temperature::Fahrenheit(f) => (f - 32.0) * 5.0 / 9.0
         }
     }
 
-    pub fn fahrenheit(self) -> i16 {
+    pub fn fahrenheit(self) -> f64 {
         match self {
-            Temperature::Celsius(c) => ((c as f64 * 9.0 / 5.0) + 32.0).round() as i16,
+            Temperature::Celsius(c) => c * 9.0 / 5.0 + 32.0,
             Temperature::Fahrenheit(f) => f
         }
     }
 
-    pub fn standard_temperature(elevation_ft: i16) -> Temperature {
-        Temperature::Fahrenheit((59.0 - ((elevation_ft as f64 / 1000.0) * 3.5)) as i16)
+    pub fn standard_temperature(elevation: Length) -> Temperature {
+        Temperature::Fahrenheit(59.0 - elevation.feet() / 1000.0 * 3.5)
     }
 }
 
-const SANDARD_PRESSURE_IN_HG: f32 = 29.92;
+/// How pressure altitude is worked out from the altimeter setting and the field elevation.
+///
+/// The standard atmosphere is the default as it is exact for any setting and elevation. The rule of thumb is kept for
+/// checking against a hand calculation, it overstates the correction by around 10% as an inch of mercury is nearer 920'
+/// at sea level and less at altitude.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureAltitudeMethod {
+    /// 1000' for each inch of mercury the setting is below 29.92".
+    RuleOfThumb,
+    /// The ISA altimeter equation applied to the station pressure the setting implies at the field elevation.
+    #[default]
+    StandardAtmosphere
+}
+
+impl PressureAltitudeMethod {
+    /// The value as it appears in a query string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PressureAltitudeMethod::RuleOfThumb => "rule_of_thumb",
+            PressureAltitudeMethod::StandardAtmosphere => "standard_atmosphere"
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            PressureAltitudeMethod::RuleOfThumb => "rule of thumb (1000' per inHg)",
+            PressureAltitudeMethod::StandardAtmosphere => "standard atmosphere"
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum Pressure {
-    InchesOfMercury(f32)
+    InchesOfMercury(f64),
+    Hectopascals(f64)
 }
 
 impl Pressure {
-    pub fn in_hg(self) -> f32 {
+    pub fn in_hg(self) -> f64 {
         match self {
-            Pressure::InchesOfMercury(p) => p
+            Pressure::InchesOfMercury(p) => p,
+            Pressure::Hectopascals(p) => p / HECTOPASCALS_PER_IN_HG
         }
     }
 
-    pub fn altitude(self, elevation_ft: i16) -> i16 {
+    pub fn hpa(self) -> f64 {
         match self {
-            Pressure::InchesOfMercury(p) => ((SANDARD_PRESSURE_IN_HG - p) * 1000.0 + elevation_ft as f32).round() as i16
-        }   
+            Pressure::InchesOfMercury(p) => p * HECTOPASCALS_PER_IN_HG,
+            Pressure::Hectopascals(p) => p
+        }
+    }
+
+    pub fn altitude(self, elevation: Length, method: PressureAltitudeMethod) -> Length {
+        match method {
+            PressureAltitudeMethod::RuleOfThumb => Length::Feet((STANDARD_PRESSURE_IN_HG - self.in_hg()) * 1000.0 + elevation.feet()),
+            PressureAltitudeMethod::StandardAtmosphere => {
+                // The setting reduces the station pressure to sea level along the standard lapse, so undo that reduction then read
+                // the station pressure against the standard datum: h = T0 / L × (1 - (QNH / P0)^n × (1 - L × elevation / T0)).
+                let ratio = (self.hpa() / STANDARD_PRESSURE_HPA).powf(STANDARD_PRESSURE_EXPONENT);
+                let station_ratio = ratio * (1.0 - STANDARD_LAPSE_RATE_K_PER_M * elevation.metres() / STANDARD_TEMPERATURE_K);
+                Length::Metres(STANDARD_TEMPERATURE_K / STANDARD_LAPSE_RATE_K_PER_M * (1.0 - station_ratio))
+            }
+        }
     }
 
     pub fn from_metar(metar: metar::Metar) -> Result<Pressure, &'static str> {
         match metar.pressure {
             metar::Data::Known(k) => {
                 match k {
-                    metar::Pressure::InchesOfMercury(p) => Ok(Pressure::InchesOfMercury(p)),
-                    metar::Pressure::Hectopascals(p) => Ok(Pressure::InchesOfMercury(p as f32 / 33.864f32))
+                    // Altimeter settings are reported to the hundredth, drop the noise from the f32 the METAR was parsed into.
+                    metar::Pressure::InchesOfMercury(p) => Ok(Pressure::InchesOfMercury((p as f64 * 100.0).round() / 100.0)),
+                    metar::Pressure::Hectopascals(p) => Ok(Pressure::Hectopascals(p as f64))
                 }
             },
             metar::Data::Unknown => Err("Pressure not available.")
@@ -101,7 +208,7 @@ impl FloatingCalcs for f64 {
         let actual_no_offset = self - lower_bound;
         match diff {
             0.0 => 0.0,
-            _ => actual_no_offset as f64 / diff
+            _ => actual_no_offset / diff
         }
     }
 
@@ -119,13 +226,94 @@ impl FloatingCalcs for f64 {
     }
 
     fn percent_velocity(&self, lower_bound: Velocity, upper_bound: Velocity) -> f64 {
-        self.percent(lower_bound.knots() as f64, upper_bound.knots() as f64)
+        self.percent(lower_bound.knots(), upper_bound.knots())
     }
 
     fn percent_of_distance(&self, lower_bound: Distance, upper_bound: Distance) -> Distance {
-        Distance::new_from_f64(
-            self.percent_of_i16(lower_bound.ground_run(), upper_bound.ground_run()),
-            self.percent_of_i16(lower_bound.clear_50_ft_obstacle(), upper_bound.clear_50_ft_obstacle())
+        Distance(
+            self.percent_of(lower_bound.ground_run_ft(), upper_bound.ground_run_ft()),
+            self.percent_of(lower_bound.clear_50_ft_obstacle_ft(), upper_bound.clear_50_ft_obstacle_ft())
         )
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn temperature_conversions_round_trip_without_rounding() {
+        assert_close(Temperature::Celsius(22.0).fahrenheit(), 71.6);
+        assert_close(Temperature::Fahrenheit(71.6).celsius(), 22.0);
+        assert_close(Temperature::Fahrenheit(Temperature::Celsius(-7.0).fahrenheit()).celsius(), -7.0);
+    }
+
+    #[test]
+    fn standard_temperature_is_not_truncated() {
+        assert_close(Temperature::standard_temperature(Length::Feet(905.0)).fahrenheit(), 55.8325);
+        assert_close(Temperature::standard_temperature(Length::Metres(0.0)).fahrenheit(), 59.0);
+    }
+
+    #[test]
+    fn velocity_and_length_conversions_round_trip() {
+        assert_close(Velocity::MilesPerHour(Velocity::Knots(13.0).miles_per_hour()).knots(), 13.0);
+        assert_close(Length::Metres(Length::Feet(4001.0).metres()).feet(), 4001.0);
+        assert_close(Length::Feet(1000.0).metres(), 304.8);
+        assert_close(Weight::Kilograms(Weight::Pounds(2300.0).kilograms()).pounds(), 2300.0);
+    }
+
+    #[test]
+    fn pressure_units_agree() {
+        assert_close(Pressure::InchesOfMercury(29.92).hpa(), 29.92 * 33.8639);
+        assert_close(Pressure::Hectopascals(Pressure::InchesOfMercury(30.12).hpa()).in_hg(), 30.12);
+    }
+
+    #[test]
+    fn rule_of_thumb_is_1000_ft_per_in_hg() {
+        let method = PressureAltitudeMethod::RuleOfThumb;
+        assert_close(Pressure::InchesOfMercury(29.92).altitude(Length::Feet(2000.0), method).feet(), 2000.0);
+        assert!((Pressure::InchesOfMercury(30.12).altitude(Length::Feet(1000.0), method).feet() - 800.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn standard_atmosphere_matches_the_isa_table() {
+        let method = PressureAltitudeMethod::StandardAtmosphere;
+        // At the standard setting the pressure altitude is the elevation, at any elevation.
+        for elevation_ft in [0.0, 1000.0, 5000.0, 9000.0] {
+            assert!((Pressure::Hectopascals(1013.25).altitude(Length::Feet(elevation_ft), method).feet() - elevation_ft).abs() < 1e-6);
+        }
+        // ISA: 1000 hPa is 364' and 29.92" is 1' above the 1013.25 hPa datum.
+        assert!((Pressure::Hectopascals(1000.0).altitude(Length::Feet(0.0), method).feet() - 364.0).abs() < 1.0);
+        assert!((Pressure::InchesOfMercury(29.92).altitude(Length::Feet(0.0), method).feet() - 1.1).abs() < 0.1);
+        // Both units of the same setting give the same answer.
+        assert_close(
+            Pressure::InchesOfMercury(30.12).altitude(Length::Feet(4001.0), method).feet(),
+            Pressure::Hectopascals(30.12 * 33.8639).altitude(Length::Feet(4001.0), method).feet()
+        );
+    }
+
+    #[test]
+    fn rule_of_thumb_overstates_the_correction() {
+        for (in_hg, elevation_ft) in [(28.92, 0.0), (30.92, 0.0), (29.42, 5000.0), (30.42, 8000.0)] {
+            let exact = Pressure::InchesOfMercury(in_hg).altitude(Length::Feet(elevation_ft), PressureAltitudeMethod::StandardAtmosphere).feet() - elevation_ft;
+            let rule_of_thumb = Pressure::InchesOfMercury(in_hg).altitude(Length::Feet(elevation_ft), PressureAltitudeMethod::RuleOfThumb).feet() - elevation_ft;
+            let ratio = exact / rule_of_thumb;
+            assert!((0.85..0.95).contains(&ratio), "{in_hg}\" at {elevation_ft}': {exact} vs {rule_of_thumb}");
+        }
+    }
+
+    #[test]
+    fn unchosen_units_are_the_given_defaults() {
+        let defaults = units::Units { pressure_unit: units::PressureUnit::Hpa, ..units::Units::IMPERIAL };
+        let choices: units::UnitChoices = serde_urlencoded::from_str("length_unit=metres").unwrap();
+        let resolved = choices.or(defaults);
+        assert_eq!(resolved.pressure_unit, units::PressureUnit::Hpa);
+        assert_eq!(resolved.length_unit, units::LengthUnit::Metres);
+        assert_eq!(resolved.temperature_unit, units::TemperatureUnit::Fahrenheit);
+        // Only the choice made is carried on through forms.
+        assert_eq!(choices.hidden_fields(), vec![(String::from("length_unit"), String::from("metres"))]);
+    }
+}
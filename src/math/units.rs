@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Length, Pressure, Temperature, Weight, METRES_PER_FOOT};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureUnit {
+    #[default]
+    InHg,
+    Hpa
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    #[default]
+    Feet,
+    Metres
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Fahrenheit,
+    Celsius
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightUnit {
+    #[default]
+    Pounds,
+    Kilograms
+}
+
+/// The units results are displayed in. Calculations are always made in the units of the charts, this only affects presentation.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Units {
+    pub pressure_unit: PressureUnit,
+    pub length_unit: LengthUnit,
+    pub temperature_unit: TemperatureUnit,
+    pub weight_unit: WeightUnit
+}
+
+/// The units chosen on a form, any left out are filled in from the defaults the caller passes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct UnitChoices {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_unit: Option<PressureUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length_unit: Option<LengthUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_unit: Option<TemperatureUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight_unit: Option<WeightUnit>
+}
+
+impl UnitChoices {
+    pub fn or(self, defaults: Units) -> Units {
+        Units {
+            pressure_unit: self.pressure_unit.unwrap_or(defaults.pressure_unit),
+            length_unit: self.length_unit.unwrap_or(defaults.length_unit),
+            temperature_unit: self.temperature_unit.unwrap_or(defaults.temperature_unit),
+            weight_unit: self.weight_unit.unwrap_or(defaults.weight_unit)
+        }
+    }
+
+    /// The choices as name and value pairs for carrying them through forms as hidden inputs, empty when none were made.
+    pub fn hidden_fields(self) -> Vec<(String, String)> {
+        let query = serde_urlencoded::to_string(self).unwrap_or_default();
+        serde_urlencoded::from_str(&query).unwrap_or_default()
+    }
+}
+
+impl Units {
+    pub const IMPERIAL: Units = Units {
+        pressure_unit: PressureUnit::InHg,
+        length_unit: LengthUnit::Feet,
+        temperature_unit: TemperatureUnit::Fahrenheit,
+        weight_unit: WeightUnit::Pounds
+    };
+
+    pub fn pressure(&self, pressure: Pressure) -> String {
+        match self.pressure_unit {
+            PressureUnit::InHg => format!("{:.2}\"", pressure.in_hg()),
+            PressureUnit::Hpa => format!("{:.0} hPa", pressure.hpa())
+        }
+    }
+
+    pub fn length(&self, length: Length) -> String {
+        match self.length_unit {
+            LengthUnit::Feet => format!("{:.0}'", length.feet()),
+            LengthUnit::Metres => format!("{:.0} m", length.metres())
+        }
+    }
+
+    pub fn feet(&self, feet: impl Into<f64>) -> String {
+        self.length(Length::Feet(feet.into()))
+    }
+
+    pub fn temperature(&self, temperature: Temperature) -> String {
+        match self.temperature_unit {
+            TemperatureUnit::Fahrenheit => format!("{:.0}°F", temperature.fahrenheit()),
+            TemperatureUnit::Celsius => format!("{:.0}°C", temperature.celsius())
+        }
+    }
+
+    /// A difference between two temperatures, which scales without the offset of the freezing point.
+    pub fn temperature_difference_f(&self, difference_f: f64) -> String {
+        match self.temperature_unit {
+            TemperatureUnit::Fahrenheit => format!("{difference_f:.0}°F"),
+            TemperatureUnit::Celsius => format!("{:.0}°C", difference_f * 5.0 / 9.0)
+        }
+    }
+
+    /// The standard lapse rate of 3.5°F per 1000', restated per 1000 of the display length unit.
+    pub fn standard_lapse_rate(&self) -> String {
+        let per_1000_f = match self.length_unit {
+            LengthUnit::Feet => 3.5,
+            LengthUnit::Metres => 3.5 / METRES_PER_FOOT
+        };
+
+        match self.temperature_unit {
+            TemperatureUnit::Fahrenheit => format!("{per_1000_f:.1}°F"),
+            TemperatureUnit::Celsius => format!("{:.1}°C", per_1000_f * 5.0 / 9.0)
+        }
+    }
+
+    pub fn standard_lapse_interval(&self) -> String {
+        match self.length_unit {
+            LengthUnit::Feet => self.length(Length::Feet(1000.0)),
+            LengthUnit::Metres => self.length(Length::Metres(1000.0))
+        }
+    }
+
+    pub fn weight(&self, weight: Weight) -> String {
+        match self.weight_unit {
+            WeightUnit::Pounds => format!("{:.0} lbs", weight.pounds()),
+            WeightUnit::Kilograms => format!("{:.0} kg", weight.kilograms())
+        }
+    }
+
+    pub fn pounds(&self, pounds: impl Into<f64>) -> String {
+        self.weight(Weight::Pounds(pounds.into()))
+    }
+}
+
+/// Weights are entered in pounds or kilograms and calculated in whole pounds, as the charts are. Like °C over °F, a metric entry takes precedence.
+pub fn whole_pounds(pounds: Option<i16>, kilograms: Option<f64>) -> Option<i16> {
+    kilograms.map(|kg| Weight::Kilograms(kg).pounds().round() as i16).or(pounds)
+}
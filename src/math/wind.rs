@@ -1,41 +1,88 @@
-use metar::{Wind, WindDirection, WindSpeed};
+use metar::{Data, Wind, WindDirection, WindSpeed};
 
 use super::Velocity;
 
-fn get_heading_and_speed(wind: &Wind) -> (i16, i16) {
-    let mut heading = 0;
-    let mut speed = 0;
-    if let WindDirection::Heading(h) = wind.dir.unwrap() {
-        heading = *h as i16;
-    }
+const METRES_PER_NAUTICAL_MILE: f64 = 1852.0;
 
-    if let WindSpeed::Knot(v) = wind.speed.unwrap() {
-        speed = *v as i16;
-    }
+/// The heading the wind is from and its speed in knots, no heading for a variable wind.
+fn get_heading_and_speed(wind: &Wind) -> Result<(Option<i16>, f64), &'static str> {
+    let speed_kts = match wind.speed {
+        Data::Known(WindSpeed::Calm) => return Ok((Some(0), 0.0)),
+        Data::Known(WindSpeed::Knot(kts)) => kts as f64,
+        Data::Known(WindSpeed::MetresPerSecond(mps)) => mps as f64 * 3600.0 / METRES_PER_NAUTICAL_MILE,
+        Data::Known(WindSpeed::KilometresPerHour(kph)) => kph as f64 * 1000.0 / METRES_PER_NAUTICAL_MILE,
+        Data::Unknown => return Err("Wind not available.")
+    };
 
-    (heading, speed)
+    match wind.dir {
+        Data::Known(WindDirection::Heading(heading)) => Ok((Some(heading as i16), speed_kts)),
+        Data::Known(WindDirection::Variable) => Ok((None, speed_kts)),
+        Data::Known(WindDirection::Above) => Err("Wind over the 99 kts a METAR reports."),
+        Data::Unknown => Err("Wind not available.")
+    }
 }
 
 fn find_difference_in_radians(wind_heading: i16, heading: i16) -> f64 {
     let result = (wind_heading - heading) as f64;
-    return result.to_radians()
+    result.to_radians()
 }
 
+/// Wind directions in a METAR are from true north, so the headings given must be true too.
+/// A variable wind may blow from any direction, so it is taken at full speed as both a tailwind and a crosswind.
 pub trait WindCalcs {
-    fn calc_crosswind_component(&self, heading: i16) -> i16;
-    fn calc_headwind_component_from_metar_wind_value(&self, aircraft_heading: i16) -> Velocity;
+    fn calc_crosswind_component(&self, heading: i16) -> Result<Velocity, &'static str>;
+    fn calc_headwind_component_from_metar_wind_value(&self, aircraft_heading: i16) -> Result<Velocity, &'static str>;
 }
 
 impl WindCalcs for Wind {
-    fn calc_crosswind_component(&self, heading: i16) -> i16 {
-        let (wind_heading, wind_speed) = get_heading_and_speed(self);
-        let rad = find_difference_in_radians(wind_heading, heading);
-        (wind_speed as f64 * rad.sin()).round() as i16
+    fn calc_crosswind_component(&self, heading: i16) -> Result<Velocity, &'static str> {
+        let crosswind_kts = match get_heading_and_speed(self)? {
+            (Some(wind_heading), wind_speed) => wind_speed * find_difference_in_radians(wind_heading, heading).sin(),
+            (None, wind_speed) => wind_speed
+        };
+        Ok(Velocity::Knots(crosswind_kts))
+    }
+
+    fn calc_headwind_component_from_metar_wind_value(&self, aircraft_heading: i16) -> Result<Velocity, &'static str> {
+        let headwind_kts = match get_heading_and_speed(self)? {
+            (Some(wind_heading), wind_speed) => wind_speed * find_difference_in_radians(wind_heading, aircraft_heading).cos(),
+            (None, wind_speed) => -wind_speed
+        };
+        Ok(Velocity::Knots(headwind_kts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components(raw_metar: &str, heading: i16) -> Result<(f64, f64), &'static str> {
+        let wind = metar::Metar::parse(raw_metar).unwrap().wind;
+        Ok((wind.calc_headwind_component_from_metar_wind_value(heading)?.knots(), wind.calc_crosswind_component(heading)?.knots()))
+    }
+
+    #[test]
+    fn components_are_resolved_against_the_heading() {
+        let (headwind_kts, crosswind_kts) = components("KANE 191153Z 27010KT 10SM CLR 18/06 A3002", 240).unwrap();
+        assert!((headwind_kts - 8.660).abs() < 0.001 && (crosswind_kts - 5.0).abs() < 0.001, "{headwind_kts} {crosswind_kts}");
+    }
+
+    #[test]
+    fn metric_speeds_are_converted_to_knots() {
+        assert!((components("EGLL 191150Z 36010MPS 9999 FEW030 12/08 Q1013", 360).unwrap().0 - 19.438).abs() < 0.001);
+        assert!((components("UUEE 191200Z 36037KPH 9999 FEW030 12/08 Q1013", 360).unwrap().0 - 19.978).abs() < 0.001);
+    }
+
+    #[test]
+    fn a_variable_wind_is_a_full_tailwind_and_crosswind() {
+        assert_eq!(components("KANE 191153Z VRB05KT 10SM CLR 18/06 A3002", 90), Ok((-5.0, 5.0)));
     }
 
-    fn calc_headwind_component_from_metar_wind_value(&self, aircraft_heading: i16) -> Velocity {
-        let (wind_heading, wind_speed) = get_heading_and_speed(self);
-        let rad = find_difference_in_radians(wind_heading, aircraft_heading);
-        Velocity::Knots((wind_speed as f64 * rad.cos()).round() as i16)
+    #[test]
+    fn calm_is_no_wind_and_a_missing_wind_is_an_error() {
+        assert_eq!(components("KANE 191153Z 00000KT 10SM CLR 18/06 A3002", 90).map(|(headwind_kts, _)| headwind_kts.abs()), Ok(0.0));
+        assert_eq!(components("KANE 191153Z CALM 10SM CLR 18/06 A3002", 90), Ok((0.0, 0.0)));
+        assert_eq!(components("KANE 191153Z /////KT 10SM CLR 18/06 A3002", 90), Err("Wind not available."));
+        assert_eq!(components("KANE 191153Z 10SM CLR 18/06 A3002", 90), Err("Wind not available."));
     }
 }
\ No newline at end of file
@@ -2,11 +2,12 @@ use askama::Template;
 use axum::{extract::Query, response::{Html, IntoResponse, Response}};
 
 use crate::{
-    data::performance::aircraft::cessna150j::{Cessna150J, Landing, TakeOff},
-    pages::{ErrorTemplate, ToPageTemplate}
+    data::performance::{aircraft::cessna150j::{Cessna150J, Corrections, Landing, TakeOff}, climb::Climb, distance::Distance, error::PerformanceError},
+    pages::{ErrorTemplate, ToPageTemplate},
+    pdf::Document
 };
 
-use super::{PerformanceParameters, QueryPerformanceParameters};
+use super::{format_distance, write_pdf_warnings, write_performance_table, PerformanceParameters, QueryPerformanceParameters};
 
 #[derive(Template)]
 #[template(path = "partials/aircraft/cessna150j/take-off.html")]
@@ -17,46 +18,126 @@ pub struct TakeOffTemplate {
     cessna: Cessna150J
 }
 
-fn get_tailwind_error_response(parameters: &PerformanceParameters) -> Response {
-    let tailwind = parameters.headwind.knots().abs();
-    let template = ErrorTemplate::new(format!("Tailind of {tailwind} kts detected. Unable to compute."));
-    Html(template.render().unwrap()).into_response()
+fn try_new_cessna(parameters: &PerformanceParameters) -> Result<Cessna150J, PerformanceError> {
+    Cessna150J::try_new(parameters.headwind, parameters.temperature, parameters.elevation, parameters.standard_temperature)
 }
 
-fn get_raw_html<T>(parameters: &PerformanceParameters, callback: &dyn Fn(Cessna150J) -> T) -> String where T : Template {
-    let cessna = Cessna150J::new(parameters.headwind, parameters.temperature.fahrenheit(), parameters.elevation_ft, parameters.standard_temperature.fahrenheit());
+pub fn calc_distance_for_take_off(parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    Ok(cessna.try_calc_take_off()?.correction.final_distance(parameters.is_grass))
+}
 
-    let template = callback(cessna);
-    template.render().unwrap()
+pub fn calc_distance_for_landing(parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    Ok(cessna.calc_landing().correction.final_distance(parameters.is_grass))
 }
 
-pub fn get_raw_html_for_take_off(parameters: &PerformanceParameters, start_landing_flow: bool) -> String {
-    get_raw_html(parameters, &|cessna| {
-        let calcs = cessna.calc_take_off(); 
-        TakeOffTemplate {
-            start_landing_flow,
-            is_grass: parameters.is_grass,
-            calcs,
-            cessna
-        }
-    })
+pub fn calc_climb(parameters: &PerformanceParameters) -> Result<Climb, PerformanceError> {
+    try_new_cessna(parameters)?.calc_climb()
 }
 
-pub async fn get_for_take_off(query_parameters: Query<QueryPerformanceParameters>) -> Response {
-    let parameters = query_parameters.to_performance_parameters();
-    if parameters.headwind.knots() < 0 {
-        get_tailwind_error_response(&parameters)
-    } else {
-        let raw_html = get_raw_html_for_take_off(&parameters, false);
-        let page = ToPageTemplate {
-            page_title:String::from("Cessna 150 J Take Off Performance"),
-            raw_html
-        };
-
-        Html(page.render().unwrap()).into_response()
+fn write_pdf_elevation(document: &mut Document, cessna: &Cessna150J) {
+    document.text(format!("The airport elevation of {:.0}' is {:.0}% of the way between {}' and {}'.",
+        cessna.elevation_ft,
+        cessna.altitude_tween_percentage * 100.0,
+        cessna.atmosphere_bounds.lower.altitude,
+        cessna.atmosphere_bounds.upper.altitude));
+}
+
+fn write_pdf_temperature_correction(document: &mut Document, cessna: &Cessna150J, interval: i16, calcs: &Corrections) {
+    document.text(format!("Increase distances 10% for each {interval}°F above standard: {:.0}°F - {:.0}°F = {:.0}°F, {:.2}% -> {}",
+        cessna.temperature_f,
+        cessna.standard_temperature_f,
+        cessna.temperature_f_diff_from_standard,
+        calcs.standard_temperature_correction_percentage * 100.0,
+        format_distance(calcs.distance_corrected_for_temperature)));
+}
+
+fn write_pdf_grass_correction(document: &mut Document, is_grass: bool, percentage: i16, calcs: &Corrections) {
+    if is_grass {
+        document.text(format!("Dry grass, increase distances by {percentage}% of the total to clear 50' obstacle: +{:.0}' -> {}",
+            calcs.grass_offset,
+            format_distance(calcs.distance_corrected_for_grass)));
     }
 }
 
+pub fn write_pdf_for_take_off(parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    let calcs = cessna.try_calc_take_off()?;
+
+    document.heading("Chart Interpolation");
+    write_pdf_elevation(document, &cessna);
+    document.text(format!("The headwind of {:.0} kts is {:.0}% between {} kts and {} kts.",
+        cessna.headwind_kts,
+        calcs.headwind_tween_percentage * 100.0,
+        calcs.headwinds.lower_value.knots(),
+        calcs.headwinds.upper_value.knots()));
+    write_performance_table(document, "Head Wind", " kts", [
+        format!("{}' at {}°F", cessna.atmosphere_bounds.lower.altitude, cessna.atmosphere_bounds.lower.temperature),
+        format!("{:.0}' at {:.0}°F", cessna.elevation_ft, cessna.standard_temperature_f),
+        format!("{}' at {}°F", cessna.atmosphere_bounds.upper.altitude, cessna.atmosphere_bounds.upper.temperature)
+    ], &calcs.takeoff_distances);
+    write_pdf_warnings(document, &calcs.lookup.warnings);
+
+    document.heading("Corrections");
+    write_pdf_temperature_correction(document, &cessna, 35, &calcs.correction);
+    write_pdf_grass_correction(document, parameters.is_grass, 7, &calcs.correction);
+
+    Ok(calcs.correction.final_distance(parameters.is_grass))
+}
+
+pub fn write_pdf_for_landing(parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    let calcs = cessna.calc_landing();
+
+    document.heading("Chart Interpolation");
+    write_pdf_elevation(document, &cessna);
+    write_performance_table(document, "", "", [
+        format!("{}' at {}°F", cessna.atmosphere_bounds.lower.altitude, cessna.atmosphere_bounds.lower.temperature),
+        format!("{:.0}' at {:.0}°F", cessna.elevation_ft, cessna.standard_temperature_f),
+        format!("{}' at {}°F", cessna.atmosphere_bounds.upper.altitude, cessna.atmosphere_bounds.upper.temperature)
+    ], std::slice::from_ref(&calcs.landing_distances));
+    write_pdf_warnings(document, &calcs.lookup.warnings);
+
+    document.heading("Corrections");
+    document.text(format!("Decrease distances 10% for each 4 knots of headwind: {:.0}% -> {}",
+        calcs.headwind_correction_percentage * 100.0,
+        format_distance(calcs.distance_with_headwind)));
+    write_pdf_temperature_correction(document, &cessna, 60, &calcs.correction);
+    write_pdf_grass_correction(document, parameters.is_grass, 20, &calcs.correction);
+
+    Ok(calcs.correction.final_distance(parameters.is_grass))
+}
+
+/// The calculation as HTML with the book distance it came to.
+pub fn get_raw_html_for_take_off(parameters: &PerformanceParameters, start_landing_flow: bool) -> Result<(String, Distance), PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    let calcs = cessna.try_calc_take_off()?;
+    let distance = calcs.correction.final_distance(parameters.is_grass);
+    let template = TakeOffTemplate {
+        start_landing_flow,
+        is_grass: parameters.is_grass,
+        calcs,
+        cessna
+    };
+    Ok((template.render().unwrap(), distance))
+}
+
+pub async fn get_for_take_off(query_parameters: Query<QueryPerformanceParameters>) -> Response {
+    let parameters = match query_parameters.to_performance_parameters("cessna150j", true) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_take_off(String::from("cessna150j"), parameters, false);
+    let page = ToPageTemplate {
+        page_title:String::from("Cessna 150 J Take Off Performance"),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
+
 #[derive(Template)]
 #[template(path = "partials/aircraft/cessna150j/landing.html")]
 pub struct LandingTemplate {
@@ -65,28 +146,29 @@ pub struct LandingTemplate {
     cessna: Cessna150J
 }
 
-pub fn get_raw_html_for_landing(parameters: &PerformanceParameters) -> String {
-    get_raw_html(&parameters, &|cessna| {
-        let calcs = cessna.calc_landing(); 
-        LandingTemplate {
-            is_grass: parameters.is_grass,
-            calcs,
-            cessna
-        }
-    })
+pub fn get_raw_html_for_landing(parameters: &PerformanceParameters) -> Result<(String, Distance), PerformanceError> {
+    let cessna = try_new_cessna(parameters)?;
+    let calcs = cessna.calc_landing();
+    let distance = calcs.correction.final_distance(parameters.is_grass);
+    let template = LandingTemplate {
+        is_grass: parameters.is_grass,
+        calcs,
+        cessna
+    };
+    Ok((template.render().unwrap(), distance))
 }
 
 pub async fn get_for_landing(query_parameters: Query<QueryPerformanceParameters>) -> Response {
-    let parameters = query_parameters.to_performance_parameters();
-    if parameters.headwind.knots() < 0 {
-        get_tailwind_error_response(&parameters)
-    } else {
-        let raw_html = get_raw_html_for_landing(&parameters);
-        let page = ToPageTemplate {
-            page_title:String::from("Cessna 150 J Landing Performance"),
-            raw_html
-        };
-
-        Html(page.render().unwrap()).into_response()
-    }
-}
\ No newline at end of file
+    let parameters = match query_parameters.to_performance_parameters("cessna150j", false) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_landing(String::from("cessna150j"), parameters);
+    let page = ToPageTemplate {
+        page_title:String::from("Cessna 150 J Landing Performance"),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
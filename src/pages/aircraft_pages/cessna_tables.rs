@@ -0,0 +1,164 @@
+use askama::Template;
+use axum::{extract::Query, response::{Html, IntoResponse, Response}};
+
+use crate::{
+    data::performance::{aircraft::cessna_tables::{Cessna, Performance, Tables}, climb::Climb, distance::Distance, error::PerformanceError},
+    math::PressureAltitudeMethod,
+    pages::{ErrorTemplate, ToPageTemplate},
+    pdf::Document
+};
+
+use super::{aircraft_name, format_distance, write_pdf_warnings, write_performance_table, PerformanceParameters, QueryPerformanceParameters};
+
+#[derive(Template)]
+#[template(path = "partials/aircraft/cessna_tables/performance.html")]
+pub struct PerformanceTemplate<'a> {
+    aircraft_type: &'a str,
+    start_landing_flow: bool,
+    is_grass: bool,
+    performance: Performance,
+    cessna: Cessna
+}
+
+fn try_new_cessna(tables: &'static Tables, parameters: &PerformanceParameters) -> Result<Cessna, PerformanceError> {
+    let pressure = parameters.pressure.ok_or("Pressure is required for the calculation")?;
+    Cessna::try_new(tables, parameters.headwind, parameters.elevation, pressure, parameters.pressure_altitude_method, parameters.temperature)
+}
+
+pub fn calc_distance_for_take_off(tables: &'static Tables, parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let performance = try_new_cessna(tables, parameters)?.try_calc_take_off(aircraft_weight_lbs)?;
+    Ok(performance.final_distance(parameters.is_grass))
+}
+
+pub fn calc_distance_for_landing(tables: &'static Tables, parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let performance = try_new_cessna(tables, parameters)?.try_calc_landing()?;
+    Ok(performance.final_distance(parameters.is_grass))
+}
+
+pub fn calc_climb(tables: &'static Tables, parameters: &PerformanceParameters) -> Result<Climb, PerformanceError> {
+    try_new_cessna(tables, parameters)?.calc_climb()
+}
+
+fn write_pdf(document: &mut Document, is_grass: bool, cessna: &Cessna, performance: &Performance) -> Distance {
+    document.heading("Chart Interpolation");
+    if let Some(aircraft_weight_lbs) = performance.aircraft_weight_lbs {
+        document.text(format!("Using the {aircraft_weight_lbs} lbs weight table."));
+    }
+    match cessna.pressure_altitude_method {
+        PressureAltitudeMethod::RuleOfThumb => document.text(format!("The pressure altitude for the altimeter setting of {:.2}\" at {:.0}' by the {} is (29.92\" - {:.2}\") × 1000 + {:.0}' ≈ {:.0}'.",
+            cessna.pressure_in_hg,
+            cessna.elevation_ft,
+            cessna.pressure_altitude_method.description(),
+            cessna.pressure_in_hg,
+            cessna.elevation_ft,
+            cessna.pressure_altitude_ft)),
+        PressureAltitudeMethod::StandardAtmosphere => document.text(format!("The pressure altitude for the altimeter setting of {:.2}\" ({:.1} hPa) at {:.0}' by the {} is 145442' × (1 - ({:.1} / 1013.25)^0.190263 × (1 - {:.0}' / 145442')) ≈ {:.0}'.",
+            cessna.pressure_in_hg,
+            cessna.pressure_hpa(),
+            cessna.elevation_ft,
+            cessna.pressure_altitude_method.description(),
+            cessna.pressure_hpa(),
+            cessna.elevation_ft,
+            cessna.pressure_altitude_ft))
+    }
+    document.text(format!("The temperature of {:.0}°C is {:.0}% between {}°C and {}°C.",
+        cessna.temperature_c,
+        performance.temperature_c_tween_percentage * 100.0,
+        performance.lower_temperature_c,
+        performance.upper_temperature_c));
+    write_performance_table(document, "Pressure Altitude", "'", [
+        format!("{}°C", performance.lower_temperature_c),
+        format!("{:.0}°C", cessna.temperature_c),
+        format!("{}°C", performance.upper_temperature_c)
+    ], &performance.distance_rows);
+    write_pdf_warnings(document, &performance.lookup.warnings);
+
+    document.heading("Corrections");
+    document.text(format!("Decrease distances 10% for each {} knots headwind, increase 10% for each {} knots tailwind: {:.2}% -> {}",
+        cessna.tables.notes.headwind_kts_per_10_percent,
+        cessna.tables.notes.tailwind_kts_per_10_percent,
+        performance.corrections.wind_correction_percentage * 100.0,
+        format_distance(performance.corrections.distance_corrected_for_wind)));
+    if is_grass {
+        document.text(format!("Dry grass, increase distances by {:.0}% of the ground roll: +{:.0}' -> {}",
+            performance.corrections.grass_ground_roll_percentage * 100.0,
+            performance.corrections.grass_offset,
+            format_distance(performance.corrections.distance_corrected_for_grass)));
+    }
+
+    performance.final_distance(is_grass)
+}
+
+pub fn write_pdf_for_take_off(tables: &'static Tables, parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let cessna = try_new_cessna(tables, parameters)?;
+    let performance = cessna.try_calc_take_off(aircraft_weight_lbs)?;
+    Ok(write_pdf(document, parameters.is_grass, &cessna, &performance))
+}
+
+pub fn write_pdf_for_landing(tables: &'static Tables, parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let cessna = try_new_cessna(tables, parameters)?;
+    let performance = cessna.try_calc_landing()?;
+    Ok(write_pdf(document, parameters.is_grass, &cessna, &performance))
+}
+
+/// The calculation as HTML with the book distance it came to.
+pub fn get_raw_html_for_take_off(aircraft_type: &str, tables: &'static Tables, parameters: &PerformanceParameters, start_landing_flow: bool) -> Result<(String, Distance), PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let cessna = try_new_cessna(tables, parameters)?;
+    let performance = cessna.try_calc_take_off(aircraft_weight_lbs)?;
+    let distance = performance.final_distance(parameters.is_grass);
+    let template = PerformanceTemplate {
+        aircraft_type,
+        start_landing_flow,
+        is_grass: parameters.is_grass,
+        performance,
+        cessna
+    };
+    Ok((template.render().unwrap(), distance))
+}
+
+pub async fn get_for_take_off(aircraft_type: &'static str, query_parameters: Query<QueryPerformanceParameters>) -> Response {
+    let parameters = match query_parameters.to_performance_parameters(aircraft_type, true) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_take_off(String::from(aircraft_type), parameters, false);
+    let page = ToPageTemplate {
+        page_title: format!("{} Take Off Performance", aircraft_name(aircraft_type).unwrap_or(aircraft_type)),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
+
+pub fn get_raw_html_for_landing(aircraft_type: &str, tables: &'static Tables, parameters: &PerformanceParameters) -> Result<(String, Distance), PerformanceError> {
+    let cessna = try_new_cessna(tables, parameters)?;
+    let performance = cessna.try_calc_landing()?;
+    let distance = performance.final_distance(parameters.is_grass);
+    let template = PerformanceTemplate {
+        aircraft_type,
+        is_grass: parameters.is_grass,
+        start_landing_flow: false,
+        performance,
+        cessna
+    };
+    Ok((template.render().unwrap(), distance))
+}
+
+pub async fn get_for_landing(aircraft_type: &'static str, query_parameters: Query<QueryPerformanceParameters>) -> Response {
+    let parameters = match query_parameters.to_performance_parameters(aircraft_type, false) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_landing(String::from(aircraft_type), parameters);
+    let page = ToPageTemplate {
+        page_title: format!("{} Landing Performance", aircraft_name(aircraft_type).unwrap_or(aircraft_type)),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
@@ -1,69 +1,507 @@
-use serde::Deserialize;
+use askama::Template;
+use axum::{extract::{Path, Query}, http::StatusCode, response::{IntoResponse, Json, Response}};
+use serde::{Deserialize, Serialize};
 
-use crate::math::{Pressure, Temperature, Velocity};
+use crate::{
+    config,
+    data::{obstacles::Obstacle, performance::{aircraft::{self, cessna_tables::Tables, profile::AircraftProfile}, climb::{self, Clearance}, configuration::{self, Configuration, ConfigurationGroup}, distance::Distance, error::PerformanceError, limits::ChartLimits, performance_row::PerformanceRow, speeds::{Speed, SpeedKind, SpeedTable}}, store::STORE},
+    math::{units::{whole_pounds, UnitChoices, Units, WeightUnit}, Length, Weight, Pressure, PressureAltitudeMethod, Temperature, Velocity},
+    pdf::Document
+};
+
+use super::{empty_as_none, validation::{ConditionFields, FieldError, Validator}};
 
 pub mod cessna150j;
-pub mod cessna172m;
+pub mod cessna_tables;
+pub mod pa28161;
 
 #[derive(Deserialize)]
 pub struct QueryPerformanceParameters {
     pub is_grass: Option<bool>,
-    pub elevation_ft: i16,
-    pub headwind_kts: i16,
-    pub pressure_in_hg: Option<f32>,
-    pub temperature_f: Option<i16>,
-    pub temperature_c: Option<i16>,
-    pub standard_temperature_f: Option<i16>,
-    pub standard_temperature_c: Option<i16>,
-    pub aircraft_weight_lbs: Option<i16>
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub standard_temperature_f: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub standard_temperature_c: Option<f64>,
+    #[serde(default)]
+    pub pressure_altitude_method: PressureAltitudeMethod,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub tail_number: Option<String>,
+    #[serde(flatten)]
+    pub configuration: ConfigurationFields,
+    #[serde(flatten)]
+    pub conditions: ConditionFields
+}
+
+/// The configuration picked from each group by id, a missing or empty field is the configuration the book charts are for.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigurationFields {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_off_technique: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub landing_flaps: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approach: Option<String>
+}
+
+impl ConfigurationFields {
+    fn get(&self, group: ConfigurationGroup) -> Option<&str> {
+        let value = match group {
+            ConfigurationGroup::TakeOffTechnique => &self.take_off_technique,
+            ConfigurationGroup::LandingFlaps => &self.landing_flaps,
+            ConfigurationGroup::Approach => &self.approach
+        };
+        value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+    }
 }
 
 impl QueryPerformanceParameters {
-    fn convert_to_temperature_or_get_standard(c: Option<i16>, f: Option<i16>) -> Temperature {
-        match c {
-            Some(c) => Temperature::Celsius(c),
-            None => match f {
-                Some(f) => Temperature::Fahrenheit(f),
-                None => Temperature::Fahrenheit(59)
-            }
+    pub fn to_performance_parameters(&self, aircraft_type: &str, is_take_off: bool) -> Result<PerformanceParameters, Vec<FieldError>> {
+        let mut validator = Validator::default();
+        let conditions = validator.conditions(&self.conditions);
+        if let Some(limits) = chart_limits(aircraft_type) {
+            validator.chart_limits(limits, is_take_off, &conditions, self.pressure_altitude_method);
         }
-    }
 
-    pub fn to_performance_parameters(&self) -> PerformanceParameters {
-        PerformanceParameters {
+        let (Some(elevation), Some(temperature), Some(headwind)) = (conditions.elevation, conditions.temperature, conditions.headwind) else {
+            return Err(validator.errors);
+        };
+        let configurations = select_configurations(&mut validator, aircraft_type, is_take_off, &self.configuration);
+        let standard_temperature = self.standard_temperature_c.map(Temperature::Celsius)
+            .or(self.standard_temperature_f.map(Temperature::Fahrenheit))
+            .unwrap_or(Temperature::standard_temperature(elevation.value));
+
+        validator.finish(|| PerformanceParameters {
             is_grass: self.is_grass.unwrap_or(false),
-            elevation_ft: self.elevation_ft,
-            pressure: match self.pressure_in_hg { Some(in_hg) => Some(Pressure::InchesOfMercury(in_hg)), None => None },
-            headwind: Velocity::Knots(self.headwind_kts),
-            temperature: Self::convert_to_temperature_or_get_standard(self.temperature_c, self.temperature_f),
-            standard_temperature: Self::convert_to_temperature_or_get_standard(self.standard_temperature_c, self.standard_temperature_f),
-            aircraft_weight_lbs: self.aircraft_weight_lbs
-        }
+            elevation: elevation.value,
+            pressure: conditions.pressure.map(|pressure| pressure.value),
+            pressure_altitude_method: self.pressure_altitude_method,
+            headwind: headwind.value,
+            temperature: temperature.value,
+            standard_temperature,
+            aircraft_weight_lbs: conditions.aircraft_weight.map(|weight| weight.value.pounds().round() as i16),
+            configurations,
+            profile: load_profile(self.tail_number.as_deref())
+        })
     }
 }
 
 pub struct PerformanceParameters {
     pub is_grass: bool,
-    pub elevation_ft: i16,
+    pub elevation: Length,
     pub pressure: Option<Pressure>,
+    pub pressure_altitude_method: PressureAltitudeMethod,
     pub headwind: Velocity,
     pub temperature: Temperature,
     pub standard_temperature: Temperature,
-    pub aircraft_weight_lbs: Option<i16>
+    pub aircraft_weight_lbs: Option<i16>,
+    /// The configurations for the take off or landing, empty for the book configuration.
+    pub configurations: Vec<&'static Configuration>,
+    pub profile: Option<AircraftProfile>
+}
+
+/// The configurations for a take off or landing in the aircraft, with an error against the field of any the aircraft does not have.
+pub fn select_configurations(validator: &mut Validator, aircraft_type: &str, is_take_off: bool, fields: &ConfigurationFields) -> Vec<&'static Configuration> {
+    let available = configurations(aircraft_type);
+    let mut selected = vec![];
+
+    for group in ConfigurationGroup::ALL.into_iter().filter(|group| group.is_take_off() == is_take_off) {
+        let Some(id) = fields.get(group) else {
+            continue;
+        };
+
+        match available.iter().find(|configuration| configuration.group == group && configuration.id == id) {
+            Some(configuration) => selected.push(configuration),
+            None => validator.error(group.field(), format!("{} {id} is not one of the configurations for this aircraft.", group.label()))
+        }
+    }
+
+    selected
+}
+
+#[derive(Template)]
+#[template(path = "partials/aircraft/profile.html")]
+pub struct ProfileTemplate<'a> {
+    profile: &'a AircraftProfile,
+    factor_percentage: f64,
+    book_distance: Distance,
+    distance: Distance
+}
+
+#[derive(Template)]
+#[template(path = "partials/aircraft/configuration.html")]
+pub struct ConfigurationTemplate<'a> {
+    configurations: &'a [&'static Configuration],
+    book_distance: Distance,
+    distance: Distance
+}
+
+#[derive(Template)]
+#[template(path = "partials/aircraft/not_available.html")]
+pub struct NotAvailableTemplate {
+    mode: &'static str,
+    error: PerformanceError
+}
+
+pub fn load_profile(tail_number: Option<&str>) -> Option<AircraftProfile> {
+    let tail_number = tail_number?;
+    STORE.lock().unwrap().find_aircraft(tail_number).map(|aircraft| aircraft.profile.clone())
+}
+
+pub fn is_seaplane(tail_number: Option<&str>) -> bool {
+    load_profile(tail_number).is_some_and(|profile| profile.is_seaplane)
+}
+
+/// The take off configurations apply to a take off and the landing ones to a landing, the abort point stops with the book landing.
+fn apply_configurations(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance) -> Distance {
+    let configurations: Vec<&Configuration> = performance.configurations.iter()
+        .copied()
+        .filter(|configuration| configuration.group.is_take_off() == is_take_off)
+        .collect();
+    configuration::apply_all(&configurations, book_distance)
+}
+
+/// Each configuration group for the take off or landing with the configuration used, the book one unless another was picked.
+pub fn describe_configurations(aircraft_type: &str, is_take_off: bool, selected: &[&Configuration]) -> Vec<String> {
+    ConfigurationGroup::ALL.into_iter()
+        .filter(|group| group.is_take_off() == is_take_off)
+        .filter_map(|group| selected.iter().copied()
+            .find(|configuration| configuration.group == group)
+            .or(configurations(aircraft_type).iter().find(|configuration| configuration.group == group))
+            .map(|configuration| format!("{}: {}", group.label(), configuration.name)))
+        .collect()
+}
+
+fn apply_profile(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance) -> Distance {
+    match &performance.profile {
+        Some(profile) => profile.apply(is_take_off, book_distance),
+        None => book_distance
+    }
+}
+
+fn get_raw_html_for_configurations(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance) -> String {
+    if performance.configurations.iter().all(|configuration| configuration.is_book()) {
+        return String::new();
+    }
+
+    ConfigurationTemplate {
+        configurations: &performance.configurations,
+        book_distance,
+        distance: apply_configurations(performance, is_take_off, book_distance)
+    }.render().unwrap()
+}
+
+fn write_pdf_for_configurations(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance, document: &mut Document) -> Distance {
+    let distance = apply_configurations(performance, is_take_off, book_distance);
+    if performance.configurations.iter().all(|configuration| configuration.is_book()) {
+        return distance;
+    }
+
+    document.heading("Configuration");
+    for configuration in &performance.configurations {
+        document.text(format!("{}: {} (ground run x{:.2}, air distance x{:.2})", configuration.group.label(), configuration.name, configuration.ground_run_factor, configuration.air_distance_factor));
+    }
+    document.text(format!("Book distances adjusted for the configuration: {} -> {}", format_distance(book_distance), format_distance(distance)));
+
+    distance
+}
+
+fn get_raw_html_for_profile(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance) -> String {
+    let Some(profile) = &performance.profile else {
+        return String::new();
+    };
+
+    ProfileTemplate {
+        profile,
+        factor_percentage: profile.factor_percentage(is_take_off),
+        book_distance,
+        distance: profile.apply(is_take_off, book_distance)
+    }.render().unwrap()
+}
+
+fn write_pdf_for_profile(performance: &PerformanceParameters, is_take_off: bool, book_distance: Distance, document: &mut Document) -> Distance {
+    let Some(profile) = &performance.profile else {
+        return book_distance;
+    };
+
+    let distance = profile.apply(is_take_off, book_distance);
+    document.heading(format!("Aircraft Profile for {}", profile.tail_number));
+    document.text(format!("Book distances adjusted by {}%: {} -> {}", profile.factor_percentage(is_take_off), format_distance(book_distance), format_distance(distance)));
+    if !profile.modifications.is_empty() {
+        document.text(format!("Engine/Prop Modifications: {}", profile.modifications));
+    }
+    if !profile.stc_notes.is_empty() {
+        document.text(format!("STC Notes: {}", profile.stc_notes));
+    }
+
+    distance
+}
+
+/// The Cessnas whose POH has take off and landing tables by pressure altitude and temperature.
+fn tables(aircraft_type: &str) -> Option<&'static Tables> {
+    match aircraft_type {
+        "cessna172m" => Some(&aircraft::cessna172m::TABLES),
+        "cessna172s" => Some(&aircraft::cessna172s::TABLES),
+        "cessna182p" => Some(&aircraft::cessna182p::TABLES),
+        "cessna182t" => Some(&aircraft::cessna182t::TABLES),
+        _ => None
+    }
+}
+
+pub fn aircraft_name(aircraft_type: &str) -> Option<&'static str> {
+    match aircraft_type {
+        "cessna150j" => Some("Cessna 150 J"),
+        "cessna172m" => Some("Cessna 172 M"),
+        "cessna172s" => Some("Cessna 172 S"),
+        "cessna182p" => Some("Cessna 182 P"),
+        "cessna182t" => Some("Cessna 182 T"),
+        "pa28161" => Some("Piper PA-28-161 Warrior"),
+        _ => None
+    }
+}
+
+pub fn configurations(aircraft_type: &str) -> &'static [Configuration] {
+    match aircraft_type {
+        "cessna150j" => &aircraft::cessna150j::CONFIGURATIONS,
+        "cessna172m" => &aircraft::cessna172m::CONFIGURATIONS,
+        "cessna172s" => &aircraft::cessna172s::CONFIGURATIONS,
+        "cessna182p" => &aircraft::cessna182p::CONFIGURATIONS,
+        "cessna182t" => &aircraft::cessna182t::CONFIGURATIONS,
+        "pa28161" => &aircraft::pa28161::CONFIGURATIONS,
+        _ => &[]
+    }
+}
+
+pub fn chart_limits(aircraft_type: &str) -> Option<&'static ChartLimits> {
+    match aircraft_type {
+        "cessna150j" => Some(&aircraft::cessna150j::LIMITS),
+        "cessna172m" => Some(&aircraft::cessna172m::LIMITS),
+        "cessna172s" => Some(&aircraft::cessna172s::LIMITS),
+        "cessna182p" => Some(&aircraft::cessna182p::LIMITS),
+        "cessna182t" => Some(&aircraft::cessna182t::LIMITS),
+        "pa28161" => Some(&aircraft::pa28161::LIMITS),
+        _ => None
+    }
+}
+
+pub fn speed_table(aircraft_type: &str) -> Option<&'static SpeedTable> {
+    match aircraft_type {
+        "cessna150j" => Some(&aircraft::cessna150j::SPEEDS),
+        "cessna172m" => Some(&aircraft::cessna172m::SPEEDS),
+        "cessna172s" => Some(&aircraft::cessna172s::SPEEDS),
+        "cessna182p" => Some(&aircraft::cessna182p::SPEEDS),
+        "cessna182t" => Some(&aircraft::cessna182t::SPEEDS),
+        "pa28161" => Some(&aircraft::pa28161::SPEEDS),
+        _ => None
+    }
+}
+
+/// The speeds at the weight, or at the heaviest weight of the table when no weight was given, with the weight they are for.
+pub fn speeds(aircraft_type: &str, aircraft_weight_lbs: Option<i16>) -> Option<(f64, Vec<Speed>)> {
+    let table = speed_table(aircraft_type)?;
+    Some(table.at(aircraft_weight_lbs.map(f64::from).unwrap_or(table.max_weight_lbs())))
+}
+
+/// A warning when the weight was off the speed table and the speeds are for the charted weight it was held at.
+fn speed_weight_warning(aircraft_weight_lbs: Option<i16>, speeds_weight_lbs: f64, units: &Units) -> Option<String> {
+    aircraft_weight_lbs
+        .filter(|weight_lbs| f64::from(*weight_lbs) != speeds_weight_lbs)
+        .map(|weight_lbs| format!("The weight of {} is off the speed table, the speeds are for {}.", units.pounds(f64::from(weight_lbs)), units.pounds(speeds_weight_lbs)))
+}
+
+/// The speeds for a briefing and the weight they were interpolated for.
+pub struct SpeedSummary {
+    pub weight: String,
+    pub speeds: Vec<Speed>,
+    pub warning: Option<String>
+}
+
+pub fn speed_summary(aircraft_type: &str, aircraft_weight_lbs: Option<i16>, units: &Units) -> Option<SpeedSummary> {
+    let (weight_lbs, speeds) = speeds(aircraft_type, aircraft_weight_lbs)?;
+    Some(SpeedSummary { weight: units.pounds(weight_lbs), speeds, warning: speed_weight_warning(aircraft_weight_lbs, weight_lbs, units) })
+}
+
+#[derive(Deserialize)]
+pub struct SpeedsQuery {
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_kg: Option<f64>,
+    #[serde(flatten)]
+    units: UnitChoices
+}
+
+#[derive(Serialize)]
+pub struct SpeedsResponse {
+    aircraft_type: String,
+    aircraft_weight_lbs: f64,
+    /// The weight in whole kilograms as well when they are the weight unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aircraft_weight_kg: Option<f64>,
+    speeds: Vec<Speed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warning: Option<String>
+}
+
+/// The speeds as JSON, for the weight given or the heaviest charted weight.
+pub async fn get_speeds(Path(aircraft_type): Path<String>, Query(query): Query<SpeedsQuery>) -> Response {
+    let units = query.units.or(config::default_units());
+    let requested_weight_lbs = whole_pounds(query.aircraft_weight_lbs, query.aircraft_weight_kg);
+    match speeds(&aircraft_type, requested_weight_lbs) {
+        Some((aircraft_weight_lbs, speeds)) => {
+            let aircraft_weight_kg = (units.weight_unit == WeightUnit::Kilograms).then(|| Weight::Pounds(aircraft_weight_lbs).kilograms().round());
+            let warning = speed_weight_warning(requested_weight_lbs, aircraft_weight_lbs, &units);
+            Json(SpeedsResponse { aircraft_type, aircraft_weight_lbs, aircraft_weight_kg, speeds, warning }).into_response()
+        },
+        None => (StatusCode::NOT_FOUND, format!("Unknown aircraft type {aircraft_type}.")).into_response()
+    }
+}
+
+pub fn rotation_speed_kias(aircraft_type: &str, aircraft_weight_lbs: Option<i16>) -> Option<f64> {
+    let (_, speeds) = speeds(aircraft_type, aircraft_weight_lbs)?;
+    speeds.iter().find(|speed| speed.kind == SpeedKind::Vr).map(|speed| speed.kias)
+}
+
+fn calc_book_distance_for_take_off(aircraft_type: &str, performance: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    match aircraft_type {
+        "cessna150j" => cessna150j::calc_distance_for_take_off(performance),
+        "pa28161" => pa28161::calc_distance_for_take_off(performance),
+        _ => match tables(aircraft_type) {
+            Some(tables) => cessna_tables::calc_distance_for_take_off(tables, performance),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    }
+}
+
+fn calc_book_distance_for_landing(aircraft_type: &str, performance: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    match aircraft_type {
+        "cessna150j" => cessna150j::calc_distance_for_landing(performance),
+        "pa28161" => pa28161::calc_distance_for_landing(performance),
+        _ => match tables(aircraft_type) {
+            Some(tables) => cessna_tables::calc_distance_for_landing(tables, performance),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    }
+}
+
+pub fn calc_distance_for_take_off(aircraft_type: &str, performance: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    calc_book_distance_for_take_off(aircraft_type, performance).map(|distance| apply_profile(performance, true, apply_configurations(performance, true, distance)))
+}
+
+pub fn calc_distance_for_landing(aircraft_type: &str, performance: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    calc_book_distance_for_landing(aircraft_type, performance).map(|distance| apply_profile(performance, false, apply_configurations(performance, false, distance)))
+}
+
+/// The margin over each obstacle past the departure end, from the take off distance and the book climb for the conditions.
+pub fn calc_obstacle_clearances(aircraft_type: &str, performance: &PerformanceParameters, distance: Distance, runway_length_ft: f64, obstacles: &[Obstacle]) -> Result<Vec<Clearance>, PerformanceError> {
+    let climb = match aircraft_type {
+        "cessna150j" => cessna150j::calc_climb(performance),
+        "pa28161" => pa28161::calc_climb(performance),
+        _ => match tables(aircraft_type) {
+            Some(tables) => cessna_tables::calc_climb(tables, performance),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    }?;
+
+    let pressure_altitude_ft = match performance.pressure {
+        Some(pressure) => pressure.altitude(performance.elevation, performance.pressure_altitude_method).feet(),
+        None => performance.elevation.feet()
+    };
+    let gradient = climb.gradient(climb::density_altitude_ft(pressure_altitude_ft, performance.temperature.celsius()), performance.headwind.knots());
+
+    Ok(climb::clearances(distance, gradient, runway_length_ft, obstacles))
+}
+
+pub fn format_distance(distance: Distance) -> String {
+    format!("{}' / {}'", distance.ground_run(), distance.clear_50_ft_obstacle())
+}
+
+fn write_performance_table(document: &mut Document, label_header: &str, label_unit: &str, column_headers: [String; 3], rows: &[PerformanceRow]) {
+    let mut table = vec![
+        vec![String::new(), column_headers[0].clone(), String::new(), column_headers[1].clone(), String::new(), column_headers[2].clone(), String::new()],
+        vec![label_header.to_string(), "Ground Run".to_string(), "50' Obs".to_string(), "Ground Run".to_string(), "50' Obs".to_string(), "Ground Run".to_string(), "50' Obs".to_string()]
+    ];
+
+    for row in rows {
+        table.push(vec![
+            row.label.map(|label| format!("{label:.0}{label_unit}")).unwrap_or_default(),
+            format!("{}'", row.lower_bound.ground_run()),
+            format!("{}'", row.lower_bound.clear_50_ft_obstacle()),
+            format!("{}'", row.middle_value.ground_run()),
+            format!("{}'", row.middle_value.clear_50_ft_obstacle()),
+            format!("{}'", row.upper_bound.ground_run()),
+            format!("{}'", row.upper_bound.clear_50_ft_obstacle())
+        ]);
+    }
+
+    document.table(table);
+}
+
+fn write_pdf_warnings(document: &mut Document, warnings: &[String]) {
+    for warning in warnings {
+        document.text(format!("Warning: {warning}"));
+    }
+}
+
+pub fn write_pdf_for_take_off(aircraft_type: &str, performance: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let book_distance = match aircraft_type {
+        "cessna150j" => cessna150j::write_pdf_for_take_off(performance, document),
+        "pa28161" => pa28161::write_pdf_for_take_off(performance, document),
+        _ => match tables(aircraft_type) {
+            Some(tables) => cessna_tables::write_pdf_for_take_off(tables, performance, document),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    }?;
+
+    let distance = write_pdf_for_configurations(performance, true, book_distance, document);
+    Ok(write_pdf_for_profile(performance, true, distance, document))
+}
+
+pub fn write_pdf_for_landing(aircraft_type: &str, performance: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let book_distance = match aircraft_type {
+        "cessna150j" => cessna150j::write_pdf_for_landing(performance, document),
+        "pa28161" => pa28161::write_pdf_for_landing(performance, document),
+        _ => match tables(aircraft_type) {
+            Some(tables) => cessna_tables::write_pdf_for_landing(tables, performance, document),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    }?;
+
+    let distance = write_pdf_for_configurations(performance, false, book_distance, document);
+    Ok(write_pdf_for_profile(performance, false, distance, document))
 }
 
 pub fn get_raw_html_for_take_off(aircraft_type: String, performance: PerformanceParameters, start_landing_flow: bool) -> String {
-    match aircraft_type.as_str() {
+    let calculation = match aircraft_type.as_str() {
         "cessna150j" => cessna150j::get_raw_html_for_take_off(&performance, start_landing_flow),
-        "cessna172m" => cessna172m::get_raw_html_for_take_off(&performance, start_landing_flow),
-        _ => "".to_string()
-    }
+        "pa28161" => pa28161::get_raw_html_for_take_off(&performance, start_landing_flow),
+        _ => match tables(&aircraft_type) {
+            Some(tables) => cessna_tables::get_raw_html_for_take_off(&aircraft_type, tables, &performance, start_landing_flow),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    };
+    let (raw_html, book_distance) = match calculation {
+        Ok(calculation) => calculation,
+        Err(error) => return NotAvailableTemplate { mode: "Take Off", error }.render().unwrap()
+    };
+
+    let distance = apply_configurations(&performance, true, book_distance);
+    format!("{raw_html}{}{}", get_raw_html_for_configurations(&performance, true, book_distance), get_raw_html_for_profile(&performance, true, distance))
 }
 
 pub fn get_raw_html_for_landing(aircraft_type: String, performance: PerformanceParameters) -> String {
-    match aircraft_type.as_str() {
+    let calculation = match aircraft_type.as_str() {
         "cessna150j" => cessna150j::get_raw_html_for_landing(&performance),
-        "cessna172m" => cessna172m::get_raw_html_for_landing(&performance),
-        _ => "".to_string()
-    }
+        "pa28161" => pa28161::get_raw_html_for_landing(&performance),
+        _ => match tables(&aircraft_type) {
+            Some(tables) => cessna_tables::get_raw_html_for_landing(&aircraft_type, tables, &performance),
+            None => Err(PerformanceError::from("Unknown aircraft type"))
+        }
+    };
+    let (raw_html, book_distance) = match calculation {
+        Ok(calculation) => calculation,
+        Err(error) => return NotAvailableTemplate { mode: "Landing", error }.render().unwrap()
+    };
+
+    let distance = apply_configurations(&performance, false, book_distance);
+    format!("{raw_html}{}{}", get_raw_html_for_configurations(&performance, false, book_distance), get_raw_html_for_profile(&performance, false, distance))
 }
\ No newline at end of file
@@ -0,0 +1,159 @@
+use askama::Template;
+use axum::{extract::Query, response::{Html, IntoResponse, Response}};
+
+use crate::{
+    data::performance::{aircraft::pa28161::{Pa28161, Performance}, climb::Climb, distance::Distance, error::PerformanceError},
+    math::PressureAltitudeMethod,
+    pages::{ErrorTemplate, ToPageTemplate},
+    pdf::Document
+};
+
+use super::{format_distance, write_pdf_warnings, write_performance_table, PerformanceParameters, QueryPerformanceParameters};
+
+#[derive(Template)]
+#[template(path = "partials/aircraft/pa28161/performance.html")]
+pub struct PerformanceTemplate {
+    start_landing_flow: bool,
+    is_grass: bool,
+    performance: Performance,
+    piper: Pa28161
+}
+
+fn try_new_piper(parameters: &PerformanceParameters) -> Result<Pa28161, PerformanceError> {
+    let pressure = parameters.pressure.ok_or("Pressure is required for the calculation")?;
+    Pa28161::try_new(parameters.headwind, parameters.elevation, pressure, parameters.pressure_altitude_method, parameters.temperature)
+}
+
+pub fn calc_distance_for_take_off(parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let performance = try_new_piper(parameters)?.try_calc_take_off(aircraft_weight_lbs)?;
+    Ok(performance.final_distance(parameters.is_grass))
+}
+
+pub fn calc_distance_for_landing(parameters: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    let performance = try_new_piper(parameters)?.try_calc_landing(parameters.aircraft_weight_lbs)?;
+    Ok(performance.final_distance(parameters.is_grass))
+}
+
+pub fn calc_climb(parameters: &PerformanceParameters) -> Result<Climb, PerformanceError> {
+    try_new_piper(parameters)?.calc_climb()
+}
+
+fn write_pdf(document: &mut Document, is_grass: bool, piper: &Pa28161, performance: &Performance) -> Distance {
+    document.heading("Graph Interpolation");
+    document.text(format!("Reading the graphs at {:.0} lbs.", performance.aircraft_weight_lbs));
+    match piper.pressure_altitude_method {
+        PressureAltitudeMethod::RuleOfThumb => document.text(format!("The pressure altitude for the altimeter setting of {:.2}\" at {:.0}' by the {} is (29.92\" - {:.2}\") × 1000 + {:.0}' ≈ {:.0}'.",
+            piper.pressure_in_hg,
+            piper.elevation_ft,
+            piper.pressure_altitude_method.description(),
+            piper.pressure_in_hg,
+            piper.elevation_ft,
+            piper.pressure_altitude_ft)),
+        PressureAltitudeMethod::StandardAtmosphere => document.text(format!("The pressure altitude for the altimeter setting of {:.2}\" ({:.1} hPa) at {:.0}' by the {} is 145442' × (1 - ({:.1} / 1013.25)^0.190263 × (1 - {:.0}' / 145442')) ≈ {:.0}'.",
+            piper.pressure_in_hg,
+            piper.pressure_hpa(),
+            piper.elevation_ft,
+            piper.pressure_altitude_method.description(),
+            piper.pressure_hpa(),
+            piper.elevation_ft,
+            piper.pressure_altitude_ft))
+    }
+    document.text(format!("The density altitude is {:.0}' + 120' × ({:.0}°C - {:.0}°C) ≈ {:.0}'.",
+        piper.pressure_altitude_ft,
+        piper.temperature_c,
+        piper.standard_temperature_c,
+        piper.density_altitude_ft));
+    document.text(format!("The headwind of {:.0} kts is {:.0}% between {} kts and {} kts.",
+        piper.headwind.knots(),
+        performance.headwind_tween_percentage * 100.0,
+        performance.lower_headwind_kts,
+        performance.upper_headwind_kts));
+    write_performance_table(document, "Density Altitude", "'", [
+        format!("{} kts", performance.lower_headwind_kts),
+        format!("{:.0} kts", piper.headwind.knots()),
+        format!("{} kts", performance.upper_headwind_kts)
+    ], &performance.distance_rows);
+    write_pdf_warnings(document, &performance.lookup.warnings);
+
+    if is_grass {
+        document.heading("Corrections");
+        document.text(format!("Dry grass, increase distances by {:.0}% of the ground roll: +{:.0}' -> {}",
+            performance.corrections.grass_ground_roll_percentage * 100.0,
+            performance.corrections.grass_offset,
+            format_distance(performance.corrections.distance_corrected_for_grass)));
+    }
+
+    performance.final_distance(is_grass)
+}
+
+pub fn write_pdf_for_take_off(parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let piper = try_new_piper(parameters)?;
+    let performance = piper.try_calc_take_off(aircraft_weight_lbs)?;
+    Ok(write_pdf(document, parameters.is_grass, &piper, &performance))
+}
+
+pub fn write_pdf_for_landing(parameters: &PerformanceParameters, document: &mut Document) -> Result<Distance, PerformanceError> {
+    let piper = try_new_piper(parameters)?;
+    let performance = piper.try_calc_landing(parameters.aircraft_weight_lbs)?;
+    Ok(write_pdf(document, parameters.is_grass, &piper, &performance))
+}
+
+/// The calculation as HTML with the book distance it came to.
+pub fn get_raw_html_for_take_off(parameters: &PerformanceParameters, start_landing_flow: bool) -> Result<(String, Distance), PerformanceError> {
+    let aircraft_weight_lbs = parameters.aircraft_weight_lbs.ok_or("The take off weight of the aircraft is required for the calculation")?;
+    let piper = try_new_piper(parameters)?;
+    let performance = piper.try_calc_take_off(aircraft_weight_lbs)?;
+    let distance = performance.final_distance(parameters.is_grass);
+    let template = PerformanceTemplate {
+        start_landing_flow,
+        is_grass: parameters.is_grass,
+        performance,
+        piper
+    };
+    Ok((template.render().unwrap(), distance))
+}
+
+pub async fn get_for_take_off(query_parameters: Query<QueryPerformanceParameters>) -> Response {
+    let parameters = match query_parameters.to_performance_parameters("pa28161", true) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_take_off(String::from("pa28161"), parameters, false);
+    let page = ToPageTemplate {
+        page_title:String::from("Piper PA-28-161 Warrior Take Off Performance"),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
+
+pub fn get_raw_html_for_landing(parameters: &PerformanceParameters) -> Result<(String, Distance), PerformanceError> {
+    let piper = try_new_piper(parameters)?;
+    let performance = piper.try_calc_landing(parameters.aircraft_weight_lbs)?;
+    let distance = performance.final_distance(parameters.is_grass);
+    let template = PerformanceTemplate {
+        is_grass: parameters.is_grass,
+        start_landing_flow: false,
+        performance,
+        piper
+    };
+    Ok((template.render().unwrap(), distance))
+}
+
+pub async fn get_for_landing(query_parameters: Query<QueryPerformanceParameters>) -> Response {
+    let parameters = match query_parameters.to_performance_parameters("pa28161", false) {
+        Ok(parameters) => parameters,
+        Err(errors) => return Html(ErrorTemplate::for_fields(&errors).render().unwrap()).into_response()
+    };
+
+    let raw_html = super::get_raw_html_for_landing(String::from("pa28161"), parameters);
+    let page = ToPageTemplate {
+        page_title:String::from("Piper PA-28-161 Warrior Landing Performance"),
+        raw_html
+    };
+
+    Html(page.render().unwrap()).into_response()
+}
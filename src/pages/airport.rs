@@ -1,52 +1,114 @@
+use std::{collections::HashMap, sync::Mutex, time::{Duration, Instant}};
+
 use askama::Template;
 use axum::{extract::{Path, Query}, response::{Html, IntoResponse, Response}, Form};
+use lazy_static::lazy_static;
 use serde::Deserialize;
 
-use crate::data::airports::{Airport, AirportHash, AIRPORTS};
+use crate::{
+    config,
+    data::{airports::{Airport, AirportHash, Runway, AIRPORTS}, store::STORE},
+    math::{units::{whole_pounds, UnitChoices, Units}, PressureAltitudeMethod}
+};
+
+use super::{aircraft_pages, empty_as_none, ErrorTemplate};
 
-use super::ErrorTemplate;
+static ARRIVAL: &str = "Arrival";
+static DEPARTURE: &str = "Departure";
 
-static ARRIVAL: &'static str = "Arrival";
-static DEPARTURE: &'static str = "Departure";
+lazy_static! {
+    /// The last METARs fetched for each airport and when, so repeated lookups within the cache TTL do not hit the weather service.
+    static ref METAR_CACHE: Mutex<HashMap<String, (Instant, String)>> = Mutex::new(HashMap::new());
+}
 
 #[derive(Deserialize)]
 pub struct SelectAirportConfig {
+    #[serde(default = "config::default_aircraft")]
     aircraft_type: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
     aircraft_weight_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_kg: Option<f64>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
     identifier: String,
-    is_arrival: Option<bool>
+    is_arrival: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pilot: Option<String>,
+    #[serde(flatten)]
+    units: UnitChoices
 }
 
 #[derive(Deserialize)]
 pub struct AirportParameters {
     aircraft_weight_lbs: Option<i16>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
     actual_metar: Option<String>,
-    custom_metar: Option<String>
+    custom_metar: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pilot: Option<String>,
+    #[serde(flatten)]
+    units: UnitChoices
 }
 
 #[derive(Template)]
 #[template(path = "airport.html")]
 pub struct AirportTemplate<'a> {
     airport: &'a Airport,
+    runways: Vec<&'a Runway>,
+    /// Helipads, water lanes for a landplane and runways without a heading.
+    unusable_runways: usize,
     mode: &'a str,
     is_take_off: bool,
     aircraft_type: String,
     aircraft_weight_lbs: Option<i16>,
+    pressure_altitude_method: PressureAltitudeMethod,
+    tail_number: Option<String>,
+    pilot: Option<String>,
     metars: Vec<String>,
-    metar: String
+    metar: String,
+    /// Carried through the runway forms, the rest stay the configured default.
+    unit_choices: UnitChoices,
+    units: Units
 }
 
-async fn load_metars(identifier: &String) -> String {
-    reqwest::get(format!("https://aviationweather.gov/api/data/metar?ids={identifier}&hours=24"))
-    .await.unwrap()
+async fn try_load_metars(identifier: &String) -> Result<String, reqwest::Error> {
+    let ttl = Duration::from_secs(config::get().weather_cache_ttl_secs);
+    if let Some((fetched, metars)) = METAR_CACHE.lock().unwrap().get(identifier) {
+        if fetched.elapsed() < ttl {
+            return Ok(metars.clone());
+        }
+    }
+
+    let metars = reqwest::get(format!("{}?ids={identifier}&hours=24", config::get().weather_url))
+    .await?
     .text()
-    .await.unwrap()
+    .await?;
+    METAR_CACHE.lock().unwrap().insert(identifier.clone(), (Instant::now(), metars.clone()));
+    Ok(metars)
+}
+
+/// An unreachable weather service leaves the list empty, the custom METAR can still be entered.
+async fn load_metars(identifier: &String) -> String {
+    try_load_metars(identifier).await.unwrap_or_default()
+}
+
+pub async fn load_latest_metar(identifier: &String) -> Option<String> {
+    try_load_metars(identifier).await.ok()?
+        .split('\n')
+        .find(|metar| !metar.is_empty())
+        .map(|metar| metar.to_string())
 }
 
 fn select_metar(airport_parameters: &Query<AirportParameters>) -> String {
     let empty_string = String::from("");
     let actual_or_empty = match &airport_parameters.actual_metar { Some(value) => value.clone(), None => empty_string };
-    if actual_or_empty.len() > 0 {
+    if !actual_or_empty.is_empty() {
         actual_or_empty
     }
     else {
@@ -54,7 +116,8 @@ fn select_metar(airport_parameters: &Query<AirportParameters>) -> String {
     }
 }
 
-async fn template(identifier: String, mode: &str, aircraft_type: String, aircraft_weight_lbs: Option<i16>, metar: String) -> Response {
+#[allow(clippy::too_many_arguments)]
+async fn template(identifier: String, mode: &str, aircraft_type: String, aircraft_weight_lbs: Option<i16>, pressure_altitude_method: PressureAltitudeMethod, tail_number: Option<String>, pilot: Option<String>, metar: String, unit_choices: UnitChoices) -> Response {
     let uppercased_identifier = identifier.to_uppercase();
 
     match AIRPORTS.load_by_identifier(&uppercased_identifier) {
@@ -62,20 +125,28 @@ async fn template(identifier: String, mode: &str, aircraft_type: String, aircraf
             let metars = match metar.len() {
                 0 => load_metars(&uppercased_identifier).await
                     .split('\n')
-                    .filter(|metar| metar.len() > 0)
+                    .filter(|metar| !metar.is_empty())
                     .map(|metar| metar.to_string())
                     .collect(),
                 _ => vec![]
             };
 
+            let runways = airport.usable_runways(aircraft_pages::is_seaplane(tail_number.as_deref()));
             let template = AirportTemplate {
                 airport,
+                unusable_runways: airport.runways.len() - runways.len(),
+                runways,
                 mode,
                 is_take_off: mode == DEPARTURE,
                 aircraft_type,
                 aircraft_weight_lbs,
+                pressure_altitude_method,
+                tail_number,
+                pilot,
                 metars,
-                metar
+                metar,
+                unit_choices,
+                units: unit_choices.or(config::default_units())
             };
 
             Html(template.render().unwrap()).into_response()
@@ -89,19 +160,25 @@ async fn template(identifier: String, mode: &str, aircraft_type: String, aircraf
 
 async fn get(identifier: String, mode: &str, aircraft_type: String, airport_parameters: Query<AirportParameters>) -> Response{
     let metar = select_metar(&airport_parameters);
-    template(identifier, mode, aircraft_type, airport_parameters.aircraft_weight_lbs, metar).await
+    template(identifier, mode, aircraft_type, airport_parameters.aircraft_weight_lbs, airport_parameters.pressure_altitude_method, airport_parameters.tail_number.clone(), airport_parameters.pilot.clone(), metar, airport_parameters.units).await
 }
 
 pub async fn post(Form(select_airport): Form<SelectAirportConfig>) -> Response {
     let mode = if select_airport.is_arrival.is_some_and(|v| v) { &ARRIVAL } else { &DEPARTURE };
-    
-    template(select_airport.identifier, mode, select_airport.aircraft_type, select_airport.aircraft_weight_lbs, String::from("")).await
+
+    let tail_number = select_airport.tail_number.map(|tail_number| tail_number.to_uppercase());
+    let aircraft_type = tail_number.as_ref()
+        .and_then(|tail_number| STORE.lock().unwrap().find_aircraft(tail_number).map(|aircraft| aircraft.aircraft_type.clone()))
+        .unwrap_or(select_airport.aircraft_type);
+
+    let aircraft_weight_lbs = whole_pounds(select_airport.aircraft_weight_lbs, select_airport.aircraft_weight_kg);
+    template(select_airport.identifier, mode, aircraft_type, aircraft_weight_lbs, select_airport.pressure_altitude_method, tail_number, select_airport.pilot, String::from(""), select_airport.units).await
 }
 
 pub async fn get_departure(Path((identifier, aircraft_type)): Path<(String, String)>, airport_parameters: Query<AirportParameters>) -> Response {
-    get(identifier, &DEPARTURE, aircraft_type, airport_parameters).await
+    get(identifier, DEPARTURE, aircraft_type, airport_parameters).await
 }
 
 pub async fn get_arrival(Path((identifier, aircraft_type)): Path<(String, String)>, airport_parameters: Query<AirportParameters>) -> Response {
-    get(identifier, &ARRIVAL, aircraft_type, airport_parameters).await
+    get(identifier, ARRIVAL, aircraft_type, airport_parameters).await
 }
\ No newline at end of file
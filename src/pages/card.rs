@@ -0,0 +1,335 @@
+use askama::Template;
+use axum::{extract::{Query, RawQuery}, http::header, response::{Html, IntoResponse, Response}};
+use serde::Deserialize;
+
+use crate::{
+    config,
+    data::{airports::{AirportHash, HeadingSource, AIRPORTS}, performance::{aircraft::profile::AircraftProfile, distance::Distance, error::PerformanceError}, store::{CalculationKind, CalculationRecord, STORE}},
+    math::{units::{whole_pounds, LengthUnit, PressureUnit, UnitChoices, Units}, Length, Pressure, PressureAltitudeMethod, Temperature, Velocity}
+};
+
+use super::{aircraft_pages::{self, PerformanceParameters, SpeedSummary}, empty_as_none, runway, ErrorTemplate};
+
+const MAX_STEPS: usize = 25;
+
+#[derive(Deserialize)]
+pub struct CardParameters {
+    identifier: String,
+    runway: String,
+    #[serde(default = "config::default_aircraft")]
+    aircraft_type: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_kg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    headwind_kts: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    temperature_min_c: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    temperature_max_c: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    temperature_step_c: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_min_in_hg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_max_in_hg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_step_in_hg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_min_hpa: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_max_hpa: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pressure_step_hpa: Option<f64>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
+    #[serde(flatten)]
+    units: UnitChoices
+}
+
+pub struct CardCell {
+    distance: Option<Distance>,
+    exceeds_runway: bool,
+    climb_limited: bool
+}
+
+pub struct CardRow {
+    temperature: Temperature,
+    cells: Vec<CardCell>
+}
+
+pub struct CardGrid {
+    mode: &'static str,
+    available: String,
+    rows: Vec<CardRow>
+}
+
+#[derive(Template)]
+#[template(path = "card.html")]
+pub struct CardTemplate {
+    airport_ident: String,
+    airport_name: String,
+    runway_ident: String,
+    heading: u16,
+    true_heading: u16,
+    heading_source: HeadingSource,
+    variation: String,
+    elevation: String,
+    length: String,
+    is_length_inferred: bool,
+    width: Option<String>,
+    is_lighted: bool,
+    surface: String,
+    is_grass: bool,
+    aircraft_name: &'static str,
+    aircraft_weight: Option<String>,
+    profile: Option<AircraftProfile>,
+    speeds: Option<SpeedSummary>,
+    headwind_kts: i16,
+    pressures: Vec<Pressure>,
+    pressure_headers: Vec<String>,
+    grids: Vec<CardGrid>,
+    units: Units,
+    csv_href: String,
+    history_id: Option<u32>,
+    history_error: Option<String>
+}
+
+fn temperature_steps(params: &CardParameters) -> Result<Vec<i16>, String> {
+    let min = params.temperature_min_c.unwrap_or(0);
+    let max = params.temperature_max_c.unwrap_or(30);
+    let step = params.temperature_step_c.unwrap_or(5);
+
+    if step <= 0 || max < min {
+        return Err(String::from("The temperature range is invalid."));
+    }
+
+    // In i32 so the span of extreme temperatures can not overflow.
+    let count = ((i32::from(max) - i32::from(min)) / i32::from(step)) as usize + 1;
+    if count > MAX_STEPS {
+        return Err(format!("The temperature range has more than {MAX_STEPS} steps."));
+    }
+
+    Ok((0..count).map(|i| min + i as i16 * step).collect())
+}
+
+fn pressure_range(min: f64, max: f64, step: f64, decimals: i32) -> Result<Vec<f64>, String> {
+    if ![min, max, step].iter().all(|value| value.is_finite()) || step <= 0.0 || max < min {
+        return Err(String::from("The altimeter range is invalid."));
+    }
+
+    // Checked before the count is cast, a tiny step would otherwise saturate it.
+    let steps = (max - min) / step;
+    if steps >= MAX_STEPS as f64 {
+        return Err(format!("The altimeter range has more than {MAX_STEPS} steps."));
+    }
+    let count = (steps + 0.0001).floor() as usize + 1;
+
+    let scale = 10f64.powi(decimals);
+    Ok((0..count).map(|i| ((min + i as f64 * step) * scale).round() / scale).collect())
+}
+
+fn pressure_steps(params: &CardParameters) -> Result<Vec<Pressure>, String> {
+    if params.pressure_min_hpa.is_some() || params.pressure_max_hpa.is_some() || params.pressure_step_hpa.is_some() {
+        let range = pressure_range(params.pressure_min_hpa.unwrap_or(996.0), params.pressure_max_hpa.unwrap_or(1030.0), params.pressure_step_hpa.unwrap_or(8.0), 0)?;
+        Ok(range.into_iter().map(Pressure::Hectopascals).collect())
+    } else {
+        let range = pressure_range(params.pressure_min_in_hg.unwrap_or(29.42), params.pressure_max_in_hg.unwrap_or(30.42), params.pressure_step_in_hg.unwrap_or(0.25), 2)?;
+        Ok(range.into_iter().map(Pressure::InchesOfMercury).collect())
+    }
+}
+
+fn build_grid(is_take_off: bool, available_ft: u16, units: &Units, temperatures_c: &[i16], pressures: &[Pressure], calc: &dyn Fn(Temperature, Pressure) -> Result<Distance, PerformanceError>) -> CardGrid {
+    let rows = temperatures_c.iter().map(|temperature_c| {
+        let temperature = Temperature::Celsius(*temperature_c as f64);
+        let cells = pressures.iter().map(|pressure| {
+            let result = calc(temperature, *pressure);
+            let distance = result.ok();
+            CardCell {
+                distance,
+                exceeds_runway: distance.is_some_and(|d| runway::required_ft(is_take_off, d).round() > available_ft as f64),
+                climb_limited: matches!(result, Err(PerformanceError::ClimbLimited { .. }))
+            }
+        }).collect();
+
+        CardRow { temperature, cells }
+    }).collect();
+
+    let mode = if is_take_off { "Take Off" } else { "Landing" };
+    CardGrid { mode, available: units.feet(available_ft), rows }
+}
+
+fn build_card(params: &CardParameters, csv_href: String) -> Result<CardTemplate, String> {
+    let uppercased_identifier = params.identifier.to_uppercase();
+    let airport = AIRPORTS.load_by_identifier(&uppercased_identifier).ok_or(format!("{uppercased_identifier} not found."))?;
+    let runway_end = airport.find_open_runway_end(&params.runway, aircraft_pages::is_seaplane(params.tail_number.as_deref())).map_err(|message| format!("{message}."))?;
+    let aircraft_name = aircraft_pages::aircraft_name(&params.aircraft_type).ok_or(format!("Unknown aircraft type {}.", params.aircraft_type))?;
+
+    let temperatures_c = temperature_steps(params)?;
+    let pressures = pressure_steps(params)?;
+    let aircraft_weight_lbs = whole_pounds(params.aircraft_weight_lbs, params.aircraft_weight_kg);
+    let units = params.units.or(config::default_units());
+
+    let headwind_kts = params.headwind_kts.unwrap_or(0);
+    let elevation_ft = runway_end.elevation;
+    let is_grass = runway_end.runway.is_grass;
+    let length_ft = runway_end.runway.length;
+    let profile = aircraft_pages::load_profile(params.tail_number.as_deref());
+
+    let performance_parameters = |temperature: Temperature, pressure: Pressure| PerformanceParameters {
+        is_grass,
+        elevation: Length::Feet(elevation_ft as f64),
+        pressure: Some(pressure),
+        pressure_altitude_method: params.pressure_altitude_method,
+        headwind: Velocity::Knots(headwind_kts as f64),
+        temperature,
+        standard_temperature: Temperature::standard_temperature(Length::Feet(elevation_ft as f64)),
+        aircraft_weight_lbs,
+        configurations: vec![],
+        profile: profile.clone()
+    };
+
+    let take_off = build_grid(true, length_ft, &units, &temperatures_c, &pressures, &|temperature, pressure| {
+        aircraft_pages::calc_distance_for_take_off(&params.aircraft_type, &performance_parameters(temperature, pressure))
+    });
+
+    let landing = build_grid(false, length_ft.saturating_sub(runway_end.displaced_threshold), &units, &temperatures_c, &pressures, &|temperature, pressure| {
+        aircraft_pages::calc_distance_for_landing(&params.aircraft_type, &performance_parameters(temperature, pressure))
+    });
+
+    Ok(CardTemplate {
+        airport_ident: airport.ident.clone(),
+        airport_name: airport.name.clone(),
+        runway_ident: runway_end.ident.to_string(),
+        heading: runway_end.heading,
+        true_heading: runway_end.true_heading,
+        heading_source: runway_end.heading_source,
+        variation: airport.formatted_variation(),
+        elevation: units.feet(elevation_ft),
+        length: units.feet(length_ft),
+        is_length_inferred: runway_end.runway.is_length_inferred,
+        width: (runway_end.runway.width > 0).then(|| units.feet(runway_end.runway.width)),
+        is_lighted: runway_end.runway.lighted,
+        surface: runway_end.runway.surface.clone(),
+        is_grass,
+        aircraft_name,
+        aircraft_weight: aircraft_weight_lbs.map(|lbs| units.pounds(lbs)),
+        profile,
+        speeds: aircraft_pages::speed_summary(&params.aircraft_type, aircraft_weight_lbs, &units),
+        headwind_kts,
+        pressure_headers: pressures.iter().map(|pressure| units.pressure(*pressure)).collect(),
+        pressures,
+        grids: vec![take_off, landing],
+        units,
+        csv_href,
+        history_id: None,
+        history_error: None
+    })
+}
+
+/// The card is recorded with the query it was made from, it has a distance for each condition rather than one result.
+fn record_card(params: &CardParameters, card: &CardTemplate, query: String) -> Result<u32, String> {
+    let record = CalculationRecord {
+        id: 0,
+        timestamp: 0,
+        kind: CalculationKind::Card,
+        aircraft_type: params.aircraft_type.clone(),
+        tail_number: params.tail_number.clone(),
+        pilot: None,
+        is_take_off: false,
+        query,
+        ground_run_ft: None,
+        clear_50_ft_obstacle_ft: None,
+        verdict: format!("Card for {} runway {}.", card.airport_ident, card.runway_ident)
+    };
+
+    STORE.lock().unwrap().record_calculation(record)
+}
+
+fn write_csv(card: &CardTemplate) -> Result<Vec<u8>, csv::Error> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let (pressure_header, length_headers) = (
+        match card.units.pressure_unit {
+            PressureUnit::InHg => "pressure_in_hg",
+            PressureUnit::Hpa => "pressure_hpa"
+        },
+        match card.units.length_unit {
+            LengthUnit::Feet => ["ground_run_ft", "clear_50_ft_obstacle_ft"],
+            LengthUnit::Metres => ["ground_run_m", "clear_50_ft_obstacle_m"]
+        }
+    );
+    writer.write_record(["mode", "temperature_c", "temperature_f", pressure_header, length_headers[0], length_headers[1], "exceeds_runway", "climb_limited"])?;
+
+    let length = |feet: f64| match card.units.length_unit {
+        LengthUnit::Feet => format!("{feet:.0}"),
+        LengthUnit::Metres => format!("{:.0}", Length::Feet(feet).metres())
+    };
+
+    for grid in &card.grids {
+        for row in &grid.rows {
+            for (pressure, cell) in card.pressures.iter().zip(&row.cells) {
+                let (ground_run, clear_50_ft_obstacle) = match cell.distance {
+                    Some(distance) => (length(distance.ground_run_ft()), length(distance.clear_50_ft_obstacle_ft())),
+                    None => (String::new(), String::new())
+                };
+
+                writer.write_record([
+                    grid.mode.to_string(),
+                    format!("{:.0}", row.temperature.celsius()),
+                    format!("{:.0}", row.temperature.fahrenheit()),
+                    match card.units.pressure_unit {
+                        PressureUnit::InHg => format!("{:.2}", pressure.in_hg()),
+                        PressureUnit::Hpa => format!("{:.0}", pressure.hpa())
+                    },
+                    ground_run,
+                    clear_50_ft_obstacle,
+                    cell.exceeds_runway.to_string(),
+                    cell.climb_limited.to_string()
+                ])?;
+            }
+        }
+    }
+
+    writer.into_inner().map_err(|err| err.into_error().into())
+}
+
+fn error_response(message: String) -> Response {
+    let template = ErrorTemplate::new(message);
+    Html(template.render().unwrap()).into_response()
+}
+
+pub async fn get(RawQuery(raw_query): RawQuery, Query(params): Query<CardParameters>) -> Response {
+    let raw_query = raw_query.unwrap_or_default();
+    let mut card = match build_card(&params, format!("/card.csv?{raw_query}")) {
+        Ok(card) => card,
+        Err(message) => return error_response(message)
+    };
+
+    match record_card(&params, &card, raw_query) {
+        Ok(id) => card.history_id = Some(id),
+        Err(message) => card.history_error = Some(message)
+    }
+
+    Html(card.render().unwrap()).into_response()
+}
+
+pub async fn get_csv(Query(params): Query<CardParameters>) -> Response {
+    let card = match build_card(&params, String::new()) {
+        Ok(card) => card,
+        Err(message) => return error_response(message)
+    };
+
+    let file_name = format!("{}-{}-{}.csv", card.airport_ident, card.runway_ident, params.aircraft_type);
+    let body = write_csv(&card).expect("To write the card as CSV");
+
+    (
+        [
+            (header::CONTENT_TYPE, String::from("text/csv")),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{file_name}\""))
+        ],
+        body
+    ).into_response()
+}
@@ -0,0 +1,282 @@
+use askama::Template;
+use axum::{response::{Html, IntoResponse, Response}, Form};
+use serde::Deserialize;
+
+use crate::{
+    config,
+    data::{airports::{AirportHash, AIRPORTS}, flight_plan::{landing_weight_lbs, FlightPlan, PlannedRunway}, obstacles, performance::distance::Distance, store::{CalculationKind, CalculationRecord, STORE}},
+    math::{units::{whole_pounds, UnitChoices, Units}, Length, PressureAltitudeMethod, Temperature}
+};
+
+use super::{
+    aircraft_pages::{self, PerformanceParameters},
+    airport::load_latest_metar,
+    runway::{abort_warnings, calc_abort_point, check_obstacles, parse_metar_conditions, required_ft, runway_warnings, verdict},
+    validation::{ConditionFields, Validator},
+    empty_as_none,
+    ErrorTemplate
+};
+
+#[derive(Deserialize)]
+pub struct FlightPlanForm {
+    #[serde(default = "config::default_aircraft")]
+    aircraft_type: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    take_off_weight_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    take_off_weight_kg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    fuel_burn_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    fuel_burn_kg: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    alternate_fuel_burn_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    alternate_fuel_burn_kg: Option<f64>,
+    departure_identifier: String,
+    departure_runway: String,
+    departure_metar: Option<String>,
+    destination_identifier: String,
+    destination_runway: String,
+    destination_metar: Option<String>,
+    alternates: Option<String>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
+    #[serde(flatten)]
+    units: UnitChoices
+}
+
+impl FlightPlanForm {
+    fn into_flight_plan(self) -> Result<FlightPlan, String> {
+        let take_off_weight_lbs = whole_pounds(self.take_off_weight_lbs, self.take_off_weight_kg).ok_or("The take off weight is required in pounds or kilograms.")?;
+        let fuel_burn_lbs = whole_pounds(self.fuel_burn_lbs, self.fuel_burn_kg).unwrap_or_default();
+        let alternate_fuel_burn_lbs = whole_pounds(self.alternate_fuel_burn_lbs, self.alternate_fuel_burn_kg).unwrap_or_default();
+        let destination_landing_weight_lbs = landing_weight_lbs(take_off_weight_lbs, fuel_burn_lbs)
+            .ok_or("The fuel burn to the destination must be at least 0 and less than the take off weight.")?;
+        let alternate_landing_weight_lbs = landing_weight_lbs(destination_landing_weight_lbs, alternate_fuel_burn_lbs)
+            .ok_or("The fuel burn to the alternate must be at least 0 and less than the landing weight at the destination.")?;
+
+        Ok(FlightPlan {
+            aircraft_type: self.aircraft_type,
+            tail_number: self.tail_number,
+            take_off_weight_lbs,
+            fuel_burn_lbs,
+            alternate_fuel_burn_lbs,
+            destination_landing_weight_lbs,
+            alternate_landing_weight_lbs,
+            departure: PlannedRunway::new(&self.departure_identifier, &self.departure_runway, self.departure_metar),
+            destination: PlannedRunway::new(&self.destination_identifier, &self.destination_runway, self.destination_metar),
+            alternates: PlannedRunway::parse_list(&self.alternates.unwrap_or_default())?,
+            pressure_altitude_method: self.pressure_altitude_method
+        })
+    }
+}
+
+pub struct Leg {
+    role: &'static str,
+    is_take_off: bool,
+    identifier: String,
+    runway: String,
+    weight_lbs: i16,
+    weight: String,
+    metar: String,
+    conditions: String,
+    available: Option<String>,
+    is_go: bool,
+    verdict: String,
+    warnings: Vec<String>,
+    raw_html: String,
+    distance: Option<Distance>,
+    history_id: Option<u32>
+}
+
+#[derive(Template)]
+#[template(path = "flight_plan.html")]
+pub struct FlightPlanTemplate {
+    aircraft_name: &'static str,
+    tail_number: Option<String>,
+    take_off_weight: String,
+    fuel_burn: String,
+    alternate_fuel_burn: String,
+    legs: Vec<Leg>
+}
+
+async fn fill_leg(leg: &mut Leg, plan: &FlightPlan, planned: &PlannedRunway, units: &Units) -> Result<(), String> {
+    let aircraft_type = plan.aircraft_type.as_str();
+    let airport = AIRPORTS.load_by_identifier(&planned.identifier).ok_or(format!("{} not found", planned.identifier))?;
+    let runway_end = airport.find_open_runway_end(&planned.runway, aircraft_pages::is_seaplane(plan.tail_number.as_deref()))?;
+
+    leg.identifier = airport.ident.clone();
+    leg.metar = match &planned.metar {
+        Some(metar) => metar.clone(),
+        None => load_latest_metar(&airport.ident).await.ok_or("No METAR available")?
+    };
+
+    let conditions = parse_metar_conditions(&leg.metar, runway_end.true_heading as i16)?;
+    leg.conditions = format!("{} {:.0} kts, {}, {}",
+        if conditions.headwind.knots() >= 0.0 { "Headwind" } else { "Tailwind" },
+        conditions.headwind.knots().abs(),
+        units.temperature(conditions.temperature),
+        units.pressure(conditions.pressure));
+
+    // The conditions at each stop are checked against the charts as the runway page would, an over-gross landing included.
+    let limits = aircraft_pages::chart_limits(aircraft_type).ok_or(format!("Unknown aircraft type {aircraft_type}"))?;
+    let fields = ConditionFields { aircraft_weight_lbs: Some(leg.weight_lbs.to_string()), ..conditions.to_fields(runway_end.elevation) };
+    let mut validator = Validator::default();
+    let checked = validator.conditions(&fields);
+    validator.chart_limits(limits, leg.is_take_off, &checked, plan.pressure_altitude_method);
+    validator.finish(|| ()).map_err(|errors| errors.iter().map(|error| error.message.trim_end_matches('.')).collect::<Vec<_>>().join(". "))?;
+
+    let available_ft = if leg.is_take_off {
+        runway_end.runway.length
+    } else {
+        runway_end.runway.length.saturating_sub(runway_end.displaced_threshold)
+    };
+    leg.available = Some(units.feet(available_ft));
+
+    let performance = PerformanceParameters {
+        is_grass: runway_end.runway.is_grass,
+        elevation: Length::Feet(runway_end.elevation as f64),
+        pressure: Some(conditions.pressure),
+        pressure_altitude_method: plan.pressure_altitude_method,
+        headwind: conditions.headwind,
+        temperature: conditions.temperature,
+        standard_temperature: Temperature::standard_temperature(Length::Feet(runway_end.elevation as f64)),
+        aircraft_weight_lbs: Some(leg.weight_lbs),
+        configurations: vec![],
+        profile: aircraft_pages::load_profile(plan.tail_number.as_deref())
+    };
+
+    let result = if leg.is_take_off {
+        aircraft_pages::calc_distance_for_take_off(aircraft_type, &performance)
+    } else {
+        aircraft_pages::calc_distance_for_landing(aircraft_type, &performance)
+    };
+
+    leg.distance = result.ok();
+    leg.is_go = result.is_ok_and(|distance| required_ft(leg.is_take_off, distance).round() as i32 <= available_ft as i32);
+    leg.verdict = verdict(leg.is_take_off, result, Some(available_ft), units);
+    leg.warnings = runway_warnings(Some(runway_end.runway.lighted), Some(runway_end.runway.width), Some(runway_end.heading_source.is_inferred()), Some(conditions.crosswind.knots()), units);
+
+    if leg.is_take_off {
+        let (clearances, warnings) = check_obstacles(aircraft_type, &performance, result, Some(available_ft), &obstacles::find(&airport.ident, runway_end.ident), units);
+        leg.is_go &= clearances.iter().all(|clearance| clearance.is_cleared);
+        leg.warnings.extend(warnings);
+
+        if let Some(abort_point) = calc_abort_point(aircraft_type, &performance, result, Some(available_ft)) {
+            leg.warnings.extend(abort_warnings(&abort_point, units));
+        }
+    }
+
+    if result.is_ok() {
+        leg.raw_html = if leg.is_take_off {
+            aircraft_pages::get_raw_html_for_take_off(aircraft_type.to_string(), performance, false)
+        } else {
+            aircraft_pages::get_raw_html_for_landing(aircraft_type.to_string(), performance)
+        };
+    }
+
+    Ok(())
+}
+
+async fn calc_leg(plan: &FlightPlan, planned: &PlannedRunway, role: &'static str, is_take_off: bool, weight_lbs: i16, units: &Units) -> Leg {
+    let mut leg = Leg {
+        role,
+        is_take_off,
+        identifier: planned.identifier.clone(),
+        runway: planned.runway.clone(),
+        weight_lbs,
+        weight: units.pounds(weight_lbs),
+        metar: String::new(),
+        conditions: String::new(),
+        available: None,
+        is_go: false,
+        verdict: String::new(),
+        warnings: vec![],
+        raw_html: String::new(),
+        distance: None,
+        history_id: None
+    };
+
+    if let Err(message) = fill_leg(&mut leg, plan, planned, units).await {
+        leg.verdict = format!("NO-GO: {}.", message.trim_end_matches('.'));
+    }
+
+    match record_leg(plan, &leg) {
+        Ok(id) => leg.history_id = Some(id),
+        Err(message) => leg.warnings.push(message)
+    }
+
+    leg
+}
+
+/// Each leg is recorded with the inputs it was calculated from, the METAR included as it gave the conditions.
+fn record_leg(plan: &FlightPlan, leg: &Leg) -> Result<u32, String> {
+    let query = serde_urlencoded::to_string([
+        ("role", leg.role.to_string()),
+        ("identifier", leg.identifier.clone()),
+        ("runway", leg.runway.clone()),
+        ("aircraft_weight_lbs", leg.weight_lbs.to_string()),
+        ("metar", leg.metar.clone())
+    ]).unwrap_or_default();
+
+    let record = CalculationRecord {
+        id: 0,
+        timestamp: 0,
+        kind: CalculationKind::FlightPlan,
+        aircraft_type: plan.aircraft_type.clone(),
+        tail_number: plan.tail_number.clone(),
+        pilot: None,
+        is_take_off: leg.is_take_off,
+        query,
+        ground_run_ft: leg.distance.map(|distance| distance.ground_run()),
+        clear_50_ft_obstacle_ft: leg.distance.map(|distance| distance.clear_50_ft_obstacle()),
+        verdict: leg.verdict.clone()
+    };
+
+    STORE.lock().unwrap().record_calculation(record)
+}
+
+pub async fn calc_flight_plan(plan: &FlightPlan, units: &Units) -> Vec<Leg> {
+    let mut legs = vec![
+        calc_leg(plan, &plan.departure, "Departure", true, plan.take_off_weight_lbs, units).await,
+        calc_leg(plan, &plan.destination, "Destination", false, plan.destination_landing_weight_lbs, units).await
+    ];
+
+    for alternate in &plan.alternates {
+        legs.push(calc_leg(plan, alternate, "Alternate", false, plan.alternate_landing_weight_lbs, units).await);
+    }
+
+    legs
+}
+
+fn error_response(message: String) -> Response {
+    let template = ErrorTemplate::new(message);
+    Html(template.render().unwrap()).into_response()
+}
+
+pub async fn post(Form(form): Form<FlightPlanForm>) -> Response {
+    let units = form.units.or(config::default_units());
+    let plan = match form.into_flight_plan() {
+        Ok(plan) => plan,
+        Err(message) => return error_response(message)
+    };
+
+    let aircraft_name = match aircraft_pages::aircraft_name(&plan.aircraft_type) {
+        Some(aircraft_name) => aircraft_name,
+        None => return error_response(format!("Unknown aircraft type {}.", plan.aircraft_type))
+    };
+
+    let template = FlightPlanTemplate {
+        aircraft_name,
+        tail_number: plan.tail_number.clone(),
+        take_off_weight: units.pounds(plan.take_off_weight_lbs),
+        fuel_burn: units.pounds(plan.fuel_burn_lbs),
+        alternate_fuel_burn: units.pounds(plan.alternate_fuel_burn_lbs),
+        legs: calc_flight_plan(&plan, &units).await
+    };
+
+    Html(template.render().unwrap()).into_response()
+}
@@ -0,0 +1,30 @@
+use askama::Template;
+use axum::{extract::Path, response::{IntoResponse, Response}};
+
+use crate::data::store::{CalculationKind, CalculationRecord, STORE};
+
+use super::{ErrorTemplate, HtmlTemplate};
+
+#[derive(Template)]
+#[template(path = "history.html")]
+pub struct HistoryTemplate {
+    history: Vec<CalculationRecord>
+}
+
+#[derive(Template)]
+#[template(path = "history_entry.html")]
+pub struct HistoryEntryTemplate {
+    record: CalculationRecord
+}
+
+pub async fn get() -> Response {
+    let history = STORE.lock().unwrap().history.iter().rev().cloned().collect();
+    HtmlTemplate(HistoryTemplate { history }).into_response()
+}
+
+pub async fn get_entry(Path(id): Path<u32>) -> Response {
+    match STORE.lock().unwrap().find_calculation(id) {
+        Some(record) => HtmlTemplate(HistoryEntryTemplate { record: record.clone() }).into_response(),
+        None => HtmlTemplate(ErrorTemplate::new(format!("Calculation {id} not found."))).into_response()
+    }
+}
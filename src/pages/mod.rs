@@ -1,12 +1,23 @@
+use std::{fmt::Display, str::FromStr};
+
 use askama::Template;
 
 use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Response}
 };
+use serde::{de, Deserialize, Deserializer};
+
+use validation::FieldError;
 
 pub mod airport;
+pub mod card;
+pub mod flight_plan;
+pub mod history;
+pub mod performance;
+pub mod profiles;
 pub mod runway;
+pub mod validation;
 
 pub mod aircraft_pages;
 
@@ -35,13 +46,34 @@ pub struct ToPageTemplate {
 #[derive(Template)]
 #[template(path = "error.html")]
 pub struct ErrorTemplate {
-    message: String
+    message: String,
+    details: Vec<String>
 }
 
 impl ErrorTemplate{
     pub fn new(message: String) -> Self {
         ErrorTemplate {
-            message
+            message,
+            details: vec![]
+        }
+    }
+
+    pub fn for_fields(errors: &[FieldError]) -> Self {
+        ErrorTemplate {
+            message: String::from("Unable to compute, please correct the following:"),
+            details: errors.iter().map(|error| format!("{}: {}", error.field, error.message)).collect()
         }
     }
-}
\ No newline at end of file
+}
+
+/// Browsers submit blank form fields as empty strings, treat those as missing rather than as a parse error.
+pub fn empty_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error> where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display
+{
+    match Option::<String>::deserialize(de)?.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use axum::{extract::RawQuery, response::{IntoResponse, Response}};
+
+use crate::config;
+
+use super::{runway::{RunwayParameters, RunwayQuery}, validation::FieldError, ErrorTemplate, HtmlTemplate};
+
+/// The direct entry form, drawn again with what was submitted and a message beside each field that needs correcting.
+#[derive(Template)]
+#[template(path = "performance.html")]
+pub struct PerformanceTemplate {
+    values: HashMap<String, String>,
+    errors: Vec<FieldError>,
+    runway_href: Option<String>
+}
+
+impl PerformanceTemplate {
+    fn value(&self, field: &str) -> &str {
+        self.values.get(field).map(String::as_str).unwrap_or_default()
+    }
+
+    fn is_selected(&self, field: &str, value: &str) -> bool {
+        self.value(field) == value
+    }
+
+    fn error(&self, field: &str) -> String {
+        self.errors.iter()
+            .filter(|error| error.field == field)
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The configured aircraft and display units are preselected.
+fn default_values() -> HashMap<String, String> {
+    let units = config::default_units();
+    let unit = |unit: serde_json::Value| unit.as_str().map(String::from).unwrap_or_default();
+
+    [("aircraft_weight_lbs", String::from("1600")), ("pressure_in_hg", String::from("29.92")), ("is_take_off", String::from("true")),
+        ("aircraft_type", config::default_aircraft()),
+        ("pressure_unit", unit(serde_json::json!(units.pressure_unit))),
+        ("length_unit", unit(serde_json::json!(units.length_unit))),
+        ("temperature_unit", unit(serde_json::json!(units.temperature_unit))),
+        ("weight_unit", unit(serde_json::json!(units.weight_unit)))]
+        .into_iter()
+        .map(|(field, value)| (field.to_string(), value))
+        .collect()
+}
+
+pub async fn get(RawQuery(query): RawQuery) -> Response {
+    let Some(query) = query.filter(|query| !query.is_empty()) else {
+        return HtmlTemplate(PerformanceTemplate { values: default_values(), errors: vec![], runway_href: None }).into_response();
+    };
+
+    let runway_query: RunwayQuery = match serde_urlencoded::from_str(&query) {
+        Ok(runway_query) => runway_query,
+        Err(err) => return HtmlTemplate(ErrorTemplate::new(format!("Invalid performance query: {err}"))).into_response()
+    };
+    let values = serde_urlencoded::from_str::<Vec<(String, String)>>(&query).unwrap_or_default().into_iter().collect();
+
+    let template = match RunwayParameters::try_from(&runway_query) {
+        Ok(_) => PerformanceTemplate { values, errors: vec![], runway_href: Some(format!("/runway?{query}")) },
+        Err(errors) => PerformanceTemplate { values, errors, runway_href: None }
+    };
+
+    HtmlTemplate(template).into_response()
+}
@@ -0,0 +1,140 @@
+use askama::Template;
+use axum::{response::{IntoResponse, Redirect, Response}, Form};
+use serde::Deserialize;
+
+use crate::data::{performance::aircraft::profile::{is_valid_factor_percentage, AircraftProfile}, store::{AircraftRecord, PilotProfile, STORE}};
+
+use super::{aircraft_pages, empty_as_none, validation::FieldError, ErrorTemplate, HtmlTemplate};
+
+#[derive(Template)]
+#[template(path = "fleet.html")]
+pub struct FleetTemplate {
+    aircraft: Vec<AircraftRecord>
+}
+
+#[derive(Template)]
+#[template(path = "pilots.html")]
+pub struct PilotsTemplate {
+    pilots: Vec<PilotProfile>
+}
+
+#[derive(Deserialize)]
+pub struct AircraftForm {
+    tail_number: String,
+    aircraft_type: String,
+    empty_weight_lbs: i16,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    empty_cg_in: Option<f32>,
+    equipment: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    take_off_factor_percentage: Option<f64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    landing_factor_percentage: Option<f64>,
+    modifications: Option<String>,
+    stc_notes: Option<String>,
+    is_seaplane: Option<bool>
+}
+
+#[derive(Deserialize)]
+pub struct PilotForm {
+    name: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    max_crosswind_kts: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    max_tailwind_kts: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    min_runway_length_ft: Option<u16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    min_runway_width_ft: Option<u16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    max_narrow_runway_crosswind_kts: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    runway_margin_percentage: Option<f64>
+}
+
+#[derive(Deserialize)]
+pub struct DeleteForm {
+    key: String
+}
+
+fn saved_or_error(result: Result<(), String>, redirect_to: &str) -> Response {
+    match result {
+        Ok(()) => Redirect::to(redirect_to).into_response(),
+        Err(message) => HtmlTemplate(ErrorTemplate::new(message)).into_response()
+    }
+}
+
+pub async fn get_fleet() -> Response {
+    let aircraft = STORE.lock().unwrap().aircraft.clone();
+    HtmlTemplate(FleetTemplate { aircraft }).into_response()
+}
+
+pub async fn post_fleet(Form(form): Form<AircraftForm>) -> Response {
+    let tail_number = form.tail_number.trim().to_uppercase();
+    if tail_number.is_empty() {
+        return HtmlTemplate(ErrorTemplate::new(String::from("A tail number is required."))).into_response();
+    }
+
+    if aircraft_pages::aircraft_name(&form.aircraft_type).is_none() {
+        return HtmlTemplate(ErrorTemplate::new(format!("Unknown aircraft type {}.", form.aircraft_type))).into_response();
+    }
+
+    let take_off_factor_percentage = form.take_off_factor_percentage.unwrap_or_default();
+    let landing_factor_percentage = form.landing_factor_percentage.unwrap_or_default();
+    let errors: Vec<FieldError> = [("take_off_factor_percentage", take_off_factor_percentage), ("landing_factor_percentage", landing_factor_percentage)].into_iter()
+        .filter(|(_, factor_percentage)| !is_valid_factor_percentage(*factor_percentage))
+        .map(|(field, factor_percentage)| FieldError { field, message: format!("A factor of {factor_percentage}% is not allowed, it must be 0% or more to add to the book distances.") })
+        .collect();
+    if !errors.is_empty() {
+        return HtmlTemplate(ErrorTemplate::for_fields(&errors)).into_response();
+    }
+
+    let record = AircraftRecord {
+        profile: AircraftProfile {
+            tail_number,
+            take_off_factor_percentage,
+            landing_factor_percentage,
+            modifications: form.modifications.unwrap_or_default(),
+            stc_notes: form.stc_notes.unwrap_or_default(),
+            is_seaplane: form.is_seaplane.unwrap_or_default()
+        },
+        aircraft_type: form.aircraft_type,
+        empty_weight_lbs: form.empty_weight_lbs,
+        empty_cg_in: form.empty_cg_in.unwrap_or_default(),
+        equipment: form.equipment.unwrap_or_default()
+    };
+
+    saved_or_error(STORE.lock().unwrap().save_aircraft(record), "/fleet")
+}
+
+pub async fn delete_fleet(Form(form): Form<DeleteForm>) -> Response {
+    saved_or_error(STORE.lock().unwrap().delete_aircraft(&form.key), "/fleet")
+}
+
+pub async fn get_pilots() -> Response {
+    let pilots = STORE.lock().unwrap().pilots.clone();
+    HtmlTemplate(PilotsTemplate { pilots }).into_response()
+}
+
+pub async fn post_pilots(Form(form): Form<PilotForm>) -> Response {
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        return HtmlTemplate(ErrorTemplate::new(String::from("A pilot name is required."))).into_response();
+    }
+
+    let pilot = PilotProfile {
+        name,
+        max_crosswind_kts: form.max_crosswind_kts,
+        max_tailwind_kts: form.max_tailwind_kts,
+        min_runway_length_ft: form.min_runway_length_ft,
+        min_runway_width_ft: form.min_runway_width_ft,
+        max_narrow_runway_crosswind_kts: form.max_narrow_runway_crosswind_kts,
+        runway_margin_percentage: form.runway_margin_percentage
+    };
+
+    saved_or_error(STORE.lock().unwrap().save_pilot(pilot), "/pilots")
+}
+
+pub async fn delete_pilots(Form(form): Form<DeleteForm>) -> Response {
+    saved_or_error(STORE.lock().unwrap().delete_pilot(&form.key), "/pilots")
+}
@@ -1,32 +1,174 @@
 use askama::Template;
-use axum::{extract::Query, response::{Html, IntoResponse}, Form};
-use serde::Deserialize;
+use axum::{extract::Query, http::header, response::{Html, IntoResponse, Response}, Form};
+use serde::{Deserialize, Serialize};
 
-use crate::math::{wind::WindCalcs, Pressure, Temperature, Velocity};
+use crate::{
+    config,
+    data::{airports::NARROW_RUNWAY_WIDTH_FT, obstacles::{self, Obstacle}, performance::{abort::AbortPoint, distance::Distance, error::PerformanceError}, store::{CalculationKind, CalculationRecord, STORE}},
+    math::{units::{UnitChoices, Units}, wind::WindCalcs, Length, Pressure, PressureAltitudeMethod, Temperature, Velocity},
+    pdf::Document
+};
 
-use super::{aircraft_pages::{self, PerformanceParameters}, ToPageTemplate};
+use super::{aircraft_pages::{self, ConfigurationFields, PerformanceParameters, SpeedSummary}, empty_as_none, validation::{ConditionFields, FieldError, Validator}, ErrorTemplate, HtmlTemplate, ToPageTemplate};
 
+/// The runway query as submitted, where each measurement may be given in either imperial or metric units.
 #[derive(Deserialize)]
+pub struct RunwayQuery {
+    #[serde(default = "config::default_aircraft")]
+    aircraft_type: String,
+    is_take_off: Option<bool>,
+    is_grass: Option<bool>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
+    runway_length_ft: Option<String>,
+    runway_length_m: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    crosswind_kts: Option<f64>,
+    is_lighted: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    runway_width_ft: Option<u16>,
+    is_heading_inferred: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    airport_ident: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    runway_ident: Option<String>,
+    /// Obstacles on the departure path as `height@distance` in feet past the runway end.
+    #[serde(default, deserialize_with = "empty_as_none")]
+    obstacles: Option<String>,
+    metar: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pilot: Option<String>,
+    #[serde(flatten)]
+    configuration: ConfigurationFields,
+    #[serde(flatten)]
+    conditions: ConditionFields,
+    #[serde(flatten)]
+    units: UnitChoices
+}
+
+/// The runway query with every measurement converted to the imperial units of the charts.
+#[derive(Serialize)]
 pub struct RunwayParameters {
     aircraft_type: String,
     is_take_off: Option<bool>,
     is_grass: Option<bool>,
-    headwind_kts: i16,
-    pressure_in_hg: Option<f32>,
-    temperature_f: i16,
+    headwind_kts: f64,
+    pressure_in_hg: Option<f64>,
+    pressure_altitude_method: PressureAltitudeMethod,
+    temperature_f: f64,
     elevation_ft: i16,
-    aircraft_weight_lbs: Option<i16>
+    aircraft_weight_lbs: Option<i16>,
+    runway_length_ft: Option<u16>,
+    crosswind_kts: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_lighted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runway_width_ft: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_heading_inferred: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    airport_ident: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runway_ident: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obstacles: Option<String>,
+    metar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tail_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pilot: Option<String>,
+    #[serde(flatten)]
+    configuration: ConfigurationFields,
+    /// Only the units chosen are carried on, so a link keeps following the configured default for the rest.
+    #[serde(flatten)]
+    unit_choices: UnitChoices,
+    #[serde(skip)]
+    units: Units
+}
+
+impl TryFrom<&RunwayQuery> for RunwayParameters {
+    type Error = Vec<FieldError>;
+
+    fn try_from(query: &RunwayQuery) -> Result<Self, Self::Error> {
+        let mut validator = Validator::default();
+        let is_take_off = query.is_take_off.unwrap_or_default();
+        let conditions = validator.conditions(&query.conditions);
+        let runway_length = validator.runway_length(&query.runway_length_ft, &query.runway_length_m);
+        if let Err(message) = obstacles::parse_list(query.obstacles.as_deref().unwrap_or_default()) {
+            validator.error("obstacles", message);
+        }
+        aircraft_pages::select_configurations(&mut validator, &query.aircraft_type, is_take_off, &query.configuration);
+
+        match aircraft_pages::chart_limits(&query.aircraft_type) {
+            Some(limits) => validator.chart_limits(limits, is_take_off, &conditions, query.pressure_altitude_method),
+            None => validator.error("aircraft_type", format!("Unknown aircraft type {}.", query.aircraft_type))
+        }
+
+        let (Some(elevation), Some(temperature), Some(headwind)) = (conditions.elevation, conditions.temperature, conditions.headwind) else {
+            return Err(validator.errors);
+        };
+
+        validator.finish(|| RunwayParameters {
+            aircraft_type: query.aircraft_type.clone(),
+            is_take_off: query.is_take_off,
+            is_grass: query.is_grass,
+            headwind_kts: headwind.value.knots(),
+            pressure_in_hg: conditions.pressure.map(|pressure| pressure.value.in_hg()),
+            pressure_altitude_method: query.pressure_altitude_method,
+            temperature_f: temperature.value.fahrenheit(),
+            elevation_ft: elevation.value.feet().round() as i16,
+            aircraft_weight_lbs: conditions.aircraft_weight.map(|weight| weight.value.pounds().round() as i16),
+            runway_length_ft: runway_length.map(|length| length.feet().round() as u16),
+            crosswind_kts: query.crosswind_kts,
+            is_lighted: query.is_lighted,
+            runway_width_ft: query.runway_width_ft,
+            is_heading_inferred: query.is_heading_inferred,
+            airport_ident: query.airport_ident.clone(),
+            runway_ident: query.runway_ident.clone(),
+            obstacles: query.obstacles.clone(),
+            metar: query.metar.clone(),
+            tail_number: query.tail_number.clone(),
+            pilot: query.pilot.clone(),
+            configuration: query.configuration.clone(),
+            unit_choices: query.units,
+            units: query.units.or(config::default_units())
+        })
+    }
 }
 
 #[derive(Deserialize)]
 pub struct RunwayConfig {
+    #[serde(default = "config::default_aircraft")]
     aircraft_type: String,
     is_take_off: Option<bool>,
     is_grass: Option<bool>,
     metar: String,
+    /// The true heading, METAR winds are reported from true north.
     heading: i16,
     elevation_ft: i16,
-    aircraft_weight_lbs: Option<i16>
+    length: Option<u16>,
+    is_lighted: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    width: Option<u16>,
+    is_heading_inferred: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    airport_ident: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    runway_ident: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_lbs: Option<i16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    aircraft_weight_kg: Option<f64>,
+    #[serde(default)]
+    pressure_altitude_method: PressureAltitudeMethod,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    tail_number: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pilot: Option<String>,
+    #[serde(flatten)]
+    units: UnitChoices
 }
 
 #[derive(Template)]
@@ -35,45 +177,293 @@ pub struct RunwayTemplate {
     mode: String,
     surface: String,
     is_grass: bool,
-    elevation_ft: i16,
-    headwind_kts: i16,
-    standard_temperature_f: i16,
-    temperature_f: i16,
-    temperature_diff_from_standard: i16
+    elevation: String,
+    headwind_kts: f64,
+    sea_level_standard_temperature: String,
+    standard_lapse_rate: String,
+    standard_lapse_interval: String,
+    standard_temperature: String,
+    temperature: String,
+    temperature_diff_from_standard: String,
+    pressure: Option<String>,
+    tail_number: Option<String>,
+    pilot: Option<String>,
+    configurations: Vec<String>,
+    speeds: Option<SpeedSummary>,
+    verdict: String,
+    warnings: Vec<String>,
+    clearances: Vec<ObstacleClearance>,
+    abort_marker: Option<AbortMarker>,
+    history_id: Option<u32>,
+    pdf_href: String
+}
+
+/// A row of the obstacle clearance table.
+pub struct ObstacleClearance {
+    pub name: String,
+    pub height: String,
+    pub distance: String,
+    pub aircraft_height: String,
+    pub margin: String,
+    pub is_cleared: bool
+}
+
+/// The abort point as drawn on the runway diagram, positions are percentages of the runway length.
+pub struct AbortMarker {
+    abort_speed: String,
+    marker: String,
+    expected: String,
+    ground_run: String,
+    stop: String,
+    accelerate_stop: String,
+    runway_length: String,
+    is_abort_speed_reached: bool,
+    is_accelerate_stop_within_runway: bool,
+    marker_percentage: String,
+    expected_percentage: String,
+    ground_run_percentage: String,
+    accelerate_stop_percentage: String
+}
+
+impl AbortMarker {
+    fn new(abort_point: &AbortPoint, units: &Units) -> Self {
+        let percentage = |distance_ft: f64| format!("{:.1}", (100.0 * distance_ft / abort_point.runway_length_ft).min(100.0));
+
+        AbortMarker {
+            abort_speed: format!("{:.0} kts", abort_point.abort_speed_kias),
+            marker: units.feet(abort_point.marker_ft),
+            expected: units.feet(abort_point.expected_ft),
+            ground_run: units.feet(abort_point.ground_run_ft),
+            stop: units.feet(abort_point.stop_ft),
+            accelerate_stop: units.feet(abort_point.accelerate_stop_ft()),
+            runway_length: units.feet(abort_point.runway_length_ft),
+            is_abort_speed_reached: abort_point.is_abort_speed_reached(),
+            is_accelerate_stop_within_runway: abort_point.is_accelerate_stop_within_runway(),
+            marker_percentage: percentage(abort_point.marker_ft),
+            expected_percentage: percentage(abort_point.expected_ft),
+            ground_run_percentage: percentage(abort_point.ground_run_ft),
+            accelerate_stop_percentage: percentage(abort_point.accelerate_stop_ft())
+        }
+    }
+}
+
+fn mode(is_take_off: bool) -> String {
+    if is_take_off { "Take Off".to_string() } else { "Landing".to_string() }
+}
+
+fn surface(is_grass: bool) -> String {
+    if is_grass { "grass".to_string() } else { "pavement".to_string() }
+}
+
+fn to_performance_parameters(params: &RunwayParameters) -> PerformanceParameters {
+    let is_take_off = params.is_take_off.unwrap_or_default();
+    PerformanceParameters {
+        headwind: Velocity::Knots(params.headwind_kts),
+        pressure: params.pressure_in_hg.map(Pressure::InchesOfMercury),
+        pressure_altitude_method: params.pressure_altitude_method,
+        temperature: Temperature::Fahrenheit(params.temperature_f),
+        elevation: Length::Feet(params.elevation_ft as f64),
+        standard_temperature: Temperature::standard_temperature(Length::Feet(params.elevation_ft as f64)),
+        is_grass: params.is_grass.unwrap_or_default(),
+        aircraft_weight_lbs: params.aircraft_weight_lbs,
+        configurations: aircraft_pages::select_configurations(&mut Validator::default(), &params.aircraft_type, is_take_off, &params.configuration),
+        profile: aircraft_pages::load_profile(params.tail_number.as_deref())
+    }
+}
+
+fn calc_distance(params: &RunwayParameters, performance: &PerformanceParameters) -> Result<Distance, PerformanceError> {
+    if params.is_take_off.unwrap_or_default() {
+        aircraft_pages::calc_distance_for_take_off(&params.aircraft_type, performance)
+    } else {
+        aircraft_pages::calc_distance_for_landing(&params.aircraft_type, performance)
+    }
+}
+
+/// What the runway data says about the runway itself, whoever is flying.
+pub fn runway_warnings(is_lighted: Option<bool>, width_ft: Option<u16>, is_heading_inferred: Option<bool>, crosswind_kts: Option<f64>, units: &Units) -> Vec<String> {
+    let mut warnings = vec![];
+
+    if is_heading_inferred == Some(true) {
+        warnings.push(String::from("The runway heading was inferred rather than surveyed, check the wind components against a chart."));
+    }
+
+    if is_lighted == Some(false) {
+        warnings.push(String::from("The runway is not lighted, it can only be used in daylight."));
+    }
+
+    if let Some(width_ft) = width_ft.filter(|width_ft| *width_ft > 0 && *width_ft < NARROW_RUNWAY_WIDTH_FT) {
+        match crosswind_kts.map(f64::abs).filter(|crosswind_kts| crosswind_kts.round() > 0.0) {
+            Some(crosswind_kts) => warnings.push(format!("The runway is only {} wide, leaving less room to correct for the {crosswind_kts:.0} kts crosswind.", units.feet(width_ft))),
+            None => warnings.push(format!("The runway is only {} wide.", units.feet(width_ft)))
+        }
+    }
+
+    warnings
+}
+
+/// The obstacles in the obstacle file for the runway end followed by those entered with the calculation.
+fn departure_obstacles(params: &RunwayParameters) -> Vec<Obstacle> {
+    let mut departure_obstacles = match (&params.airport_ident, &params.runway_ident) {
+        (Some(airport_ident), Some(runway_ident)) => obstacles::find(airport_ident, runway_ident),
+        _ => vec![]
+    };
+    departure_obstacles.extend(obstacles::parse_list(params.obstacles.as_deref().unwrap_or_default()).unwrap_or_default());
+
+    departure_obstacles
+}
+
+/// Whether the aircraft out-climbs each obstacle past the departure end, with a warning for each one it does not.
+pub fn check_obstacles(aircraft_type: &str, performance: &PerformanceParameters, result: Result<Distance, PerformanceError>, runway_length_ft: Option<u16>, obstacles: &[Obstacle], units: &Units) -> (Vec<ObstacleClearance>, Vec<String>) {
+    let (Ok(distance), false) = (result, obstacles.is_empty()) else {
+        return (vec![], vec![]);
+    };
+    let Some(runway_length_ft) = runway_length_ft else {
+        return (vec![], vec![String::from("Obstacle clearance is measured from the runway end, no runway length was provided so the obstacles were not checked.")]);
+    };
+
+    let clearances = match aircraft_pages::calc_obstacle_clearances(aircraft_type, performance, distance, runway_length_ft as f64, obstacles) {
+        Ok(clearances) => clearances,
+        Err(error) => return (vec![], vec![format!("The climb performance is not available to check the obstacles: {error}.")])
+    };
+
+    let warnings = clearances.iter()
+        .filter(|clearance| !clearance.is_cleared())
+        .map(|clearance| format!("Cannot out-climb {} {} tall {} past the runway end, the aircraft is only {} up by then.",
+            if clearance.obstacle.name.is_empty() { String::from("the obstacle") } else { clearance.obstacle.name.clone() },
+            units.feet(clearance.obstacle.height_ft),
+            units.feet(clearance.obstacle.distance_ft),
+            units.feet(clearance.aircraft_height_ft)))
+        .collect();
+
+    let rows = clearances.into_iter()
+        .map(|clearance| ObstacleClearance {
+            is_cleared: clearance.is_cleared(),
+            name: clearance.obstacle.name,
+            height: units.feet(clearance.obstacle.height_ft),
+            distance: units.feet(clearance.obstacle.distance_ft),
+            aircraft_height: units.feet(clearance.aircraft_height_ft),
+            margin: units.feet(clearance.margin_ft)
+        })
+        .collect();
+
+    (rows, warnings)
+}
+
+/// Where to abandon a take off from the start of the runway, stopping in the landing ground roll for the same conditions.
+pub fn calc_abort_point(aircraft_type: &str, performance: &PerformanceParameters, result: Result<Distance, PerformanceError>, runway_length_ft: Option<u16>) -> Option<AbortPoint> {
+    let take_off = result.ok()?;
+    let landing = aircraft_pages::calc_distance_for_landing(aircraft_type, performance).ok()?;
+    Some(AbortPoint::new(aircraft_pages::rotation_speed_kias(aircraft_type, performance.aircraft_weight_lbs)?, runway_length_ft? as f64, take_off, landing))
+}
+
+pub fn abort_warnings(abort_point: &AbortPoint, units: &Units) -> Vec<String> {
+    let mut warnings = vec![];
+
+    if !abort_point.is_abort_speed_reached() {
+        warnings.push(format!("The ground run only reaches {:.0} kts after {}, past the {} runway midpoint where the take off should be abandoned without it.",
+            abort_point.abort_speed_kias, units.feet(abort_point.expected_ft), units.feet(abort_point.marker_ft)));
+    }
+
+    if !abort_point.is_accelerate_stop_within_runway() {
+        warnings.push(format!("Accelerating to rotation and stopping again takes {}, more than the {} runway.",
+            units.feet(abort_point.accelerate_stop_ft()), units.feet(abort_point.runway_length_ft)));
+    }
+
+    warnings
+}
+
+fn pilot_warnings(params: &RunwayParameters, result: Result<Distance, PerformanceError>) -> Vec<String> {
+    let Some(name) = &params.pilot else {
+        return vec![];
+    };
+
+    match STORE.lock().unwrap().find_pilot(name) {
+        Some(pilot) => pilot.check_minimums(params.headwind_kts, params.crosswind_kts, params.runway_length_ft, params.runway_width_ft, result.ok().map(|distance| distance.clear_50_ft_obstacle()), &params.units),
+        None => vec![format!("No profile saved for pilot {name}, personal minimums were not checked.")]
+    }
+}
+
+fn record_calculation(params: &RunwayParameters, result: Result<Distance, PerformanceError>) -> Result<u32, String> {
+    let is_take_off = params.is_take_off.unwrap_or_default();
+    let record = CalculationRecord {
+        id: 0,
+        timestamp: 0,
+        kind: CalculationKind::Runway,
+        aircraft_type: params.aircraft_type.clone(),
+        tail_number: params.tail_number.clone(),
+        pilot: params.pilot.clone(),
+        is_take_off,
+        query: serde_urlencoded::to_string(params).unwrap_or_default(),
+        ground_run_ft: result.ok().map(|distance| distance.ground_run()),
+        clear_50_ft_obstacle_ft: result.ok().map(|distance| distance.clear_50_ft_obstacle()),
+        verdict: verdict(is_take_off, result, params.runway_length_ft, &params.units)
+    };
+
+    STORE.lock().unwrap().record_calculation(record)
 }
 
-async fn get_response(params: RunwayParameters, start_landing_flow: bool) -> impl IntoResponse {
+/// Only a submitted form is recorded, reloading a result or following a link to it is not another calculation.
+async fn get_response(params: RunwayParameters, start_landing_flow: bool, is_recorded: bool) -> impl IntoResponse {
     let is_take_off = params.is_take_off.unwrap_or_default();
     let is_grass = params.is_grass.unwrap_or_default();
 
-    let standard_temperature_f = Temperature::standard_temperature(params.elevation_ft).fahrenheit();
+    // Built once, each build takes the store lock to load the profile.
+    let performance = to_performance_parameters(&params);
+    let result = calc_distance(&params, &performance);
+    let mut warnings = runway_warnings(params.is_lighted, params.runway_width_ft, params.is_heading_inferred, params.crosswind_kts, &params.units);
+    let (clearances, obstacle_warnings) = if is_take_off {
+        check_obstacles(&params.aircraft_type, &performance, result, params.runway_length_ft, &departure_obstacles(&params), &params.units)
+    } else {
+        (vec![], vec![])
+    };
+    warnings.extend(obstacle_warnings);
+    let abort_point = calc_abort_point(&params.aircraft_type, &performance, result, params.runway_length_ft).filter(|_| is_take_off);
+    if let Some(abort_point) = &abort_point {
+        warnings.extend(abort_warnings(abort_point, &params.units));
+    }
+    warnings.extend(pilot_warnings(&params, result));
+    let history_id = match is_recorded.then(|| record_calculation(&params, result)) {
+        Some(Ok(id)) => Some(id),
+        Some(Err(message)) => {
+            warnings.push(message);
+            None
+        },
+        None => None
+    };
+
+    let units = params.units;
+    let standard_temperature_f = Temperature::standard_temperature(Length::Feet(params.elevation_ft as f64)).fahrenheit();
 
-    let mode = if is_take_off { "Take Off".to_string() } else { "Landing".to_string() };
+    let mode = mode(is_take_off);
     let page_title = format!("{mode} Performance");
 
     let template = RunwayTemplate {
         mode,
-        surface: if is_grass { "grass".to_string() } else { "pavement".to_string() },
+        surface: surface(is_grass),
         is_grass,
-        elevation_ft: params.elevation_ft,
+        elevation: units.feet(params.elevation_ft),
         headwind_kts: params.headwind_kts,
-        standard_temperature_f,
-        temperature_f: params.temperature_f,
-        temperature_diff_from_standard: params.temperature_f - standard_temperature_f
+        sea_level_standard_temperature: units.temperature(Temperature::Fahrenheit(59.0)),
+        standard_lapse_rate: units.standard_lapse_rate(),
+        standard_lapse_interval: units.standard_lapse_interval(),
+        standard_temperature: units.temperature(Temperature::Fahrenheit(standard_temperature_f)),
+        temperature: units.temperature(Temperature::Fahrenheit(params.temperature_f)),
+        temperature_diff_from_standard: units.temperature_difference_f(params.temperature_f - standard_temperature_f),
+        pressure: params.pressure_in_hg.map(|in_hg| units.pressure(Pressure::InchesOfMercury(in_hg))),
+        tail_number: params.tail_number.clone(),
+        pilot: params.pilot.clone(),
+        configurations: aircraft_pages::describe_configurations(&params.aircraft_type, is_take_off, &performance.configurations),
+        speeds: aircraft_pages::speed_summary(&params.aircraft_type, params.aircraft_weight_lbs, &units),
+        verdict: verdict(is_take_off, result, params.runway_length_ft, &units),
+        warnings,
+        clearances,
+        abort_marker: abort_point.map(|abort_point| AbortMarker::new(&abort_point, &units)),
+        history_id,
+        pdf_href: format!("/runway.pdf?{}", serde_urlencoded::to_string(&params).unwrap_or_default())
     };
 
     let runway_raw_html = template.render().unwrap();
 
-    let performance = PerformanceParameters {
-        headwind: Velocity::Knots(params.headwind_kts),
-        pressure: match params.pressure_in_hg { Some(in_hg) => Some(Pressure::InchesOfMercury(in_hg)), None => None },
-        temperature: Temperature::Fahrenheit(params.temperature_f),
-        elevation_ft: params.elevation_ft,
-        standard_temperature: Temperature::Fahrenheit(standard_temperature_f),
-        is_grass,
-        aircraft_weight_lbs: params.aircraft_weight_lbs
-    };
-
     let aircraft_raw_html = if is_take_off {
         aircraft_pages::get_raw_html_for_take_off(params.aircraft_type, performance, start_landing_flow)
     } else {
@@ -88,32 +478,229 @@ async fn get_response(params: RunwayParameters, start_landing_flow: bool) -> imp
     Html(page.render().unwrap()).into_response()
 }
 
-pub async fn get(Query(parameters): Query<RunwayParameters>) -> impl IntoResponse {
-    get_response(parameters, false).await
+/// The length of runway needed, the book distance to clear a 50' obstacle multiplied by the configured safety factor.
+pub fn required_ft(is_take_off: bool, distance: Distance) -> f64 {
+    distance.clear_50_ft_obstacle_ft() * config::get().safety_factor(is_take_off)
+}
+
+pub fn verdict(is_take_off: bool, result: Result<Distance, PerformanceError>, runway_length_ft: Option<u16>, units: &Units) -> String {
+    let obstacle = units.length(Length::Feet(50.0));
+    let factor = config::get().safety_factor(is_take_off);
+    let required = |distance: Distance| if factor == 1.0 {
+        units.feet(distance.clear_50_ft_obstacle_ft())
+    } else {
+        format!("{} ({} with the {factor} safety factor)", units.feet(distance.clear_50_ft_obstacle_ft()), units.feet(required_ft(is_take_off, distance)))
+    };
+
+    match (result, runway_length_ft) {
+        (Err(message), _) => format!("NO-GO: {message}."),
+        (Ok(distance), Some(runway_length_ft)) if required_ft(is_take_off, distance).round() as i32 <= runway_length_ft as i32 => {
+            format!("GO: {} to clear a {obstacle} obstacle is within the {} runway.", required(distance), units.feet(runway_length_ft))
+        },
+        (Ok(distance), Some(runway_length_ft)) => {
+            format!("NO-GO: {} to clear a {obstacle} obstacle exceeds the {} runway.", required(distance), units.feet(runway_length_ft))
+        },
+        (Ok(distance), None) => {
+            let mode = mode(is_take_off);
+            format!("{mode} requires {} of ground run and {} to clear a {obstacle} obstacle. No runway length was provided to compare against.", units.feet(distance.ground_run_ft()), required(distance))
+        }
+    }
+}
+
+pub fn render_pdf(params: &RunwayParameters) -> Vec<u8> {
+    let is_take_off = params.is_take_off.unwrap_or_default();
+    let is_grass = params.is_grass.unwrap_or_default();
+    let aircraft_name = aircraft_pages::aircraft_name(&params.aircraft_type).unwrap_or(&params.aircraft_type);
+    let units = params.units;
+    let standard_temperature_f = Temperature::standard_temperature(Length::Feet(params.elevation_ft as f64)).fahrenheit();
+
+    let performance = to_performance_parameters(params);
+
+    let mut document = Document::new(format!("{aircraft_name} {} Performance", mode(is_take_off)));
+
+    document.heading("Inputs");
+    if let Some(tail_number) = &params.tail_number {
+        document.text(format!("Tail Number: {tail_number}"));
+    }
+    if let Some(pilot) = &params.pilot {
+        document.text(format!("Pilot: {pilot}"));
+    }
+    if let Some(aircraft_weight_lbs) = params.aircraft_weight_lbs {
+        document.text(format!("Weight: {}", units.pounds(aircraft_weight_lbs)));
+    }
+    document.text(format!("Elevation: {}", units.feet(params.elevation_ft)));
+    if params.headwind_kts >= 0.0 {
+        document.text(format!("Headwind: {:.0} kts", params.headwind_kts));
+    } else {
+        document.text(format!("Tailwind: {:.0} kts", params.headwind_kts.abs()));
+    }
+    document.text(format!("Temperature: {} ({} from the standard {})",
+        units.temperature(Temperature::Fahrenheit(params.temperature_f)),
+        units.temperature_difference_f(params.temperature_f - standard_temperature_f),
+        units.temperature(Temperature::Fahrenheit(standard_temperature_f))));
+    if let Some(pressure_in_hg) = params.pressure_in_hg {
+        document.text(format!("Altimeter: {}", units.pressure(Pressure::InchesOfMercury(pressure_in_hg))));
+    }
+    document.text(format!("Surface: {}", surface(is_grass)));
+    for configuration in aircraft_pages::describe_configurations(&params.aircraft_type, is_take_off, &performance.configurations) {
+        document.text(configuration);
+    }
+    if let Some(runway_length_ft) = params.runway_length_ft {
+        document.text(format!("Runway Length: {}", units.feet(runway_length_ft)));
+    }
+
+    if let Some(metar) = params.metar.as_ref().filter(|metar| !metar.is_empty()) {
+        document.heading("METAR");
+        document.text(metar.as_str());
+    }
+
+    let result = if is_take_off {
+        aircraft_pages::write_pdf_for_take_off(&params.aircraft_type, &performance, &mut document)
+    } else {
+        aircraft_pages::write_pdf_for_landing(&params.aircraft_type, &performance, &mut document)
+    };
+
+    document.heading("Verdict");
+    document.text(verdict(is_take_off, result, params.runway_length_ft, &units));
+    for warning in pilot_warnings(params, result) {
+        document.text(warning);
+    }
+
+    if let Some(summary) = aircraft_pages::speed_summary(&params.aircraft_type, params.aircraft_weight_lbs, &units) {
+        document.heading(format!("Speeds at {}", summary.weight));
+        if let Some(warning) = summary.warning {
+            document.text(warning);
+        }
+        let mut table = vec![vec![String::from("Speed"), String::from("Description"), String::from("KIAS")]];
+        table.extend(summary.speeds.iter().map(|speed| vec![speed.kind.name().to_string(), speed.kind.description().to_string(), format!("{:.0}", speed.kias)]));
+        document.table(table);
+    }
+
+    if let Some(abort_point) = calc_abort_point(&params.aircraft_type, &performance, result, params.runway_length_ft).filter(|_| is_take_off) {
+        document.heading("Abort Point");
+        document.text(format!("Abandon the take off if {:.0} kts is not reached by the {} marker at the runway midpoint, the book ground run reaches it after {}.",
+            abort_point.abort_speed_kias, units.feet(abort_point.marker_ft), units.feet(abort_point.expected_ft)));
+        document.text(format!("Accelerate-stop: {} to rotation and {} to stop, {} of the {} runway.",
+            units.feet(abort_point.ground_run_ft), units.feet(abort_point.stop_ft), units.feet(abort_point.accelerate_stop_ft()), units.feet(abort_point.runway_length_ft)));
+        for warning in abort_warnings(&abort_point, &units) {
+            document.text(warning);
+        }
+    }
+
+    if is_take_off {
+        let (clearances, warnings) = check_obstacles(&params.aircraft_type, &performance, result, params.runway_length_ft, &departure_obstacles(params), &units);
+        if !clearances.is_empty() || !warnings.is_empty() {
+            document.heading("Obstacle Clearance");
+        }
+        if !clearances.is_empty() {
+            let mut table = vec![vec![String::from("Obstacle"), String::from("Height"), String::from("Past Runway End"), String::from("Aircraft Height"), String::from("Margin")]];
+            table.extend(clearances.into_iter().map(|clearance| vec![clearance.name, clearance.height, clearance.distance, clearance.aircraft_height, clearance.margin]));
+            document.table(table);
+        }
+        for warning in warnings {
+            document.text(warning);
+        }
+    }
+
+    document.render()
+}
+
+pub async fn get(Query(query): Query<RunwayQuery>) -> Response {
+    match RunwayParameters::try_from(&query) {
+        Ok(parameters) => get_response(parameters, false, false).await.into_response(),
+        Err(errors) => HtmlTemplate(ErrorTemplate::for_fields(&errors)).into_response()
+    }
 }
 
-pub async fn post(Form(config): Form<RunwayConfig>) -> impl IntoResponse {
-    let metar = metar::Metar::parse(&config.metar).expect("To decode the METAR");
-    let headwind = metar.wind.calc_headwind_component_from_metar_wind_value(config.heading);
-    let temperature = Temperature::Celsius(*metar.temperature.unwrap() as i16);
-    let pressure = Pressure::from_metar(metar).expect("To get the pressure value from the metar");
+pub async fn get_pdf(Query(query): Query<RunwayQuery>) -> Response {
+    let parameters = match RunwayParameters::try_from(&query) {
+        Ok(parameters) => parameters,
+        Err(errors) => return HtmlTemplate(ErrorTemplate::for_fields(&errors)).into_response()
+    };
+    let file_name = format!("{}-{}.pdf", parameters.aircraft_type, mode(parameters.is_take_off.unwrap_or_default()).to_lowercase().replace(' ', "-"));
+    (
+        [
+            (header::CONTENT_TYPE, String::from("application/pdf")),
+            (header::CONTENT_DISPOSITION, format!("inline; filename=\"{file_name}\""))
+        ],
+        render_pdf(&parameters)
+    ).into_response()
+}
 
-    let headwind_kts = headwind.knots();
-    let temperature_f = temperature.fahrenheit();
-    let pressure_in_hg = pressure.in_hg();
+pub struct MetarConditions {
+    pub headwind: Velocity,
+    pub crosswind: Velocity,
+    pub temperature: Temperature,
+    pub pressure: Pressure
+}
 
-    let params = RunwayParameters {
-        aircraft_type: config.aircraft_type,
-        is_take_off: config.is_take_off,
-        is_grass: config.is_grass,
-        headwind_kts,
-        pressure_in_hg: Some(pressure_in_hg),
-        temperature_f,
-        elevation_ft: config.elevation_ft,
-        aircraft_weight_lbs: config.aircraft_weight_lbs
+impl MetarConditions {
+    /// The conditions as if entered on the performance form, so they are validated the same way. The weight is left for the caller.
+    pub fn to_fields(&self, elevation_ft: i16) -> ConditionFields {
+        ConditionFields {
+            elevation_ft: Some(elevation_ft.to_string()),
+            pressure_in_hg: Some(self.pressure.in_hg().to_string()),
+            temperature_c: Some(self.temperature.celsius().to_string()),
+            headwind_kts: Some(self.headwind.knots().to_string()),
+            ..ConditionFields::default()
+        }
+    }
+}
+
+pub fn parse_metar_conditions(raw_metar: &str, true_heading: i16) -> Result<MetarConditions, &'static str> {
+    let metar = metar::Metar::parse(raw_metar).map_err(|_| "Unable to decode the METAR.")?;
+    let headwind = metar.wind.calc_headwind_component_from_metar_wind_value(true_heading)?;
+    let crosswind = metar.wind.calc_crosswind_component(true_heading)?;
+    let temperature = match metar.temperature {
+        metar::Data::Known(c) => Temperature::Celsius(c as f64),
+        metar::Data::Unknown => return Err("Temperature not available.")
     };
-    
-    let start_landing_flow = params.is_take_off.unwrap_or_default();
+    let pressure = Pressure::from_metar(metar)?;
+
+    Ok(MetarConditions { headwind, crosswind, temperature, pressure })
+}
+
+/// A runway picked on the airport page is checked as if its METAR conditions had been entered on the performance form.
+impl TryFrom<RunwayConfig> for RunwayQuery {
+    type Error = Vec<FieldError>;
 
-    get_response(params, start_landing_flow).await
-}
\ No newline at end of file
+    fn try_from(config: RunwayConfig) -> Result<Self, Self::Error> {
+        let conditions = parse_metar_conditions(&config.metar, config.heading)
+            .map_err(|message| vec![FieldError { field: "metar", message: message.to_string() }])?;
+
+        Ok(RunwayQuery {
+            aircraft_type: config.aircraft_type,
+            is_take_off: config.is_take_off,
+            is_grass: config.is_grass,
+            pressure_altitude_method: config.pressure_altitude_method,
+            runway_length_ft: config.length.map(|length| length.to_string()),
+            runway_length_m: None,
+            crosswind_kts: Some(conditions.crosswind.knots()),
+            is_lighted: config.is_lighted,
+            runway_width_ft: config.width,
+            is_heading_inferred: config.is_heading_inferred,
+            airport_ident: config.airport_ident,
+            runway_ident: config.runway_ident,
+            obstacles: None,
+            metar: Some(config.metar),
+            tail_number: config.tail_number,
+            pilot: config.pilot,
+            configuration: ConfigurationFields::default(),
+            conditions: ConditionFields {
+                aircraft_weight_lbs: config.aircraft_weight_lbs.map(|weight| weight.to_string()),
+                aircraft_weight_kg: config.aircraft_weight_kg.map(|weight| weight.to_string()),
+                ..conditions.to_fields(config.elevation_ft)
+            },
+            units: config.units
+        })
+    }
+}
+
+pub async fn post(Form(config): Form<RunwayConfig>) -> Response {
+    let start_landing_flow = config.is_take_off.unwrap_or_default();
+
+    match RunwayQuery::try_from(config).and_then(|query| RunwayParameters::try_from(&query)) {
+        Ok(parameters) => get_response(parameters, start_landing_flow, true).await.into_response(),
+        Err(errors) => HtmlTemplate(ErrorTemplate::for_fields(&errors)).into_response()
+    }
+}
@@ -0,0 +1,248 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::performance::limits::ChartLimits,
+    math::{units::{LengthUnit, PressureUnit, TemperatureUnit, Units, WeightUnit}, Length, Pressure, PressureAltitudeMethod, Temperature, Velocity, Weight}
+};
+
+const ELEVATION_FT: (f64, f64) = (-1500.0, 15000.0);
+const PRESSURE_IN_HG: (f64, f64) = (25.0, 32.5);
+const TEMPERATURE_C: (f64, f64) = (-60.0, 60.0);
+const HEADWIND_KTS: (f64, f64) = (-60.0, 60.0);
+const RUNWAY_LENGTH_FT: (f64, f64) = (1.0, 20000.0);
+
+/// A problem with one submitted field, named as the form names it so the message can be shown beside the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The measured conditions as submitted, kept as text so a value that does not parse is reported against its field.
+#[derive(Default, Deserialize, Serialize)]
+pub struct ConditionFields {
+    pub elevation_ft: Option<String>,
+    pub elevation_m: Option<String>,
+    pub pressure_in_hg: Option<String>,
+    pub pressure_hpa: Option<String>,
+    pub temperature_f: Option<String>,
+    pub temperature_c: Option<String>,
+    pub headwind_kts: Option<String>,
+    pub aircraft_weight_lbs: Option<String>,
+    pub aircraft_weight_kg: Option<String>
+}
+
+/// A parsed value with the field it came from and the units it was entered in, for reporting on it later.
+#[derive(Clone, Copy)]
+pub struct Field<T> {
+    pub name: &'static str,
+    pub value: T,
+    pub units: Units
+}
+
+pub struct Conditions {
+    pub elevation: Option<Field<Length>>,
+    pub pressure: Option<Field<Pressure>>,
+    pub temperature: Option<Field<Temperature>>,
+    pub headwind: Option<Field<Velocity>>,
+    pub aircraft_weight: Option<Field<Weight>>
+}
+
+/// Collects every problem with a submission rather than stopping at the first.
+#[derive(Default)]
+pub struct Validator {
+    pub errors: Vec<FieldError>
+}
+
+impl Validator {
+    pub fn error(&mut self, field: &'static str, message: impl Into<String>) {
+        self.errors.push(FieldError { field, message: message.into() });
+    }
+
+    pub fn finish<T>(self, value: impl FnOnce() -> T) -> Result<T, Vec<FieldError>> {
+        if self.errors.is_empty() {
+            Ok(value())
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// NaN and infinity parse as floats but are not measurements, so they are refused with anything else that is not a number.
+    fn parse<T: FromStr>(&mut self, field: &'static str, label: &str, value: Option<&str>) -> Option<T> {
+        let value = value.map(str::trim).filter(|value| !value.is_empty())?;
+        let is_finite = value.parse::<f64>().map_or(true, f64::is_finite);
+        match value.parse().ok().filter(|_| is_finite) {
+            Some(value) => Some(value),
+            None => {
+                self.error(field, format!("{label} of \"{value}\" is not a number."));
+                None
+            }
+        }
+    }
+
+    /// A measurement which may be given in imperial or metric units, the metric one taking precedence as °C does over °F.
+    fn measurement(&mut self, label: &str, imperial: (&'static str, &Option<String>), metric: (&'static str, &Option<String>), is_required: bool) -> Option<(&'static str, f64, bool)> {
+        let errors = self.errors.len();
+        let metric_value = self.parse(metric.0, label, metric.1.as_deref());
+        let imperial_value = self.parse(imperial.0, label, imperial.1.as_deref());
+
+        match (metric_value, imperial_value) {
+            (Some(value), _) => Some((metric.0, value, true)),
+            (None, Some(value)) => Some((imperial.0, value, false)),
+            (None, None) => {
+                if is_required && errors == self.errors.len() {
+                    self.error(imperial.0, format!("{label} is required."));
+                }
+                None
+            }
+        }
+    }
+
+    fn in_range(&mut self, field: &'static str, label: &str, value: f64, (min, max): (f64, f64), format: impl Fn(f64) -> String) -> bool {
+        if (min..=max).contains(&value) {
+            true
+        } else {
+            self.error(field, format!("{label} of {} is not between {} and {}.", format(value), format(min), format(max)));
+            false
+        }
+    }
+
+    pub fn elevation(&mut self, fields: &ConditionFields) -> Option<Field<Length>> {
+        let (name, value, is_metric) = self.measurement("Elevation", ("elevation_ft", &fields.elevation_ft), ("elevation_m", &fields.elevation_m), true)?;
+        let units = Units { length_unit: if is_metric { LengthUnit::Metres } else { LengthUnit::Feet }, ..Units::default() };
+        let elevation = if is_metric { Length::Metres(value) } else { Length::Feet(value) };
+
+        self.in_range(name, "Elevation", elevation.feet(), ELEVATION_FT, |feet| units.feet(feet))
+            .then_some(Field { name, value: elevation, units })
+    }
+
+    pub fn pressure(&mut self, fields: &ConditionFields) -> Option<Field<Pressure>> {
+        let (name, value, is_metric) = self.measurement("Altimeter setting", ("pressure_in_hg", &fields.pressure_in_hg), ("pressure_hpa", &fields.pressure_hpa), false)?;
+        let units = Units { pressure_unit: if is_metric { PressureUnit::Hpa } else { PressureUnit::InHg }, ..Units::default() };
+        let pressure = if is_metric { Pressure::Hectopascals(value) } else { Pressure::InchesOfMercury(value) };
+
+        self.in_range(name, "Altimeter setting", pressure.in_hg(), PRESSURE_IN_HG, |in_hg| units.pressure(Pressure::InchesOfMercury(in_hg)))
+            .then_some(Field { name, value: pressure, units })
+    }
+
+    /// The temperature is always required, guessing at standard would understate the distances on a hot day.
+    pub fn temperature(&mut self, fields: &ConditionFields) -> Option<Field<Temperature>> {
+        let (name, value, is_metric) = self.measurement("Temperature", ("temperature_f", &fields.temperature_f), ("temperature_c", &fields.temperature_c), true)?;
+        let units = Units { temperature_unit: if is_metric { TemperatureUnit::Celsius } else { TemperatureUnit::Fahrenheit }, ..Units::default() };
+        let temperature = if is_metric { Temperature::Celsius(value) } else { Temperature::Fahrenheit(value) };
+
+        self.in_range(name, "Temperature", temperature.celsius(), TEMPERATURE_C, |c| units.temperature(Temperature::Celsius(c)))
+            .then_some(Field { name, value: temperature, units })
+    }
+
+    pub fn headwind(&mut self, fields: &ConditionFields) -> Option<Field<Velocity>> {
+        let name = "headwind_kts";
+        let errors = self.errors.len();
+        let Some(headwind_kts) = self.parse::<f64>(name, "Headwind", fields.headwind_kts.as_deref()) else {
+            if errors == self.errors.len() {
+                self.error(name, "Headwind is required, use a negative value for a tailwind.");
+            }
+            return None;
+        };
+
+        self.in_range(name, "Headwind", headwind_kts, HEADWIND_KTS, |kts| format!("{kts:.0} kts"))
+            .then_some(Field { name, value: Velocity::Knots(headwind_kts), units: Units::default() })
+    }
+
+    pub fn aircraft_weight(&mut self, fields: &ConditionFields) -> Option<Field<Weight>> {
+        let (name, value, is_metric) = self.measurement("Aircraft weight", ("aircraft_weight_lbs", &fields.aircraft_weight_lbs), ("aircraft_weight_kg", &fields.aircraft_weight_kg), false)?;
+        let units = Units { weight_unit: if is_metric { WeightUnit::Kilograms } else { WeightUnit::Pounds }, ..Units::default() };
+        let weight = if is_metric { Weight::Kilograms(value) } else { Weight::Pounds(value) };
+
+        if weight.pounds() <= 0.0 {
+            self.error(name, "Aircraft weight must be more than zero.");
+            return None;
+        }
+
+        Some(Field { name, value: weight, units })
+    }
+
+    pub fn runway_length(&mut self, length_ft: &Option<String>, length_m: &Option<String>) -> Option<Length> {
+        let (name, value, is_metric) = self.measurement("Runway length", ("runway_length_ft", length_ft), ("runway_length_m", length_m), false)?;
+        let units = Units { length_unit: if is_metric { LengthUnit::Metres } else { LengthUnit::Feet }, ..Units::default() };
+        let length = if is_metric { Length::Metres(value) } else { Length::Feet(value) };
+
+        self.in_range(name, "Runway length", length.feet(), RUNWAY_LENGTH_FT, |feet| units.feet(feet))
+            .then_some(length)
+    }
+
+    pub fn conditions(&mut self, fields: &ConditionFields) -> Conditions {
+        Conditions {
+            elevation: self.elevation(fields),
+            pressure: self.pressure(fields),
+            temperature: self.temperature(fields),
+            headwind: self.headwind(fields),
+            aircraft_weight: self.aircraft_weight(fields)
+        }
+    }
+
+    /// Checks the conditions that parsed against what the aircraft's charts cover.
+    pub fn chart_limits(&mut self, limits: &ChartLimits, is_take_off: bool, conditions: &Conditions, pressure_altitude_method: PressureAltitudeMethod) {
+        match &conditions.aircraft_weight {
+            Some(weight) if weight.value.pounds().round() > limits.max_weight_lbs as f64 => {
+                self.error(weight.name, format!("Aircraft weight of {} is over the charted maximum of {}.", weight.units.weight(weight.value), weight.units.pounds(limits.max_weight_lbs)));
+            },
+            None if is_take_off && limits.take_off_by_weight && !self.has_error("aircraft_weight_lbs") && !self.has_error("aircraft_weight_kg") => {
+                self.error("aircraft_weight_lbs", "Aircraft weight is required, the take off charts are by weight.");
+            },
+            _ => {}
+        }
+
+        if limits.by_pressure_altitude && conditions.pressure.is_none() && !self.has_error("pressure_in_hg") && !self.has_error("pressure_hpa") {
+            self.error("pressure_in_hg", "Altimeter setting is required, the charts are by pressure altitude.");
+        }
+
+        if let Some(elevation) = &conditions.elevation {
+            let (label, altitude) = match (limits.by_pressure_altitude, &conditions.pressure) {
+                (true, Some(pressure)) => ("Pressure altitude", pressure.value.altitude(elevation.value, pressure_altitude_method)),
+                (true, None) => ("Pressure altitude", elevation.value),
+                (false, _) => ("Elevation", elevation.value)
+            };
+
+            if let Some(min_altitude_ft) = limits.min_altitude_ft.filter(|min| altitude.feet() < *min) {
+                self.error(elevation.name, format!("{label} of {} is below the charted {}.", elevation.units.length(altitude), elevation.units.feet(min_altitude_ft)));
+            }
+            if let Some(max_altitude_ft) = limits.max_altitude_ft.filter(|max| altitude.feet() > *max) {
+                self.error(elevation.name, format!("{label} of {} is above the charted {}.", elevation.units.length(altitude), elevation.units.feet(max_altitude_ft)));
+            }
+        }
+
+        if let (Some(temperature), Some(max_temperature_c)) = (&conditions.temperature, limits.max_temperature_c) {
+            if temperature.value.celsius() > max_temperature_c {
+                self.error(temperature.name, format!("Temperature of {} is above the charted {}.", temperature.units.temperature(temperature.value), temperature.units.temperature(Temperature::Celsius(max_temperature_c))));
+            }
+        }
+
+        if let Some(headwind) = &conditions.headwind {
+            let headwind_kts = headwind.value.knots();
+            if headwind_kts < limits.min_headwind_kts {
+                let message = if limits.min_headwind_kts == 0.0 {
+                    String::from("The charts give no correction for a tailwind.")
+                } else {
+                    format!("Tailwind of {:.0} kts is over the {:.0} kts the charts correct for.", headwind_kts.abs(), limits.min_headwind_kts.abs())
+                };
+                self.error(headwind.name, message);
+            }
+            if let Some(max_headwind_kts) = limits.max_take_off_headwind_kts.filter(|max| is_take_off && headwind_kts > *max) {
+                self.error(headwind.name, format!("Headwind of {headwind_kts:.0} kts is over the charted {max_headwind_kts:.0} kts."));
+            }
+        }
+    }
+
+    fn has_error(&self, field: &str) -> bool {
+        self.errors.iter().any(|error| error.field == field)
+    }
+}
@@ -0,0 +1,313 @@
+use std::io::Write;
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 40.0;
+/// Room kept above the bottom margin for the page number.
+const FOOTER_HEIGHT: f32 = 12.0;
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Monospace
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Monospace => "F3"
+        }
+    }
+
+    /// The average glyph width as a fraction of the font size, Courier is exact and Helvetica is rounded up to be safe.
+    fn average_width(self) -> f32 {
+        match self {
+            Font::Regular => 0.52,
+            Font::Bold => 0.58,
+            Font::Monospace => 0.6
+        }
+    }
+}
+
+/// Breaks the text at spaces into lines of at most `max_chars`, a word longer than a line is split.
+/// Runs of spaces within a line are kept so the padding of table columns survives.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line: Option<String> = None;
+
+    for word in text.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        if line.as_ref().is_some_and(|line| line.chars().count() + 1 + word.len() > max_chars) {
+            lines.extend(line.take());
+        }
+        while word.len() > max_chars {
+            lines.push(word.drain(..max_chars).collect());
+        }
+        match &mut line {
+            Some(line) => {
+                line.push(' ');
+                line.extend(word);
+            },
+            None => line = Some(word.into_iter().collect())
+        }
+    }
+    lines.extend(line);
+
+    lines
+}
+
+struct Line {
+    font: Font,
+    size: f32,
+    text: String
+}
+
+/// A text-only PDF built from the standard 14 fonts so no font files or external tools are needed.
+/// Text wider than the page is wrapped and content that does not fit on a page continues on the next, so nothing of a dispatch record is lost.
+pub struct Document {
+    title: String,
+    lines: Vec<Line>
+}
+
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars().flat_map(|c| {
+        let byte = match c {
+            '(' | ')' | '\\' => return vec![b'\\', c as u8],
+            ' '..='~' => c as u8,
+            '°' => 0xB0,
+            'º' => 0xBA,
+            '×' => 0xD7,
+            '—' => 0x97,
+            '–' | '−' => b'-',
+            '≈' => b'~',
+            _ => b'?'
+        };
+        vec![byte]
+    }).collect()
+}
+
+impl Document {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        let title = title.into();
+        let mut document = Document { title: title.clone(), lines: vec![] };
+        document.push(Font::Bold, 14.0, title);
+        document
+    }
+
+    fn push(&mut self, font: Font, size: f32, text: String) {
+        let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / (size * font.average_width())) as usize;
+        self.lines.extend(wrap(&text, max_chars).into_iter().map(|text| Line { font, size, text }));
+    }
+
+    pub fn heading<S: Into<String>>(&mut self, text: S) {
+        self.push(Font::Bold, 11.0, text.into());
+    }
+
+    pub fn text<S: Into<String>>(&mut self, text: S) {
+        self.push(Font::Regular, 9.0, text.into());
+    }
+
+    /// Lays the cells out in fixed width columns using a monospaced font.
+    pub fn table(&mut self, rows: Vec<Vec<String>>) {
+        let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..column_count)
+            .map(|i| rows.iter().filter_map(|row| row.get(i)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+            .collect();
+
+        for row in rows {
+            let line = row.iter().enumerate()
+                .map(|(i, cell)| format!("{cell:>width$}", width = widths[i]))
+                .collect::<Vec<String>>()
+                .join("  ");
+            self.push(Font::Monospace, 8.0, line);
+        }
+    }
+
+    /// Splits the lines into pages, each starting at the top margin.
+    fn pages(&self) -> Vec<&[Line]> {
+        let mut pages = Vec::new();
+        let mut start = 0;
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        for (i, line) in self.lines.iter().enumerate() {
+            let leading = line.size * 1.4;
+            if y - leading < MARGIN + FOOTER_HEIGHT && i > start {
+                pages.push(&self.lines[start..i]);
+                start = i;
+                y = PAGE_HEIGHT - MARGIN;
+            }
+            y -= leading;
+        }
+        pages.push(&self.lines[start..]);
+
+        pages
+    }
+
+    fn content_stream(lines: &[Line], footer: Option<String>) -> Vec<u8> {
+        let mut content = Vec::new();
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        for line in lines {
+            y -= line.size * 1.4;
+            content.extend_from_slice(format!("BT /{} {} Tf {MARGIN} {y:.1} Td (", line.font.resource_name(), line.size).as_bytes());
+            content.extend(encode_win_ansi(&line.text));
+            content.extend_from_slice(b") Tj ET\n");
+        }
+
+        if let Some(footer) = footer {
+            content.extend_from_slice(format!("BT /F1 8 Tf {MARGIN} {MARGIN} Td (").as_bytes());
+            content.extend(encode_win_ansi(&footer));
+            content.extend_from_slice(b") Tj ET\n");
+        }
+
+        content
+    }
+
+    pub fn render(&self) -> Vec<u8> {
+        let pages = self.pages();
+        let page_count = pages.len();
+        let font = |base_font: &str| format!("<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>").into_bytes();
+
+        let mut title = b"(".to_vec();
+        title.extend(encode_win_ansi(&self.title));
+        title.extend_from_slice(b")");
+
+        // The catalog and page tree, a page and its content stream for each page, then the fonts and the info.
+        let font_object = 3 + 2 * page_count;
+        let kids = (0..page_count).map(|i| format!("{} 0 R", 3 + 2 * i)).collect::<Vec<String>>().join(" ");
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!("<< /Type /Pages /Kids [{kids}] /Count {page_count} >>").into_bytes()
+        ];
+
+        for (i, lines) in pages.into_iter().enumerate() {
+            let footer = (page_count > 1).then(|| format!("Page {} of {page_count}", i + 1));
+            let content = Self::content_stream(lines, footer);
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend(content);
+            stream.extend_from_slice(b"\nendstream");
+
+            objects.push(format!("<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Contents {} 0 R /Resources << /Font << /F1 {} 0 R /F2 {} 0 R /F3 {} 0 R >> >> >>",
+                4 + 2 * i, font_object, font_object + 1, font_object + 2).into_bytes());
+            objects.push(stream);
+        }
+
+        objects.extend([
+            font("Helvetica"),
+            font("Helvetica-Bold"),
+            font("Courier"),
+            [b"<< /Title ".to_vec(), title, b" /Producer (aircraft-performance) >>".to_vec()].concat()
+        ]);
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            writeln!(pdf, "{} 0 obj", i + 1).unwrap();
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).unwrap();
+        for offset in offsets {
+            writeln!(pdf, "{offset:010} 00000 n ").unwrap();
+        }
+        write!(pdf, "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n", objects.len() + 1, objects.len()).unwrap();
+
+        pdf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pdf: &[u8], needle: &str) -> Option<usize> {
+        pdf.windows(needle.len()).position(|window| window == needle.as_bytes())
+    }
+
+    /// The offsets from the xref table, after the free entry for object 0.
+    fn xref_offsets(pdf: &[u8]) -> Vec<usize> {
+        // Everything from the xref table on is ASCII, the content before it need not be UTF-8.
+        let tail = std::str::from_utf8(&pdf[find(pdf, "xref\n").unwrap()..]).unwrap();
+        let startxref: usize = tail.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert_eq!(&pdf[startxref..startxref + 5], b"xref\n");
+
+        std::str::from_utf8(&pdf[startxref..]).unwrap().lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect()
+    }
+
+    fn assert_structure(pdf: &[u8]) -> usize {
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let offsets = xref_offsets(pdf);
+        for (i, offset) in offsets.iter().enumerate() {
+            let header = format!("{} 0 obj\n", i + 1);
+            assert_eq!(&pdf[*offset..*offset + header.len()], header.as_bytes(), "object {} is not at its xref offset", i + 1);
+        }
+        assert!(find(pdf, &format!("trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>", offsets.len() + 1, offsets.len())).is_some());
+
+        offsets.len()
+    }
+
+    #[test]
+    fn a_short_document_is_one_page_with_every_object_in_the_xref() {
+        let mut document = Document::new("Take Off (Book)");
+        document.heading("Inputs");
+        document.text("Temperature: 15°C");
+        document.table(vec![vec![String::from("Vr"), String::from("55")]]);
+        let pdf = document.render();
+
+        assert_eq!(assert_structure(&pdf), 8);
+        assert!(find(&pdf, "/Count 1 >>").is_some());
+        assert!(find(&pdf, "/Title (Take Off \\(Book\\))").is_some());
+        assert!(pdf.windows(b"(Temperature: 15\xB0C)".len()).any(|window| window == b"(Temperature: 15\xB0C)"));
+        assert!(find(&pdf, "Page 1 of").is_none());
+    }
+
+    #[test]
+    fn a_long_document_continues_on_further_pages() {
+        let mut document = Document::new("Chart");
+        for i in 0..200 {
+            document.text(format!("Row {i}"));
+        }
+        let pdf = document.render();
+
+        let object_count = assert_structure(&pdf);
+        let page_count = (object_count - 6) / 2;
+        assert!(page_count > 1);
+        assert!(find(&pdf, &format!("/Count {page_count} >>")).is_some());
+        assert!(find(&pdf, &format!("(Page {page_count} of {page_count})")).is_some());
+        assert!((0..200).all(|i| find(&pdf, &format!("(Row {i})")).is_some()));
+    }
+
+    #[test]
+    fn long_text_is_wrapped_within_the_margins() {
+        let mut document = Document::new("Verdict");
+        document.text("NO-GO: ".repeat(40));
+        document.text("x".repeat(250));
+
+        let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / (9.0 * Font::Regular.average_width())) as usize;
+        let lines = &document.lines[1..];
+        assert!(lines.len() > 2);
+        assert!(lines.iter().all(|line| line.text.chars().count() <= max_chars));
+        assert_eq!(lines.iter().map(|line| line.text.matches("NO-GO:").count()).sum::<usize>(), 40);
+        assert_eq!(lines.iter().map(|line| line.text.matches('x').count()).sum::<usize>(), 250);
+    }
+
+    #[test]
+    fn wrapping_breaks_between_words() {
+        assert_eq!(wrap("Decrease distances 10%", 12), ["Decrease", "distances", "10%"]);
+        assert_eq!(wrap("abcdefgh ij", 5), ["abcde", "fgh", "ij"]);
+        assert_eq!(wrap("", 5), [""]);
+        assert_eq!(wrap("  Vr  55", 12), ["  Vr  55"]);
+    }
+}
@@ -0,0 +1,51 @@
+use std::{env, fs, path::PathBuf, process::{Command, Output}};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Runs the binary on a copy of the fixtures so nothing is written to the source tree.
+fn run(name: &str, store: Option<&str>, args: &[&str]) -> (Output, PathBuf) {
+    let data_dir = env::temp_dir().join(format!("aircraft-performance-cli-{name}-{}", std::process::id()));
+    fs::create_dir_all(&data_dir).unwrap();
+    for file in ["airports.csv", "runways.csv", "obstacles.csv"] {
+        fs::copy(format!("{FIXTURES}/{file}"), data_dir.join(file)).unwrap();
+    }
+    if let Some(store) = store {
+        fs::write(data_dir.join("store.json"), store).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aircraft-performance"))
+        .arg(format!("--data-dir={}", data_dir.display()))
+        .args(args)
+        .output()
+        .unwrap();
+    (output, data_dir)
+}
+
+#[test]
+fn runway_pdf_writes_the_document() {
+    let path = env::temp_dir().join(format!("aircraft-performance-cli-{}.pdf", std::process::id()));
+    let (output, data_dir) = run("pdf", None, &["runway-pdf", "aircraft_type=cessna172m&is_take_off=true&headwind_kts=5&pressure_in_hg=29.92&temperature_f=59&elevation_ft=900&aircraft_weight_lbs=2300", path.to_str().unwrap()]);
+    fs::remove_dir_all(data_dir).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let pdf = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+}
+
+#[test]
+fn runway_pdf_lists_the_field_errors() {
+    let (output, data_dir) = run("errors", None, &["runway-pdf", "aircraft_type=cessna172m&is_take_off=true&elevation_ft=900"]);
+    fs::remove_dir_all(data_dir).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("temperature_f: Temperature is required."), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn a_corrupt_store_is_reported_at_startup() {
+    let (output, data_dir) = run("store", Some("{ \"aircraft\": ["), &["runway-pdf", "aircraft_type=cessna150j"]);
+    fs::remove_dir_all(data_dir).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("store.json is not a valid store"), "{}", String::from_utf8_lossy(&output.stderr));
+}
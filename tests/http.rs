@@ -0,0 +1,483 @@
+use std::{collections::HashMap, env, fs, net::SocketAddr, path::PathBuf, sync::OnceLock, thread};
+
+use aircraft_performance::{config::Config, router};
+use axum::{
+    body::{to_bytes, Body},
+    extract::Query,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router
+};
+use tower::ServiceExt;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Serves the fixture METARs for KANE in the aviationweather.gov text format, nothing for anywhere else.
+async fn metars(Query(query): Query<HashMap<String, String>>) -> String {
+    match query.get("ids").map(String::as_str) {
+        Some("KANE") => fs::read_to_string(format!("{FIXTURES}/metars.txt")).unwrap(),
+        _ => String::new()
+    }
+}
+
+/// The stub runs on its own thread and runtime so it outlives each test's runtime.
+fn start_weather_stub() -> SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    listener.set_nonblocking(true).unwrap();
+
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+            axum::serve(listener, Router::new().route("/api/data/metar", get(metars))).await.unwrap();
+        });
+    });
+
+    address
+}
+
+/// The fixtures are copied so the calculation history is written outside the source tree.
+fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let data_dir = env::temp_dir().join(format!("aircraft-performance-http-{}", std::process::id()));
+        fs::create_dir_all(&data_dir).unwrap();
+        for file in ["airports.csv", "runways.csv", "obstacles.csv"] {
+            fs::copy(format!("{FIXTURES}/{file}"), data_dir.join(file)).unwrap();
+        }
+
+        Config {
+            data_dir,
+            static_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/static")),
+            weather_url: format!("http://{}/api/data/metar", start_weather_stub()),
+            ..Config::default()
+        }
+    })
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, String) {
+    let response = router(config().clone()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn get_page(uri: &str) -> (StatusCode, String) {
+    send(Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+/// For responses that are not text, with their content type.
+async fn get_file(uri: &str) -> (StatusCode, String, Vec<u8>) {
+    let response = router(config().clone()).oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let content_type = response.headers().get(header::CONTENT_TYPE).map(|value| value.to_str().unwrap().to_string()).unwrap_or_default();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, body.to_vec())
+}
+
+async fn post_form(uri: &str, form: &[(&str, &str)]) -> (StatusCode, String) {
+    let body = serde_urlencoded::to_string(form).unwrap();
+    send(Request::post(uri).header(header::CONTENT_TYPE, "application/x-www-form-urlencoded").body(Body::from(body)).unwrap()).await
+}
+
+#[tokio::test]
+async fn departure_lists_the_metars_from_the_weather_service() {
+    let (status, body) = get_page("/airport/KANE/departure/cessna172m").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Departure Airport: KANE - Anoka County-Blaine Airport"));
+    assert!(body.contains("KANE 191153Z 36008KT 10SM FEW250 18/06 A3002"));
+    assert!(body.contains("KANE 191053Z 35006KT 10SM CLR 16/06 A3001"));
+}
+
+#[tokio::test]
+async fn departure_with_a_custom_metar_shows_it() {
+    let (status, body) = get_page("/airport/XGRS/departure/cessna150j?custom_metar=XGRS+191200Z+04010KT+10SM+CLR+20%2F10+A2992").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Test Grass Strip"));
+    assert!(body.contains("XGRS 191200Z 04010KT 10SM CLR 20/10 A2992"));
+}
+
+#[tokio::test]
+async fn departure_flags_closed_runways_and_shows_lighting() {
+    let (status, body) = get_page("/airport/XGRS/departure/cessna150j?custom_metar=XGRS+191200Z+04010KT+10SM+CLR+20%2F10+A2992").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.matches("<button>Select</button>").count(), 4);
+    assert_eq!(body.matches("CLOSED").count(), 2);
+    assert!(body.contains("30&#x27; (narrow)"));
+}
+
+#[tokio::test]
+async fn departure_lists_only_runways_the_aircraft_can_use() {
+    let custom_metar = "custom_metar=XGRS+191200Z+04010KT+10SM+CLR+20%2F10+A2992";
+    let (_, body) = get_page(&format!("/airport/XGRS/departure/cessna150j?{custom_metar}")).await;
+    assert!(!body.contains("<td>H1</td>"));
+    assert!(!body.contains("<td>18W</td>"));
+    assert!(body.contains("(2 here)"));
+    assert!(body.contains("<td>A</td>"));
+    assert!(body.contains("value=\"45\">045°M / 045°T (inferred from the threshold coordinates)"));
+    assert!(body.contains("value=\"2567\">2567&#x27; (between the threshold coordinates)"));
+
+    post_form("/fleet", &[("tail_number", "N150SP"), ("aircraft_type", "cessna150j"), ("empty_weight_lbs", "1100"), ("is_seaplane", "true")]).await;
+    let (_, body) = get_page(&format!("/airport/XGRS/departure/cessna150j?tail_number=N150SP&{custom_metar}")).await;
+    assert!(body.contains("<td>18W</td>"));
+    assert!(body.contains("(1 here)"));
+}
+
+#[tokio::test]
+async fn card_refuses_a_closed_runway() {
+    let (status, body) = get_page("/card?identifier=XGRS&runway=18&aircraft_type=cessna150j").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Runway 18 at XGRS is closed."));
+}
+
+#[tokio::test]
+async fn card_refuses_a_temperature_range_too_wide_to_count() {
+    let (status, body) = get_page("/card?identifier=KANE&runway=36&aircraft_type=cessna150j&temperature_min_c=-32768&temperature_max_c=32767&temperature_step_c=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("The temperature range has more than 25 steps."));
+}
+
+#[tokio::test]
+async fn card_refuses_altimeter_ranges_it_can_not_count() {
+    let (_, body) = get_page("/card?identifier=KANE&runway=36&aircraft_type=cessna150j&pressure_step_in_hg=1e-300").await;
+    assert!(body.contains("The altimeter range has more than 25 steps."));
+
+    let (_, body) = get_page("/card?identifier=KANE&runway=36&aircraft_type=cessna150j&pressure_max_in_hg=inf").await;
+    assert!(body.contains("The altimeter range is invalid."));
+}
+
+#[tokio::test]
+async fn card_uses_the_defaults_for_cleared_fields() {
+    let (status, body) = get_page("/card?identifier=KANE&runway=36&aircraft_type=cessna150j&headwind_kts=&temperature_min_c=&temperature_max_c=&temperature_step_c=&pressure_min_in_hg=&pressure_max_in_hg=&pressure_step_in_hg=").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains("<title>Error</title>"));
+}
+
+#[tokio::test]
+async fn airport_post_finds_the_airport_without_the_k_prefix() {
+    let (status, body) = post_form("/airport", &[("identifier", "ane"), ("aircraft_type", "cessna150j")]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Departure Airport: KANE"));
+}
+
+#[tokio::test]
+async fn unknown_airport_renders_the_error_page() {
+    let (status, body) = get_page("/airport/KZZZ/arrival/cessna150j").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("KZZZ not found."));
+}
+
+#[tokio::test]
+async fn runway_get_compares_the_distance_with_the_runway() {
+    let (status, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&aircraft_weight_lbs=2300&elevation_ft=912&headwind_kts=5&temperature_c=18&pressure_in_hg=30.02&runway_length_ft=4855").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Take Off Performance</title>"));
+    assert!(body.contains("GO: "));
+    assert!(body.contains("is within the 4855&#x27; runway."));
+}
+
+#[tokio::test]
+async fn runway_get_checks_the_departure_obstacles() {
+    let (status, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&aircraft_weight_lbs=2300&elevation_ft=905&headwind_kts=5&temperature_c=18&pressure_in_hg=30.02&runway_length_ft=4001&airport_ident=KANE&runway_ident=09&obstacles=Mast+900%40500").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<h3>Obstacle Clearance</h3>"));
+    assert!(body.contains("<td>Water Tower</td>"));
+    assert_eq!(body.matches("(cannot out-climb)").count(), 1);
+    assert!(body.contains("Cannot out-climb Mast 900&#x27; tall 500&#x27; past the runway end"));
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&aircraft_weight_lbs=2300&elevation_ft=905&headwind_kts=5&temperature_c=18&pressure_in_hg=30.02&obstacles=Mast+900").await;
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("Expected height@distance but found &quot;Mast 900&quot;."));
+}
+
+#[tokio::test]
+async fn runway_get_marks_the_abort_point() {
+    let (status, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&aircraft_weight_lbs=2300&elevation_ft=912&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92&runway_length_ft=4000").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<h3>Abort Point</h3>"));
+    assert!(body.contains("Abandon the take off if 38 kts is not reached by the 2000&#x27; marker at the runway midpoint"));
+    assert!(body.contains("class=\"marker abort\" style=\"left: 50.0%\""));
+    assert!(!body.contains("more than the"));
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&aircraft_weight_lbs=2300&elevation_ft=912&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92&runway_length_ft=800").await;
+    assert!(body.contains("past the 400&#x27; runway midpoint where the take off should be abandoned without it."));
+    assert!(body.contains("more than the 800&#x27; runway."));
+}
+
+#[tokio::test]
+async fn runway_get_adjusts_for_the_configuration() {
+    let query = "/runway?aircraft_type=cessna172m&aircraft_weight_lbs=2300&elevation_ft=912&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92";
+    let (_, body) = get_page(&format!("{query}&is_take_off=true")).await;
+    assert!(body.contains("<li>Take Off Technique: Short field, flaps 10° (book)</li>"));
+    assert!(!body.contains("book distances are adjusted for the technique"));
+
+    let (status, body) = get_page(&format!("{query}&landing_flaps=flaps_0&approach=power_on")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<li>Landing Flaps: Flaps 0°</li>"));
+    assert!(body.contains("<li>Approach: Power on</li>"));
+    assert!(body.contains("book distances are adjusted for the technique and flap setting."));
+
+    let (_, body) = get_page(&format!("{query}&is_take_off=true&take_off_technique=soft_field")).await;
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("Take Off Technique soft_field is not one of the configurations for this aircraft."));
+}
+
+#[tokio::test]
+async fn runway_get_shows_the_speeds_for_the_weight() {
+    let (status, body) = get_page("/runway?aircraft_type=cessna172m&aircraft_weight_lbs=2100&is_take_off=true&elevation_ft=912&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<th>Vr</th>"));
+    assert!(body.contains("<td>53 KIAS</td>"));
+    assert!(body.contains("<td>57 KIAS</td>"));
+}
+
+#[tokio::test]
+async fn speeds_are_returned_as_json() {
+    let (status, body) = get_page("/aircraft/cessna172m/speeds?aircraft_weight_lbs=2200").await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["aircraft_weight_lbs"], 2200.0);
+    assert_eq!(json["speeds"][0]["kind"], "Vr");
+    assert_eq!(json["speeds"][0]["kias"], 54.0);
+    assert!(json.get("aircraft_weight_kg").is_none());
+
+    let (_, body) = get_page("/aircraft/cessna172m/speeds?aircraft_weight_kg=998&weight_unit=kilograms").await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["aircraft_weight_lbs"], 2200.0);
+    assert_eq!(json["aircraft_weight_kg"], 998.0);
+
+    let (status, _) = get_page("/aircraft/piper/speeds").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn speeds_off_the_table_are_for_its_nearest_weight() {
+    let (status, body) = get_page("/aircraft/cessna172m/speeds?aircraft_weight_lbs=1500").await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["aircraft_weight_lbs"], 1900.0);
+    assert_eq!(json["speeds"][0]["kias"], 50.0);
+    assert_eq!(json["warning"], "The weight of 1500 lbs is off the speed table, the speeds are for 1900 lbs.");
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172m&aircraft_weight_lbs=1500&is_take_off=true&elevation_ft=912&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92").await;
+    assert!(body.contains("Speeds at 1900 lbs"));
+    assert!(body.contains("The weight of 1500 lbs is off the speed table, the speeds are for 1900 lbs."));
+}
+
+#[tokio::test]
+async fn runway_get_reads_the_warrior_graphs_by_density_altitude() {
+    let (status, body) = get_page("/runway?aircraft_type=pa28161&aircraft_weight_lbs=2325&is_take_off=true&elevation_ft=0&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92&runway_length_ft=2000").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("GO: 1490&#x27; to clear a 50&#x27; obstacle is within the 2000&#x27; runway."));
+    assert!(body.contains("Reading the graphs at 2325 lbs."));
+
+    let (_, body) = get_page("/runway?aircraft_type=pa28161&aircraft_weight_lbs=2325&is_take_off=true&elevation_ft=0&headwind_kts=-6&temperature_c=15&pressure_in_hg=29.92").await;
+    assert!(body.contains("Tailwind of 6 kts is over the 5 kts the charts correct for."));
+}
+
+#[tokio::test]
+async fn aircraft_page_reads_the_182t_tables_with_its_notes() {
+    let (status, body) = get_page("/aircraft/cessna182t/take-off?elevation_ft=2000&headwind_kts=11&temperature_c=20&pressure_in_hg=29.92&aircraft_weight_lbs=2900").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Cessna 182 T Take Off Performance</title>"));
+    assert!(body.contains("Using the 3100 lbs weight table."));
+    assert!(body.contains("Decrease distances 10% for each 11 knots headwind."));
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172s&aircraft_weight_lbs=2550&is_take_off=true&elevation_ft=2000&headwind_kts=0&temperature_c=20&pressure_in_hg=29.92&runway_length_ft=2500").await;
+    assert!(body.contains("GO: 2110&#x27; to clear a 50&#x27; obstacle is within the 2500&#x27; runway."));
+}
+
+#[tokio::test]
+async fn runway_get_lists_the_field_errors() {
+    let (status, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&elevation_ft=912&headwind_kts=-12&pressure_in_hg=30.02").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("temperature_f: Temperature is required."));
+    assert!(body.contains("aircraft_weight_lbs: Aircraft weight is required, the take off charts are by weight."));
+    assert!(body.contains("headwind_kts: Tailwind of 12 kts is over the 10 kts the charts correct for."));
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172m&is_take_off=true&elevation_ft=912&headwind_kts=0&temperature_f=inf&aircraft_weight_lbs=NaN").await;
+    assert!(body.contains("aircraft_weight_lbs: Aircraft weight of &quot;NaN&quot; is not a number."));
+    assert!(body.contains("temperature_f: Temperature of &quot;inf&quot; is not a number."));
+}
+
+#[tokio::test]
+async fn runway_post_works_out_the_wind_from_the_metar() {
+    let (status, body) = post_form("/runway", &[
+        ("aircraft_type", "cessna150j"),
+        ("metar", "KANE 191153Z 36008KT 10SM FEW250 18/06 A3002"),
+        ("heading", "360"),
+        ("elevation_ft", "909"),
+        ("length", "4855")
+    ]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Landing Performance</title>"));
+    assert!(body.contains("<li>Headwind: 8 kts</li>"));
+    assert!(body.contains("GO: "));
+}
+
+#[tokio::test]
+async fn runway_post_warns_of_an_unlit_narrow_runway() {
+    let (status, body) = post_form("/runway", &[
+        ("aircraft_type", "cessna150j"),
+        ("metar", "XGRS 191200Z 09010KT 10SM CLR 20/10 A2992"),
+        ("heading", "40"),
+        ("elevation_ft", "1200"),
+        ("length", "2200"),
+        ("is_lighted", "false"),
+        ("width", "30"),
+        ("is_heading_inferred", "true")
+    ]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("The runway heading was inferred rather than surveyed, check the wind components against a chart."));
+    assert!(body.contains("The runway is not lighted, it can only be used in daylight."));
+    assert!(body.contains("The runway is only 30&#x27; wide, leaving less room to correct for the 8 kts crosswind."));
+}
+
+/// The history entry a page links to, if it was saved.
+fn history_link(body: &str) -> Option<&str> {
+    let start = body.find("href=\"/history/")? + "href=\"".len();
+    body[start..].split('"').next()
+}
+
+#[tokio::test]
+async fn runway_post_records_a_calculation_once() {
+    let form = [
+        ("aircraft_type", "cessna150j"),
+        ("metar", "KANE 191153Z 36006KT 10SM FEW250 18/06 A3002"),
+        ("heading", "360"),
+        ("elevation_ft", "909"),
+        ("length", "4855")
+    ];
+    let (_, first) = post_form("/runway", &form).await;
+    let (_, second) = post_form("/runway", &form).await;
+    let link = history_link(&first).expect("The calculation to be saved to the history");
+    assert_eq!(history_link(&second), Some(link));
+
+    let (_, body) = get_page(link).await;
+    assert!(body.contains("Mode: Landing"));
+    assert!(body.contains("Recalculate"));
+
+    let (_, body) = get_page("/runway?aircraft_type=cessna172m&aircraft_weight_lbs=2250&is_take_off=true&elevation_ft=913&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92").await;
+    assert!(history_link(&body).is_none());
+}
+
+#[tokio::test]
+async fn cards_and_flight_plans_are_recorded() {
+    let (_, body) = get_page("/card?identifier=KANE&runway=36&aircraft_type=cessna150j&headwind_kts=3").await;
+    let (_, entry) = get_page(history_link(&body).expect("The card to be saved to the history")).await;
+    assert!(entry.contains("Mode: Card"));
+    assert!(entry.contains("Card for KANE runway 36."));
+
+    let (_, body) = post_form("/plan", &[
+        ("aircraft_type", "cessna150j"), ("take_off_weight_lbs", "1550"), ("fuel_burn_lbs", "50"),
+        ("departure_identifier", "KANE"), ("departure_runway", "36"), ("departure_metar", "KANE 191153Z 36004KT 10SM FEW250 18/06 A3002"),
+        ("destination_identifier", "XGRS"), ("destination_runway", "04"), ("destination_metar", "XGRS 191200Z 04004KT 10SM CLR 20/10 A2992")
+    ]).await;
+    assert_eq!(body.matches("Saved to history").count(), 2);
+    let (_, entry) = get_page(history_link(&body).unwrap()).await;
+    assert!(entry.contains("Mode: Take Off"));
+    assert!(!entry.contains("Recalculate"));
+}
+
+#[tokio::test]
+async fn runway_post_lists_the_field_errors() {
+    let runway = |metar| vec![("aircraft_type", "cessna150j"), ("metar", metar), ("heading", "180"), ("elevation_ft", "909"), ("length", "4855")];
+
+    let (status, body) = post_form("/runway", &runway("not a metar")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("metar: Unable to decode the METAR."));
+
+    let (_, body) = post_form("/runway", &runway("KANE 191153Z /////KT 10SM FEW250 18/06 A3002")).await;
+    assert!(body.contains("metar: Wind not available."));
+
+    let (_, body) = post_form("/runway", &runway("KANE 191153Z 36010KT 10SM FEW250 18/06 A3002")).await;
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("headwind_kts: The charts give no correction for a tailwind."));
+}
+
+#[tokio::test]
+async fn aircraft_endpoints_render_the_calculation() {
+    let (status, body) = get_page("/aircraft/cessna150j/take-off?elevation_ft=900&headwind_kts=5&temperature_f=70").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Cessna 150 J Take Off Performance</title>"));
+    assert!(!body.contains("NO-GO"));
+
+    let (status, body) = get_page("/aircraft/cessna172m/landing?elevation_ft=900&headwind_kts=0&temperature_c=15&pressure_in_hg=29.92").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Cessna 172 M Landing Performance</title>"));
+    assert!(!body.contains("NO-GO"));
+}
+
+#[tokio::test]
+async fn aircraft_endpoints_report_deleted_chart_cells() {
+    let (status, body) = get_page("/aircraft/cessna172m/take-off?elevation_ft=7000&headwind_kts=0&temperature_c=35&pressure_in_hg=29.92&aircraft_weight_lbs=2300").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("NO-GO"));
+    assert!(body.contains("climb below 150 fpm"));
+}
+
+#[tokio::test]
+async fn aircraft_endpoints_list_the_field_errors() {
+    let (status, body) = get_page("/aircraft/cessna150j/landing?elevation_ft=abc&headwind_kts=-3&temperature_f=70").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("elevation_ft: Elevation of &quot;abc&quot; is not a number."));
+    assert!(body.contains("headwind_kts: The charts give no correction for a tailwind."));
+}
+
+#[tokio::test]
+async fn runway_pdf_is_a_complete_document() {
+    let (status, content_type, pdf) = get_file("/runway.pdf?aircraft_type=cessna172m&is_take_off=true&headwind_kts=5&pressure_in_hg=29.92&temperature_f=59&elevation_ft=900&aircraft_weight_lbs=2300&runway_length_ft=2500").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/pdf");
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+    for text in ["(Cessna 172 M Take Off Performance)", "(Verdict)", "(Speeds at 2300 lbs)"] {
+        assert!(pdf.windows(text.len()).any(|window| window == text.as_bytes()), "{text} is missing");
+    }
+}
+
+#[tokio::test]
+async fn plan_refuses_fuel_burns_outside_the_take_off_weight() {
+    let plan = |fuel_burn_lbs: &'static str, alternate_fuel_burn_lbs: &'static str| [
+        ("aircraft_type", "cessna172m"), ("take_off_weight_lbs", "2300"), ("fuel_burn_lbs", fuel_burn_lbs), ("alternate_fuel_burn_lbs", alternate_fuel_burn_lbs),
+        ("departure_identifier", "KANE"), ("departure_runway", "36"), ("destination_identifier", "XGRS"), ("destination_runway", "04")
+    ];
+
+    for fuel_burn_lbs in ["-50", "2300", "32767"] {
+        let (status, body) = post_form("/plan", &plan(fuel_burn_lbs, "0")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("The fuel burn to the destination must be at least 0 and less than the take off weight."), "{fuel_burn_lbs}");
+    }
+
+    let (_, body) = post_form("/plan", &plan("100", "2200")).await;
+    assert!(body.contains("The fuel burn to the alternate must be at least 0 and less than the landing weight at the destination."));
+}
+
+#[tokio::test]
+async fn plan_checks_each_leg_against_the_charts() {
+    let (status, body) = post_form("/plan", &[
+        ("aircraft_type", "cessna172m"), ("take_off_weight_lbs", "2500"), ("fuel_burn_lbs", "100"),
+        ("departure_identifier", "KANE"), ("departure_runway", "36"), ("departure_metar", "KANE 191153Z /////KT 10SM FEW250 18/06 A3002"),
+        ("destination_identifier", "XGRS"), ("destination_runway", "04"), ("destination_metar", "XGRS 191200Z 04005KT 10SM CLR 20/10 A2992")
+    ]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("NO-GO: Wind not available."));
+    assert!(body.contains("NO-GO: Aircraft weight of 2400 lbs is over the charted maximum of 2300 lbs."));
+}
+
+#[tokio::test]
+async fn fleet_refuses_factors_that_shorten_the_book_distances() {
+    for factor_percentage in ["-50", "NaN", "inf"] {
+        let (status, body) = post_form("/fleet", &[("tail_number", "N172XX"), ("aircraft_type", "cessna172m"), ("empty_weight_lbs", "1500"), ("take_off_factor_percentage", factor_percentage), ("landing_factor_percentage", "10")]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("take_off_factor_percentage: A factor of"), "{factor_percentage}");
+    }
+
+    let (_, body) = get_page("/fleet").await;
+    assert!(!body.contains("N172XX"));
+}